@@ -1,17 +1,21 @@
 use evesharedcache::cache::{CacheError, SharedCache};
-use evestaticdata::sde::load::{SDELoadError, SDELoader, TypeList};
+use evestaticdata::sde::load::{SDELoadError, SDELoader, SDE_Full, Type, TypeDogma, TypeList};
 use evestaticdata::types::{ids, values};
+use image::codecs::jpeg::JpegEncoder;
 use image::imageops::FilterType;
 use image::{DynamicImage, ImageFormat, ImageReader, imageops};
 use image_blend::BufferBlend;
-use serde::Serialize;
+use rayon::prelude::*;
+use serde::{Serialize, Serializer};
+use std::borrow::Cow;
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::hash_map::Entry;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 use std::fs::File;
-use std::io::{BufRead, BufReader, ErrorKind};
+use std::io::{BufRead, BufReader, BufWriter, ErrorKind};
 use std::io::{Cursor, Write};
-use std::path::{Path};
+use std::path::{Path, PathBuf};
 use std::{fs, io};
 use zip::write::FileOptions;
 use zip::{CompressionMethod, ZipWriter};
@@ -19,13 +23,25 @@ use zip::{CompressionMethod, ZipWriter};
 // Industry "reaction" blueprints use a different background
 const REACTION_GROUPS: [u32; 4] = [1888, 1889, 1890, 4097];
 
+// Typelist "RenderableTypeIDs", types for which the game has renders
+const RENDERABLE_TYPELIST: ids::TypeListID = 140;
+
+// Header record of the icon index (cache.csv), followed by the recipe version
+const INDEX_HEADER_PREFIX: &str = "recipe:";
+
 pub mod hash {
     //! Not used for security, using md5 as it's good enough and has consistency with EVE Online's sharedcache.
     //! Actual hash algorithm subject to future change.
 
+    /// Version of the icon compositing "recipe"
+    /// Bump this whenever compositing logic changes (overlays, resizing, etc.) so previously built icons are invalidated
+    pub const RECIPE_VERSION: u32 = 1;
+
     #[allow(private_bounds)]
-    pub fn index_key<T: HashTuple>(ext: &'static str, items: T) -> String {
+    pub fn index_key<T: HashTuple>(ext: &'static str, icon_size: u32, items: T) -> String {
         let mut context = md5::Context::new();
+        context.consume(RECIPE_VERSION.to_le_bytes());
+        context.consume(icon_size.to_le_bytes());
         items.hash_all(|bytes| context.consume(bytes));
         format!("{:X}.{}", context.finalize(), ext)
     }
@@ -91,13 +107,90 @@ pub mod hash {
             consumer(self.3.cache_bytes().as_ref());
         }
     }
+
+    impl<A: IndexHash, B: IndexHash, C: IndexHash, D: IndexHash, E: IndexHash> HashTuple for (A, B, C, D, E) {
+        fn hash_all<F: FnMut(&[u8])>(self, mut consumer: F) {
+            consumer(self.0.cache_bytes().as_ref());
+            consumer(self.1.cache_bytes().as_ref());
+            consumer(self.2.cache_bytes().as_ref());
+            consumer(self.3.cache_bytes().as_ref());
+            consumer(self.4.cache_bytes().as_ref());
+        }
+    }
+}
+
+/// Default width and height of icons, in pixels
+pub const DEFAULT_ICON_SIZE: u32 = 64;
+
+/// Image format of built icons
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum IconFormat {
+    Png,
+    /// Lossless WebP
+    WebP
+}
+
+impl IconFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            IconFormat::Png => "png",
+            IconFormat::WebP => "webp",
+        }
+    }
+
+    fn image_format(self) -> ImageFormat {
+        match self {
+            IconFormat::Png => ImageFormat::Png,
+            IconFormat::WebP => ImageFormat::WebP,
+        }
+    }
+}
+
+/// Default quality of JPEG renders that must be re-encoded; Renders already in JPEG format are copied as-is
+pub const DEFAULT_JPEG_QUALITY: u8 = 90;
+
+/// Blend mode for the overlay of blueprint-style icons
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum BlendMode {
+    /// Additive blending, matches the game client's "plus" compositing
+    #[default]
+    Additive,
+    Screen,
+    /// Regular alpha compositing
+    Normal
+}
+
+impl BlendMode {
+    /// Name used in icon index keys
+    fn key_name(self) -> &'static str {
+        match self {
+            BlendMode::Additive => "blend-additive",
+            BlendMode::Screen => "blend-screen",
+            BlendMode::Normal => "blend-normal",
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone)]
 pub struct IconConfig {
     pub use_old_overlays: bool,
     pub module_overlays: bool,
-    pub clone_overlays: bool
+    pub clone_overlays: bool,
+    /// Width and height of icons, in pixels; Overlays are scaled to a quarter of this size
+    pub icon_size: u32,
+    /// Format of icons; Renders are always copied as-is in JPEG format
+    pub format: IconFormat,
+    /// Blend mode for the overlay of blueprint-style icons
+    pub overlay_blend: BlendMode,
+    /// Quality (1-100) of JPEG renders whose source is not a JPEG image
+    pub jpeg_quality: u8
+}
+
+impl IconConfig {
+    /// Width and height of tech/module/clone overlays, in pixels
+    pub fn overlay_size(self) -> u32 {
+        self.icon_size / 4
+    }
 }
 
 #[derive(Debug)]
@@ -118,24 +211,99 @@ pub struct GraphicInfo {
     pub hull: Option<String>
 }
 
-#[derive(Copy, Clone)]
-pub enum IconOverlay {  // TODO: Cache parsed images
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum IconOverlay {
     None,
     Resource(&'static str),
     Bytes(&'static [u8], &'static str)
 }
 
 impl IconOverlay {
-    pub fn load<C: SharedCache>(self, cache: &C) -> Result<Option<(&str, DynamicImage)>, IconError> {
+    /// Name of this overlay, used in icon index keys
+    pub fn name(self) -> Option<&'static str> {
+        match self {
+            IconOverlay::None => None,
+            IconOverlay::Resource(res) => Some(res.rsplit_once('/').map(|(_, suffix)| suffix).expect("resources are hardcoded and always have a filename")),
+            IconOverlay::Bytes(_, name) => Some(name)
+        }
+    }
+
+    /// Cache resource of this overlay, None for overlays that are not loaded from the cache
+    pub fn resource(self) -> Option<&'static str> {
+        match self {
+            IconOverlay::Resource(res) => Some(res),
+            IconOverlay::None | IconOverlay::Bytes(_, _) => None
+        }
+    }
+
+    pub fn load<C: SharedCache>(self, cache: &C, overlay_size: u32) -> Result<Option<(&str, DynamicImage)>, IconError> {
         match self {
             IconOverlay::None => Ok(None),
-            IconOverlay::Resource(res) => Ok(Some((res.rsplit_once('/').map(|(_, suffix)| suffix).expect("resources are hardcoded and always have a filename"), ImageReader::open(cache.path_of(res)?)?.with_guessed_format()?.decode()?.resize_exact(16, 16, FilterType::Lanczos3)))),
+            IconOverlay::Resource(res) => Ok(Some((self.name().expect("resource overlays are named"), ImageReader::open(cache.path_of(res)?)?.with_guessed_format()?.decode()?.resize_exact(overlay_size, overlay_size, FilterType::Lanczos3)))),
             IconOverlay::Bytes(bytes, name) => {
                 let mut reader = ImageReader::new(Cursor::new(bytes));
                 reader.set_format(ImageFormat::Png);
-                Ok(Some((name, reader.decode()?.resize_exact(16, 16, FilterType::Lanczos3))))
+                Ok(Some((name, reader.decode()?.resize_exact(overlay_size, overlay_size, FilterType::Lanczos3))))
+            }
+        }
+    }
+}
+
+/// Decoded overlay and background images, so that each is decoded once rather than for every icon
+///
+/// Images not in the cache are decoded on use
+#[derive(Default)]
+pub struct OverlayCache {
+    overlays: HashMap<IconOverlay, DynamicImage>,
+    backgrounds: HashMap<&'static str, DynamicImage>,
+}
+
+impl OverlayCache {
+    /// Decodes the overlays and backgrounds used by the specified recipes
+    pub fn build<'r, C: SharedCache, I: IntoIterator<Item=&'r IconRecipe>>(cache: &C, icon_config: IconConfig, recipes: I) -> Result<OverlayCache, IconError> {
+        let mut overlay_cache = OverlayCache::default();
+        for recipe in recipes {
+            match recipe {
+                IconRecipe::Resize { tech_overlay, module_overlay, clone_overlay, .. } => {
+                    for overlay in [tech_overlay, module_overlay, clone_overlay] {
+                        overlay_cache.insert_overlay(cache, *overlay, icon_config.overlay_size())?;
+                    }
+                }
+                IconRecipe::Composite { background, overlay, tech_overlay, .. } => {
+                    for resource in [background, overlay] {
+                        if let Entry::Vacant(entry) = overlay_cache.backgrounds.entry(resource) {
+                            entry.insert(load_resized(&cache.path_of(resource)?, icon_config.icon_size)?);
+                        }
+                    }
+                    overlay_cache.insert_overlay(cache, *tech_overlay, icon_config.overlay_size())?;
+                }
+                IconRecipe::Copy { .. } => {}
             }
         }
+        Ok(overlay_cache)
+    }
+
+    fn insert_overlay<C: SharedCache>(&mut self, cache: &C, overlay: IconOverlay, overlay_size: u32) -> Result<(), IconError> {
+        if let Entry::Vacant(entry) = self.overlays.entry(overlay) && let Some((_, image)) = overlay.load(cache, overlay_size)? {
+            entry.insert(image);
+        }
+        Ok(())
+    }
+
+    /// Overlay image resized to `overlay_size`, None for [`IconOverlay::None`]
+    fn overlay<C: SharedCache>(&self, cache: &C, overlay: IconOverlay, overlay_size: u32) -> Result<Option<Cow<'_, DynamicImage>>, IconError> {
+        match self.overlays.get(&overlay) {
+            Some(image) => Ok(Some(Cow::Borrowed(image))),
+            None => Ok(overlay.load(cache, overlay_size)?.map(|(_, image)| Cow::Owned(image)))
+        }
+    }
+
+    /// Background (or blueprint overlay) image resized to `icon_size`
+    fn background<C: SharedCache>(&self, cache: &C, resource: &str, icon_size: u32) -> Result<Cow<'_, DynamicImage>, IconError> {
+        match self.backgrounds.get(resource) {
+            Some(image) => Ok(Cow::Borrowed(image)),
+            None => Ok(Cow::Owned(load_resized(&cache.path_of(resource)?, icon_size)?))
+        }
     }
 }
 
@@ -211,7 +379,13 @@ pub enum IconError {
     SDE(SDELoadError),
     IO(io::Error),
     Image(image::ImageError),
-    String(String)
+    /// Type's group has no associated category in the SDE
+    GroupWithoutCategory { type_id: ids::TypeID, group_id: ids::GroupID },
+    /// IconID not present in the SDE
+    UnknownIcon(ids::IconID),
+    /// Output path is not usable for the selected output mode (e.g. a file where a directory is expected)
+    BadOutputPath(PathBuf),
+    Other(String)
 }
 
 impl Display for IconError {
@@ -221,7 +395,10 @@ impl Display for IconError {
             IconError::SDE(err) => Display::fmt(err, f),
             IconError::IO(err) => Display::fmt(err, f),
             IconError::Image(err) => Display::fmt(err, f),
-            IconError::String(msg) => Display::fmt(msg, f),
+            IconError::GroupWithoutCategory { type_id, group_id } => write!(f, "Type without associated category? Type:{} Group:{}", type_id, group_id),
+            IconError::UnknownIcon(icon_id) => write!(f, "unknown icon id: {}", icon_id),
+            IconError::BadOutputPath(path) => write!(f, "Output must be a directory! ({})", path.to_string_lossy()),
+            IconError::Other(msg) => Display::fmt(msg, f),
         }
     }
 }
@@ -233,7 +410,10 @@ impl Error for IconError {
             IconError::SDE(err) => Some(err),
             IconError::IO(err) => Some(err),
             IconError::Image(err) => Some(err),
-            IconError::String(_) => None
+            IconError::GroupWithoutCategory { .. } => None,
+            IconError::UnknownIcon(_) => None,
+            IconError::BadOutputPath(_) => None,
+            IconError::Other(_) => None
         }
     }
 }
@@ -275,11 +455,12 @@ pub struct IconBuildData {
     types: HashMap<u32, TypeInfo>,
     icon_files: HashMap<u32, String>,
     graphics_folders: HashMap<u32, GraphicInfo>,
-    skin_materials: HashMap<u32, u32>
+    skin_materials: HashMap<u32, u32>,
+    faction_icons: HashMap<ids::FactionID, ids::IconID>
 }
 
 impl IconBuildData {
-    pub fn load(mut loader: SDELoader, icon_config: IconConfig) -> Result<IconBuildData, SDELoadError> {
+    pub fn load(mut loader: SDELoader, icon_config: IconConfig) -> Result<IconBuildData, IconError> {
         let group_categories = { loader.load_groups()?.map(|g_res| g_res.map(|g| (g.groupID, g.categoryID))).collect::<Result<HashMap<_, _>, _>>()? };
 
         Ok(Self {
@@ -287,7 +468,7 @@ impl IconBuildData {
                 fn load_renderable_types(loader: &mut SDELoader) -> Result<TypeList, SDELoadError> {
                     for res in loader.load_type_lists()? {
                         let type_list = res?;
-                        if type_list.typeListID == 140 {
+                        if type_list.typeListID == RENDERABLE_TYPELIST {
                             return Ok(type_list);
                         }
                     }
@@ -299,72 +480,23 @@ impl IconBuildData {
                 let mut types = HashMap::<ids::TypeID, TypeInfo>::new();
                 for item_type in loader.load_types()? {
                     let item_type = item_type?;
-                    let item_category = *group_categories.get(&item_type.groupID).ok_or_else(|| SDELoadError::IntegrityError(format!("Type without associated category? Type:{} Group:{}", item_type.typeID, item_type.groupID)))?;
-
-                    if item_type.graphicID.is_some() || item_type.iconID.is_some() || (1950..=1955).contains(&item_type.groupID) || item_type.groupID == 4040 {
-                        types.insert(item_type.typeID, TypeInfo {
-                            group_id: item_type.groupID,
-                            category_id: item_category,
-                            icon_id: item_type.iconID,
-                            graphic_id: item_type.graphicID,
-                            meta_group_id: item_type.metaGroupID,
-                            is_renderable: renderable_types.contains(item_type.typeID, item_type.groupID, item_category),
-                            module_slot: None,
-                            omega_required: None,
-                        });
+                    if let Some(type_info) = Self::type_info(&item_type, &group_categories, &renderable_types)? {
+                        types.insert(item_type.typeID, type_info);
                     }
                 }
 
                 if icon_config.clone_overlays || icon_config.module_overlays {
                     let mut alpha_skills = HashMap::<ids::TypeID, values::SkillLevel>::new();
                     if icon_config.clone_overlays {
-                        for clone_grade in loader.load_clone_grades()? {
-                            let clone_grade = clone_grade?;
-                            alpha_skills.extend(clone_grade.skills);
-                            break;  // All alpha clone grades are the same, skip after the first
-                        }
-
-                        for (skill, _) in &alpha_skills {
-                            if let Some(item_type) = types.get_mut(skill) {
-                                item_type.omega_required = Some(false)
-                            }
+                        // All alpha clone grades are the same, skip after the first
+                        if let Some(clone_grade) = loader.load_clone_grades()?.next() {
+                            alpha_skills.extend(clone_grade?.skills);
                         }
+                        Self::mark_alpha_skills(&mut types, &alpha_skills);
                     }
 
                     for type_dogma in loader.load_type_dogma()? {
-                        let type_dogma = type_dogma?;
-                        if let Some(item_type) = types.get_mut(&type_dogma.typeID) {
-
-                            if type_dogma.dogmaEffects.contains_key(&11) { item_type.module_slot = Some(ModuleSlot::Low); }
-                            if type_dogma.dogmaEffects.contains_key(&13) { item_type.module_slot = Some(ModuleSlot::Medium); }
-                            if type_dogma.dogmaEffects.contains_key(&12) { item_type.module_slot = Some(ModuleSlot::High); }
-                            if type_dogma.dogmaEffects.contains_key(&2663) { item_type.module_slot = Some(ModuleSlot::Rig); }
-                            if type_dogma.dogmaEffects.contains_key(&3772) { item_type.module_slot = Some(ModuleSlot::Subsystem); }
-
-                            if icon_config.clone_overlays {
-                                // For skills, set requirement directly. For other items, determine requirement from required skills
-                                if group_categories.get(&item_type.group_id) == Some(&16) {
-                                    item_type.omega_required = Some(!alpha_skills.contains_key(&type_dogma.typeID))
-                                } else {
-                                    const SKILL_ATTRIBUTES: [ids::AttributeID; 6] = [182, 183, 184, 1285, 1289, 1290];
-                                    const LEVEL_ATTRIBUTES: [ids::AttributeID; 6] = [277, 278, 279, 1286, 1287, 1288];
-
-                                    let mut skill_required = false;
-                                    let mut omega_required = false;
-
-                                    for i in 0..6 {
-                                        if let (Some(skill), Some(level)) = (type_dogma.dogmaAttributes.get(&SKILL_ATTRIBUTES[i]), type_dogma.dogmaAttributes.get(&LEVEL_ATTRIBUTES[i])) {
-                                            skill_required = true;
-                                            omega_required |= alpha_skills.get(&(*skill as ids::TypeID)).is_none_or(|alpha_level| *alpha_level < (*level as u8));
-                                        }
-                                    }
-
-                                    if skill_required {
-                                        item_type.omega_required = Some(omega_required)
-                                    }
-                                }
-                            }
-                        }
+                        Self::apply_dogma(&mut types, &type_dogma?, &alpha_skills, &group_categories, icon_config);
                     }
                 }
 
@@ -383,26 +515,169 @@ impl IconBuildData {
             skin_materials: {
                 let license_skins = loader.load_skin_licenses()?.map(|l_res| l_res.map(|l| (l.typeID, l.skinID))).collect::<Result<HashMap<_, _>, _>>()?;
                 let skin_materials = loader.load_skins()?.map(|s_res| s_res.map(|s| (s.skinID, s.skinMaterialID))).collect::<Result<HashMap<_, _>, _>>()?;
+                Self::license_materials(license_skins, |skin_id| skin_materials.get(&skin_id).copied())
+            },
+            faction_icons: { loader.load_factions()?.map(|f_res| f_res.map(|f| (f.factionID, f.iconID))).collect::<Result<HashMap<_, _>, _>>()? },
+        })
+    }
 
-                let mut license_materials = HashMap::with_capacity(license_skins.len());
-                for (license_id, skin_id) in license_skins {
-                    if let Some(material_id) = skin_materials.get(&skin_id) {
-                        license_materials.insert(license_id, *material_id);
-                    }
+    /// Builds icon data from an already-loaded SDE
+    pub fn from_sde(sde: &SDE_Full, icon_config: IconConfig) -> Result<IconBuildData, IconError> {
+        let group_categories = sde.groups.values().map(|g| (g.groupID, g.categoryID)).collect::<HashMap<_, _>>();
+        let renderable_types = sde.type_lists.get(&RENDERABLE_TYPELIST)
+            .ok_or_else(|| SDELoadError::IntegrityError("Could not find typelist #140: RenderableTypeIDs in Static Data Export".to_string()))?;
+
+        let mut types = HashMap::<ids::TypeID, TypeInfo>::new();
+        for item_type in sde.types.values() {
+            if let Some(type_info) = Self::type_info(item_type, &group_categories, renderable_types)? {
+                types.insert(item_type.typeID, type_info);
+            }
+        }
+
+        if icon_config.clone_overlays || icon_config.module_overlays {
+            let mut alpha_skills = HashMap::<ids::TypeID, values::SkillLevel>::new();
+            if icon_config.clone_overlays {
+                // All alpha clone grades are the same, use the first
+                if let Some(clone_grade) = sde.clone_grades.values().next() {
+                    alpha_skills.extend(&clone_grade.skills);
                 }
-                license_materials
+                Self::mark_alpha_skills(&mut types, &alpha_skills);
+            }
+
+            for type_dogma in sde.type_dogma.values() {
+                Self::apply_dogma(&mut types, type_dogma, &alpha_skills, &group_categories, icon_config);
             }
+        }
+
+        Ok(Self {
+            types,
+            icon_files: sde.icons.values().map(|i| (i.iconID, i.iconFile.clone())).collect(),
+            graphics_folders: sde.graphics.values().map(|g| (g.graphicID, GraphicInfo { folder: g.iconFolder.clone(), hull: g.sofHullName.clone() })).collect(),
+            skin_materials: Self::license_materials(
+                sde.skin_licenses.values().map(|l| (l.typeID, l.skinID)),
+                |skin_id| sde.skins.get(&skin_id).map(|s| s.skinMaterialID)
+            ),
+            faction_icons: sde.factions.values().map(|f| (f.factionID, f.iconID)).collect(),
         })
     }
+
+    /// Type info for types that may have an icon, None for types that don't
+    fn type_info(item_type: &Type, group_categories: &HashMap<ids::GroupID, ids::CategoryID>, renderable_types: &TypeList) -> Result<Option<TypeInfo>, IconError> {
+        let item_category = *group_categories.get(&item_type.groupID).ok_or(IconError::GroupWithoutCategory { type_id: item_type.typeID, group_id: item_type.groupID })?;
+
+        if item_type.graphicID.is_some() || item_type.iconID.is_some() || (1950..=1955).contains(&item_type.groupID) || item_type.groupID == 4040 {
+            Ok(Some(TypeInfo {
+                group_id: item_type.groupID,
+                category_id: item_category,
+                icon_id: item_type.iconID,
+                graphic_id: item_type.graphicID,
+                meta_group_id: item_type.metaGroupID,
+                is_renderable: renderable_types.contains(item_type.typeID, item_type.groupID, item_category),
+                module_slot: None,
+                omega_required: None,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Marks alpha-trainable skills as not requiring omega, including skills without type dogma
+    fn mark_alpha_skills(types: &mut HashMap<ids::TypeID, TypeInfo>, alpha_skills: &HashMap<ids::TypeID, values::SkillLevel>) {
+        for skill in alpha_skills.keys() {
+            if let Some(item_type) = types.get_mut(skill) {
+                item_type.omega_required = Some(false)
+            }
+        }
+    }
+
+    /// Sets module slot & clone state requirements from type dogma
+    fn apply_dogma(types: &mut HashMap<ids::TypeID, TypeInfo>, type_dogma: &TypeDogma, alpha_skills: &HashMap<ids::TypeID, values::SkillLevel>, group_categories: &HashMap<ids::GroupID, ids::CategoryID>, icon_config: IconConfig) {
+        if let Some(item_type) = types.get_mut(&type_dogma.typeID) {
+
+            if type_dogma.dogmaEffects.contains_key(&11) { item_type.module_slot = Some(ModuleSlot::Low); }
+            if type_dogma.dogmaEffects.contains_key(&13) { item_type.module_slot = Some(ModuleSlot::Medium); }
+            if type_dogma.dogmaEffects.contains_key(&12) { item_type.module_slot = Some(ModuleSlot::High); }
+            if type_dogma.dogmaEffects.contains_key(&2663) { item_type.module_slot = Some(ModuleSlot::Rig); }
+            if type_dogma.dogmaEffects.contains_key(&3772) { item_type.module_slot = Some(ModuleSlot::Subsystem); }
+
+            if icon_config.clone_overlays {
+                // For skills, set requirement directly. For other items, determine requirement from required skills
+                if group_categories.get(&item_type.group_id) == Some(&16) {
+                    item_type.omega_required = Some(!alpha_skills.contains_key(&type_dogma.typeID))
+                } else {
+                    const SKILL_ATTRIBUTES: [ids::AttributeID; 6] = [182, 183, 184, 1285, 1289, 1290];
+                    const LEVEL_ATTRIBUTES: [ids::AttributeID; 6] = [277, 278, 279, 1286, 1287, 1288];
+
+                    let mut skill_required = false;
+                    let mut omega_required = false;
+
+                    for i in 0..6 {
+                        if let (Some(skill), Some(level)) = (type_dogma.dogmaAttributes.get(&SKILL_ATTRIBUTES[i]), type_dogma.dogmaAttributes.get(&LEVEL_ATTRIBUTES[i])) {
+                            skill_required = true;
+                            omega_required |= alpha_skills.get(&(*skill as ids::TypeID)).is_none_or(|alpha_level| *alpha_level < (*level as u8));
+                        }
+                    }
+
+                    if skill_required {
+                        item_type.omega_required = Some(omega_required)
+                    }
+                }
+            }
+        }
+    }
+
+    /// Maps SKIN license types to their SKIN material
+    fn license_materials<I: IntoIterator<Item=(ids::TypeID, ids::SkinID)>, F: Fn(ids::SkinID) -> Option<ids::SkinMaterialID>>(license_skins: I, skin_material: F) -> HashMap<ids::TypeID, ids::SkinMaterialID> {
+        let mut license_materials = HashMap::new();
+        for (license_id, skin_id) in license_skins {
+            if let Some(material_id) = skin_material(skin_id) {
+                license_materials.insert(license_id, material_id);
+            }
+        }
+        license_materials
+    }
+}
+
+/// File extension of an icon index key
+fn file_extension(index_key: &str) -> &str {
+    index_key.rsplit_once('.').map_or("", |(_, extension)| extension)
+}
+
+/// Decodes an image file, resized to a square of `size`
+fn load_resized(path: &Path, size: u32) -> Result<DynamicImage, IconError> {
+    Ok(ImageReader::open(path)?.with_guessed_format()?.decode()?.resize_exact(size, size, FilterType::Lanczos3))
 }
 
-fn composite_blueprint(background: &Path, overlay: &Path, icon: &Path, tech_icon: Option<&DynamicImage>, out: &Path) -> Result<(), IconError> {
-    let mut background_image = ImageReader::open(background)?.with_guessed_format()?.decode()?.into_rgba8();
-    let icon_image = ImageReader::open(icon)?.with_guessed_format()?.decode()?.resize_exact(64, 64, FilterType::Lanczos3);
+/// Resizes an icon, then applies overlays; Tech overlay top-left, module overlay bottom-right, clone overlay top-right
+fn composite_icon(icon: &Path, tech_icon: Option<&DynamicImage>, module_icon: Option<&DynamicImage>, clone_icon: Option<&DynamicImage>, icon_size: u32, out: &Path) -> Result<(), IconError> {
+    let mut image = load_resized(icon, icon_size)?;
+    if let Some(techoverlay) = tech_icon {
+        imageops::overlay(&mut image, techoverlay, 0, 0);
+    }
+    if let Some(moduleoverlay) = module_icon {
+        let offset = (icon_size - moduleoverlay.width()) as i64;
+        imageops::overlay(&mut image, moduleoverlay, offset, offset);
+    }
+    if let Some(cloneoverlay) = clone_icon {
+        imageops::overlay(&mut image, cloneoverlay, (icon_size - cloneoverlay.width()) as i64, 0);
+    }
+    image.save(out)?;
+    Ok(())
+}
+
+/// Composites a blueprint-style icon; `background` and `overlay` must already be resized to `icon_size`
+fn composite_blueprint(background: &DynamicImage, overlay: &DynamicImage, icon: &Path, tech_icon: Option<&DynamicImage>, icon_size: u32, blend_mode: BlendMode, out: &Path) -> Result<(), IconError> {
+    let mut background_image = background.to_rgba8();
+    let icon_image = load_resized(icon, icon_size)?;
     imageops::overlay(&mut background_image, &icon_image, 0, 0);
-    let overlay_image = ImageReader::open(overlay)?.with_guessed_format()?.decode()?.into_rgba8();
+    let overlay_image = overlay.to_rgba8();
 
-    background_image.blend(&overlay_image, image_blend::pixelops::pixel_add, true, false).map_err(io::Error::other)?;
+    // Blending is applied to color only, the background's alpha is preserved
+    match blend_mode {
+        BlendMode::Additive => background_image.blend(&overlay_image, image_blend::pixelops::pixel_add, true, false).map_err(io::Error::other)?,
+        BlendMode::Screen => background_image.blend(&overlay_image, image_blend::pixelops::pixel_screen, true, false).map_err(io::Error::other)?,
+        BlendMode::Normal => imageops::overlay(&mut background_image, &overlay_image, 0, 0),
+    }
 
     if let Some(techoverlay) = tech_icon {
         imageops::overlay(&mut background_image, techoverlay, 0, 0);
@@ -413,7 +688,7 @@ fn composite_blueprint(background: &Path, overlay: &Path, icon: &Path, tech_icon
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize)]
-enum IconKind {
+pub enum IconKind {
     #[serde(rename="icon")]
     Icon,
     #[serde(rename="bp")]
@@ -447,14 +722,268 @@ impl Display for IconKind {
     }
 }
 
+/// How a single icon file is built from game resources
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum IconRecipe {
+    /// Resize resource to the configured icon size, then apply overlays
+    Resize { resource: String, tech_overlay: IconOverlay, module_overlay: IconOverlay, clone_overlay: IconOverlay },
+    /// Blueprint-style composite; Icon resource on top of a background, with an additive overlay
+    Composite { background: &'static str, overlay: &'static str, resource: String, tech_overlay: IconOverlay },
+    /// Copy resource as-is
+    Copy { resource: String }
+}
+
+impl IconRecipe {
+    /// Icon index key (and icon folder filename) for this recipe
+    pub fn index_key<C: SharedCache>(&self, cache: &C, icon_config: IconConfig) -> Result<String, IconError> {
+        let IconConfig { icon_size, format, overlay_blend, jpeg_quality, .. } = icon_config;
+        Ok(match self {
+            IconRecipe::Resize { resource, tech_overlay, module_overlay, clone_overlay } => {
+                hash::index_key(format.extension(), icon_size, (cache.hash_of(resource)?, tech_overlay.name(), module_overlay.name(), clone_overlay.name()))
+            }
+            IconRecipe::Composite { background, overlay, resource, tech_overlay } => {
+                hash::index_key(format.extension(), icon_size, (cache.hash_of(resource)?, cache.hash_of(background)?, cache.hash_of(overlay)?, tech_overlay.name(), overlay_blend.key_name()))
+            }
+            IconRecipe::Copy { resource } => {
+                // Copied as-is, icon size does not apply; JPEG renders are not converted
+                if resource.ends_with(".jpg") {
+                    let quality_key = format!("jpeg-q{}", jpeg_quality);
+                    hash::index_key("jpg", DEFAULT_ICON_SIZE, (cache.hash_of(resource)?, quality_key.as_str()))
+                } else {
+                    hash::index_key(format.extension(), DEFAULT_ICON_SIZE, cache.hash_of(resource)?)
+                }
+            }
+        })
+    }
+
+    /// Cache resources read when building this recipe
+    pub fn resources(&self) -> Vec<&str> {
+        match self {
+            IconRecipe::Resize { resource, tech_overlay, module_overlay, clone_overlay } => {
+                [tech_overlay, module_overlay, clone_overlay].into_iter()
+                    .filter_map(|overlay| overlay.resource())
+                    .chain([resource.as_str()])
+                    .collect()
+            }
+            IconRecipe::Composite { background, overlay, resource, tech_overlay } => {
+                tech_overlay.resource().into_iter().chain([*background, *overlay, resource.as_str()]).collect()
+            }
+            IconRecipe::Copy { resource } => vec![resource.as_str()]
+        }
+    }
+
+    /// Builds this icon into `out`, using decoded overlays from `overlays` where available
+    pub fn build<C: SharedCache>(&self, cache: &C, icon_config: IconConfig, overlays: &OverlayCache, out: &Path) -> Result<(), IconError> {
+        let overlay_size = icon_config.overlay_size();
+        match self {
+            IconRecipe::Resize { resource, tech_overlay, module_overlay, clone_overlay } => {
+                composite_icon(
+                    &cache.path_of(resource)?,
+                    overlays.overlay(cache, *tech_overlay, overlay_size)?.as_deref(),
+                    overlays.overlay(cache, *module_overlay, overlay_size)?.as_deref(),
+                    overlays.overlay(cache, *clone_overlay, overlay_size)?.as_deref(),
+                    icon_config.icon_size,
+                    out
+                )?;
+            }
+            IconRecipe::Composite { background, overlay, resource, tech_overlay } => {
+                composite_blueprint(
+                    &*overlays.background(cache, background, icon_config.icon_size)?,
+                    &*overlays.background(cache, overlay, icon_config.icon_size)?,
+                    &cache.path_of(resource)?,
+                    overlays.overlay(cache, *tech_overlay, overlay_size)?.as_deref(),
+                    icon_config.icon_size,
+                    icon_config.overlay_blend,
+                    out
+                )?;
+            }
+            IconRecipe::Copy { resource } => {
+                let source = cache.path_of(resource)?;
+                let target_format = if resource.ends_with(".jpg") { ImageFormat::Jpeg } else { icon_config.format.image_format() };
+                // Detect the actual format of the source, rather than trusting the resource's file extension
+                let reader = ImageReader::open(&source)?.with_guessed_format()?;
+                if reader.format() == Some(target_format) {
+                    fs::copy(source, out)?;
+                } else if target_format == ImageFormat::Jpeg {
+                    // JPEG has no alpha channel
+                    let encoder = JpegEncoder::new_with_quality(BufWriter::new(File::create(out)?), icon_config.jpeg_quality);
+                    reader.decode()?.into_rgb8().write_with_encoder(encoder)?;
+                } else {
+                    reader.decode()?.save_with_format(out, target_format)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Icon planned for a type, see [`plan_type_icons`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PlannedIcon {
+    /// Icon to build, and which kinds of icon it provides for the type
+    Build { kinds: &'static [IconKind], recipe: IconRecipe },
+    /// Icon resource is missing from the game files
+    Missing { resource: String }
+}
+
+/// Resource of the 512px render for a graphic
+fn render_resource(graphic_folder: &str, graphic_id: ids::GraphicID) -> String {
+    format!("{}/{}_512.jpg", graphic_folder.trim_end_matches('/'), graphic_id)
+}
+
+/// Determines which icons to build for a type, and from which resources
+///
+/// # Arguments
+///
+/// * `type_id`: Type to plan icons for
+/// * `type_info`: Type info of `type_id`
+/// * `data`: Icon build data
+/// * `icon_config`: Icon config
+/// * `has_resource`: Predicate returning true if the resource is available in the game files
+///
+/// returns: Icons to build, empty if the type has no icon
+pub fn plan_type_icons<F: Fn(&str) -> bool>(type_id: ids::TypeID, type_info: &TypeInfo, data: &IconBuildData, icon_config: IconConfig, has_resource: F) -> Result<Vec<PlannedIcon>, IconError> {
+    let mut planned = Vec::new();
+    // Skip types without iconID or graphicID as they have no icon, SKINs have custom logic
+    if type_info.icon_id.is_none() && type_info.graphic_id.is_none() && type_info.category_id != 91 { return Ok(planned); }
+
+    let graphic_folder = type_info.graphic_id.and_then(|graphic_id| data.graphics_folders.get(&graphic_id)).and_then(|g| g.folder.as_ref());
+
+    if (type_info.category_id == 9) || (type_info.category_id == 34) {
+        // Blueprint or reaction
+        let tech_overlay = get_techoverlay(type_info.meta_group_id.unwrap_or(1), icon_config.use_old_overlays);
+
+        if let Some(folder) = graphic_folder {
+            let graphic_id = type_info.graphic_id.expect("graphic folder implies graphic ID");
+            let icon_resource_bp = format!("{}/{}_64_bp.png", folder.trim_end_matches('/'), graphic_id);
+            let icon_resource_bpc = format!("{}/{}_64_bpc.png", folder.trim_end_matches('/'), graphic_id);
+
+            if has_resource(&icon_resource_bp) && type_info.is_renderable {
+                planned.push(PlannedIcon::Build {
+                    kinds: &[IconKind::Icon, IconKind::Blueprint],
+                    recipe: IconRecipe::Resize { resource: icon_resource_bp, tech_overlay, module_overlay: IconOverlay::None, clone_overlay: IconOverlay::None }
+                });
+                if has_resource(&icon_resource_bpc) {
+                    planned.push(PlannedIcon::Build {
+                        kinds: &[IconKind::BlueprintCopy],
+                        recipe: IconRecipe::Resize { resource: icon_resource_bpc, tech_overlay, module_overlay: IconOverlay::None, clone_overlay: IconOverlay::None }
+                    });
+                }
+            }
+        } else if let Some(icon) = type_info.icon_id { // If no graphics icon, try icon
+            let icon_resource = data.icon_files.get(&icon).ok_or(IconError::UnknownIcon(icon))?;
+            if !has_resource(icon_resource) {
+                planned.push(PlannedIcon::Missing { resource: icon_resource.clone() });
+            } else if type_info.category_id == 34 {
+                // Relic BG/overlay
+                planned.push(PlannedIcon::Build {
+                    kinds: &[IconKind::Icon, IconKind::Relic],
+                    recipe: IconRecipe::Composite { background: "res:/ui/texture/icons/relic.png", overlay: "res:/ui/texture/icons/relic_overlay.png", resource: icon_resource.clone(), tech_overlay }
+                });
+            } else if REACTION_GROUPS.contains(&type_info.group_id) {
+                // Reaction BG/overlay, also listed as Blueprint; Incorrect behaviour of the image service, included for compatibility
+                planned.push(PlannedIcon::Build {
+                    kinds: &[IconKind::Icon, IconKind::Reaction, IconKind::Blueprint],
+                    recipe: IconRecipe::Composite { background: "res:/ui/texture/icons/reaction.png", overlay: "res:/ui/texture/icons/bpo_overlay.png", resource: icon_resource.clone(), tech_overlay }
+                });
+            } else {
+                // BP & BPC BG/overlay
+                planned.push(PlannedIcon::Build {
+                    kinds: &[IconKind::Icon, IconKind::Blueprint],
+                    recipe: IconRecipe::Composite { background: "res:/ui/texture/icons/bpo.png", overlay: "res:/ui/texture/icons/bpo_overlay.png", resource: icon_resource.clone(), tech_overlay }
+                });
+                planned.push(PlannedIcon::Build {
+                    kinds: &[IconKind::BlueprintCopy],
+                    recipe: IconRecipe::Composite { background: "res:/ui/texture/icons/bpc.png", overlay: "res:/ui/texture/icons/bpc_overlay.png", resource: icon_resource.clone(), tech_overlay }
+                });
+            }
+        }
+    } else {
+        // Regular item
+        let icon_resource;
+        if let Some(folder) = graphic_folder {
+            let graphic_id = type_info.graphic_id.expect("graphic folder implies graphic ID");
+            let graphic_resource = format!("{}/{}_64.png", folder.trim_end_matches('/'), graphic_id);
+
+            // If no graphic, try icon
+            if has_resource(&graphic_resource) && type_info.is_renderable {
+                icon_resource = graphic_resource;
+            } else if let Some(icon) = type_info.icon_id {
+                icon_resource = data.icon_files.get(&icon).ok_or(IconError::UnknownIcon(icon))?.clone();
+            } else {
+                return Ok(planned);   // No icon
+            }
+
+            let render_resource = render_resource(folder, graphic_id);
+            if has_resource(&render_resource) {
+                planned.push(PlannedIcon::Build { kinds: &[IconKind::Render], recipe: IconRecipe::Copy { resource: render_resource } });
+            }
+        } else if let Some(icon) = type_info.icon_id {
+            icon_resource = data.icon_files.get(&icon).ok_or(IconError::UnknownIcon(icon))?.clone();
+        } else if type_info.category_id == 91 {
+            // SKIN
+            if let Some(material_id) = data.skin_materials.get(&type_id) {
+                icon_resource = format!("res:/ui/texture/classes/skins/icons/{}.png", material_id);
+            } else {
+                return Ok(planned);   // Some skins are region-exclusive and do not have the resources available on the TQ client, so skip and treat as no-icon types
+            }
+        } else {
+            return Ok(planned); // No icon to be generated here
+        }
+
+        if has_resource(&icon_resource) {
+            // Icons without overlays are still resized, and so are copied to the icon-cache folder
+            planned.push(PlannedIcon::Build {
+                kinds: &[IconKind::Icon],
+                recipe: IconRecipe::Resize {
+                    resource: icon_resource,
+                    tech_overlay: get_techoverlay(type_info.meta_group_id.unwrap_or(1), icon_config.use_old_overlays),
+                    module_overlay: get_moduleoverlay(type_info.module_slot, icon_config.use_old_overlays),
+                    clone_overlay: get_cloneoverlay(type_info.omega_required, icon_config.use_old_overlays),
+                }
+            });
+        } else {
+            planned.push(PlannedIcon::Missing { resource: icon_resource });
+        }
+    }
+
+    Ok(planned)
+}
+
+/// Reads an icon index file (`cache.csv`)
+///
+/// returns: Recipe version (None for indices without a header) and index keys
+fn read_index(path: &Path) -> Result<(Option<u32>, HashSet<String>), IconError> {
+    let mut recipe = None;
+    let mut index = HashSet::new();
+    let mut buf = Vec::new();
+    let mut reader = BufReader::new(File::open(path)?);
+    while reader.read_until(b'\x1E', &mut buf)? > 0 {
+        let file = std::str::from_utf8(&buf).map_err(io::Error::other)?.trim_end_matches('\x1E');
+        if let Some(version) = file.strip_prefix(INDEX_HEADER_PREFIX) {
+            recipe = version.parse::<u32>().ok();
+        } else {
+            index.insert(file.to_string());
+        }
+        buf.clear();
+    };
+    Ok((recipe, index))
+}
+
 #[derive(Debug)]
 pub enum OutputMode<'a> {
     ServiceBundle { out: &'a Path },
     IEC { out: &'a Path },
     Web { out: &'a Path, copy_files: bool, hard_link: bool },
     Checksum { out: Option<&'a Path> },
+    /// Service bundle containing only icons not in a previous index (`cache.csv`), with a manifest of removed icons
+    DeltaBundle { out: &'a Path, base_index: &'a Path },
     AuxShipTreeRenders { out: &'a Path },
+    /// Archive of only the 512px type renders, skipping icon compositing
+    Renders { out: &'a Path },
     AuxIcons { out: &'a Path },
+    /// Archive of faction logos (from `Faction::iconID`), named by FactionID
+    AuxFactionLogos { out: &'a Path },
     AuxImages { out: &'a Path, incl_character: bool }
 }
 
@@ -465,14 +994,136 @@ impl<'a> OutputMode<'a> {
             OutputMode::IEC { .. } => true,
             OutputMode::Web { .. } => true,
             OutputMode::Checksum { .. } => true,
+            OutputMode::DeltaBundle { .. } => true,
             OutputMode::AuxShipTreeRenders { .. } => false,
+            OutputMode::Renders { .. } => false,
             OutputMode::AuxIcons { .. } => false,
+            OutputMode::AuxFactionLogos { .. } => false,
             OutputMode::AuxImages { .. } => false
         }
     }
 }
 
-pub fn build_icon_export<C: SharedCache, P: AsRef<Path>>(icon_config: IconConfig, output_modes: Vec<OutputMode>, skip_output_if_fresh: bool, no_purge: bool, data: &IconBuildData, cache: &C, icon_dir: P, force_rebuild: bool, mut silent_mode: bool) -> Result<(), IconError> {
+/// Icon planned for export, see [`plan_icon_export`]
+#[derive(Debug)]
+pub struct ExportIcon {
+    pub type_id: ids::TypeID,
+    pub kinds: &'static [IconKind],
+    pub recipe: IconRecipe,
+    pub index_key: String
+}
+
+/// Plans icons for all types, in parallel
+///
+/// returns: Icons to export sorted by TypeID, and types with missing icon resources
+pub fn plan_icon_export<C: SharedCache + Sync>(data: &IconBuildData, cache: &C, icon_config: IconConfig) -> Result<(Vec<ExportIcon>, Vec<ids::TypeID>), IconError> {
+    let mut type_icons = data.types.par_iter()
+        .map(|(type_id, type_info)| {
+            let mut icons = Vec::new();
+            let mut missing = false;
+            for planned_icon in plan_type_icons(*type_id, type_info, data, icon_config, |resource| cache.has_resource(resource))? {
+                match planned_icon {
+                    PlannedIcon::Build { kinds, recipe } => {
+                        let index_key = recipe.index_key(cache, icon_config)?;
+                        icons.push(ExportIcon { type_id: *type_id, kinds, recipe, index_key });
+                    }
+                    PlannedIcon::Missing { .. } => missing = true
+                }
+            }
+            Ok((*type_id, icons, missing))
+        })
+        .collect::<Result<Vec<_>, IconError>>()?;
+    type_icons.sort_by_key(|(type_id, _, _)| *type_id);
+
+    let missing = type_icons.iter().filter(|(_, _, missing)| *missing).map(|(type_id, _, _)| *type_id).collect();
+    let icons = type_icons.into_iter().flat_map(|(_, icons, _)| icons).collect();
+    Ok((icons, missing))
+}
+
+/// Number of threads used to fetch resources, see [`prefetch`]
+const PREFETCH_THREADS: usize = 16;
+
+/// Fetches resources into the cache, downloading them if the cache does so, with [`PREFETCH_THREADS`] parallel fetches
+///
+/// Fetching is IO-bound, so uses a separate thread pool from CPU-bound image compositing
+pub fn prefetch<'r, C: SharedCache + Sync, I: IntoIterator<Item=&'r str>>(cache: &C, resources: I) -> Result<(), IconError> {
+    let resources = resources.into_iter().collect::<Vec<_>>();
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(PREFETCH_THREADS)
+        .build()
+        .map_err(|err| IconError::Other(format!("could not start prefetch threads: {}", err)))?;
+    pool.install(|| resources.into_par_iter().try_for_each(|resource| cache.path_of(resource).map(drop)))?;
+    Ok(())
+}
+
+/// Service metadata; Map of TypeID to the icon file of each kind of icon of that type
+pub fn icon_service_metadata(icons: &[ExportIcon]) -> BTreeMap<ids::TypeID, BTreeMap<IconKind, String>> {
+    let mut service_metadata = BTreeMap::<ids::TypeID, BTreeMap<IconKind, String>>::new();
+    for icon in icons {
+        for icon_kind in icon.kinds {
+            service_metadata.entry(icon.type_id).or_default().insert(*icon_kind, icon.index_key.clone());
+        }
+    }
+    service_metadata
+}
+
+/// Pixel dimensions and format of an icon file
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct IconFileInfo<'a> {
+    pub filename: &'a str,
+    pub width: u32,
+    pub height: u32,
+    /// File extension of the image format, e.g. "png", "jpg"
+    pub format: &'static str,
+}
+
+/// Service metadata including the dimensions and format of each icon file, written as `service_metadata_full.json`
+///
+/// Only image headers are read, files shared between types are read once
+pub fn icon_file_metadata<'a>(service_metadata: &'a BTreeMap<ids::TypeID, BTreeMap<IconKind, String>>, icon_dir: &Path) -> Result<BTreeMap<ids::TypeID, BTreeMap<IconKind, IconFileInfo<'a>>>, IconError> {
+    let mut file_info = HashMap::<&str, IconFileInfo>::new();
+    let mut metadata = BTreeMap::new();
+    for (type_id, icons) in service_metadata {
+        let mut type_metadata = BTreeMap::new();
+        for (icon_kind, filename) in icons {
+            let info = match file_info.entry(filename) {
+                Entry::Occupied(entry) => entry.get().clone(),
+                Entry::Vacant(entry) => {
+                    let reader = ImageReader::open(icon_dir.join(filename))?.with_guessed_format()?;
+                    let format = reader.format().and_then(|format| format.extensions_str().first().copied()).unwrap_or("unknown");
+                    let (width, height) = reader.into_dimensions()?;
+                    entry.insert(IconFileInfo { filename, width, height, format }).clone()
+                }
+            };
+            type_metadata.insert(*icon_kind, info);
+        }
+        metadata.insert(*type_id, type_metadata);
+    }
+    Ok(metadata)
+}
+
+/// Manifest of icon kinds available for each type, written as `manifest.json` in archive outputs
+pub fn icon_kind_manifest(service_metadata: &BTreeMap<ids::TypeID, BTreeMap<IconKind, String>>) -> BTreeMap<ids::TypeID, Vec<IconKind>> {
+    service_metadata.iter()
+        .map(|(type_id, icons)| (*type_id, icons.keys().copied().collect()))
+        .collect()
+}
+
+/// Checksum of an icon export
+///
+/// Depends only on the TypeID, kind, and index key of each icon, so is independent of output mode and platform
+pub fn icon_checksum(service_metadata: &BTreeMap<ids::TypeID, BTreeMap<IconKind, String>>) -> md5::Digest {
+    let mut context = md5::Context::new();
+    context.consume(hash::RECIPE_VERSION.to_le_bytes());
+    for (type_id, icons) in service_metadata {
+        for (icon_kind, index_key) in icons {
+            context.consume(format!("{}\x1F{}\x1F{}\x1E", type_id, icon_kind.name(), index_key));
+        }
+    }
+    context.finalize()
+}
+
+pub fn build_icon_export<C: SharedCache + Sync, P: AsRef<Path>>(icon_config: IconConfig, output_modes: Vec<OutputMode>, skip_output_if_fresh: bool, no_purge: bool, data: &IconBuildData, cache: &C, icon_dir: P, force_rebuild: bool, mut silent_mode: bool) -> Result<(), IconError> {
     let log_file = crate::LOG_FILE.get();   // TODO: Put in a parameter
     silent_mode |= output_modes.iter().any(|mode| matches!(mode, OutputMode::Checksum { out: None }));  // If "Checksum to stdout" output mode is present, enforce silent mode
 
@@ -486,273 +1137,53 @@ pub fn build_icon_export<C: SharedCache, P: AsRef<Path>>(icon_config: IconConfig
     if DO_INDEX_UPDATE {
         fs::create_dir_all(icon_dir)?;
         if fs::exists(&index_path)? {
-            let mut buf = Vec::new();
-            let mut reader = BufReader::new(File::open(&index_path)?);
-            while reader.read_until(b'\x1E', &mut buf)? > 0 {
-                let file = std::str::from_utf8(&buf).map_err(io::Error::other)?.trim_end_matches('\x1E');
-                old_index.insert(file.to_string());
-                buf.clear();
-            };
+            let old_recipe;
+            (old_recipe, old_index) = read_index(&index_path)?;
+
+            // Old-recipe icons have different index keys and are not fresh; They remain in old_index so they get purged
+            if old_recipe != Some(hash::RECIPE_VERSION) {
+                if !silent_mode { println!("Icon recipe changed ({} -> {}), rebuilding icons", old_recipe.map_or("none".to_string(), |v| v.to_string()), hash::RECIPE_VERSION); }
+                if let Some(mut log) = log_file { writeln!(log, "Icon recipe changed ({:?} -> {}), rebuilding icons", old_recipe, hash::RECIPE_VERSION)?; }
+            }
         }
     }
 
     let mut service_metadata = BTreeMap::<u32, BTreeMap<IconKind, String>>::new();
     let mut new_index = HashSet::<String>::new();
 
-    fn is_up_to_date(old_index: &HashSet<String>, new_index: &mut HashSet<String>, index_key: &str, force_rebuild: bool) -> bool {
-        new_index.insert(index_key.to_string());
-        old_index.contains(index_key) && !force_rebuild
-    }
-
-    let mut index_bytes = Vec::new();
     let skip_output;
     let to_remove;
     if DO_INDEX_UPDATE {
-        for (type_id, type_info) in &data.types {
-            // Skip types without iconID or graphicID as they have no icon, SKINs have custom logic
-            if type_info.icon_id.is_none() && type_info.graphic_id.is_none() && type_info.category_id != 91 { continue; }
-
-            if (type_info.category_id == 9) || (type_info.category_id == 34) {
-                // Blueprint or reaction
-
-                if let Some(folder) = type_info.graphic_id.and_then(|graphic_id| data.graphics_folders.get(&graphic_id)).and_then(|g| g.folder.as_ref()) {
-                    let icon_resource_bp = format!("{}/{}_64_bp.png", folder.trim_end_matches('/'), type_info.graphic_id.unwrap());
-                    let icon_resource_bpc = format!("{}/{}_64_bpc.png", folder.trim_end_matches('/'), type_info.graphic_id.unwrap());
-
-                    if cache.has_resource(&*icon_resource_bp) && type_info.is_renderable {
-                        let techoverlay = get_techoverlay(type_info.meta_group_id.unwrap_or(1), icon_config.use_old_overlays);
-                        if let Some((overlay_cache, techoverlay)) = techoverlay.load(cache)? {
-                            let index_key = hash::index_key("png", (cache.hash_of(&icon_resource_bp)?, overlay_cache));
-                            service_metadata.entry(*type_id).or_default().insert(IconKind::Icon, index_key.clone());
-                            service_metadata.entry(*type_id).or_default().insert(IconKind::Blueprint, index_key.clone());
-                            if !is_up_to_date(&old_index, &mut new_index, &index_key, force_rebuild) {
-                                let mut image = ImageReader::open(&cache.path_of(&*icon_resource_bp)?)?.with_guessed_format()?.decode()?.resize_exact(64, 64, FilterType::Lanczos3);
-                                imageops::overlay(&mut image, &techoverlay, 0, 0);
-                                image.save(&icon_dir.join(index_key))?;
-                            }
-
-                            if cache.has_resource(&*icon_resource_bpc) {
-                                let index_key = hash::index_key("png", (cache.hash_of(&icon_resource_bpc)?, overlay_cache));
-                                service_metadata.entry(*type_id).or_default().insert(IconKind::BlueprintCopy, index_key.clone());
-                                if !is_up_to_date(&old_index, &mut new_index, &index_key, force_rebuild) {
-                                    let mut image = ImageReader::open(&cache.path_of(&*icon_resource_bpc)?)?.with_guessed_format()?.decode()?
-                                        .resize_exact(64, 64, FilterType::Lanczos3);
-                                    imageops::overlay(&mut image, &techoverlay, 0, 0);
-                                    image.save(&icon_dir.join(index_key))?;
-                                }
-                            }
-                        } else {
-                            let index_key = hash::index_key("png", cache.hash_of(&icon_resource_bp)?);
-                            service_metadata.entry(*type_id).or_default().insert(IconKind::Icon, index_key.clone());
-                            service_metadata.entry(*type_id).or_default().insert(IconKind::Blueprint, index_key.clone());
-                            if !is_up_to_date(&old_index, &mut new_index, &index_key, force_rebuild) {
-                                let image = ImageReader::open(&cache.path_of(&*icon_resource_bp)?)?.with_guessed_format()?.decode()?
-                                    .resize_exact(64, 64, FilterType::Lanczos3);
-                                image.save(&icon_dir.join(index_key))?;
-                            }
-
-                            if cache.has_resource(&*icon_resource_bpc) {
-                                let index_key = hash::index_key("png", cache.hash_of(&icon_resource_bpc)?);
-                                service_metadata.entry(*type_id).or_default().insert(IconKind::BlueprintCopy, index_key.clone());
-                                if !is_up_to_date(&old_index, &mut new_index, &index_key, force_rebuild) {
-                                    let image = ImageReader::open(&cache.path_of(&*icon_resource_bpc)?)?.with_guessed_format()?.decode()?
-                                        .resize_exact(64, 64, FilterType::Lanczos3);
-                                    image.save(&icon_dir.join(index_key))?;
-                                }
-                            }
-                        }
-                    }
-                } else if let Some(icon) = type_info.icon_id { // If no graphics icon, try icon
-                    let icon_resource = &*data.icon_files.get(&icon).ok_or(IconError::String(format!("unknown icon id: {}", icon)))?;
-                    if cache.has_resource(&icon_resource) {
-                        let (techoverlay_cache, techoverlay) = get_techoverlay(type_info.meta_group_id.unwrap_or(1), icon_config.use_old_overlays).load(cache)?.unzip();
-
-                        if type_info.category_id == 34 {
-                            let index_key = hash::index_key("png", (
-                                cache.hash_of(&icon_resource)?,
-                                cache.hash_of("res:/ui/texture/icons/relic.png")?,
-                                cache.hash_of("res:/ui/texture/icons/relic_overlay.png")?,
-                                techoverlay_cache
-                            ));
-
-                            service_metadata.entry(*type_id).or_default().insert(IconKind::Icon, index_key.clone());
-                            service_metadata.entry(*type_id).or_default().insert(IconKind::Relic, index_key.clone());
-                            if !is_up_to_date(&old_index, &mut new_index, &index_key, force_rebuild) {
-                                // Relic BG/overlay
-                                composite_blueprint(
-                                    &cache.path_of("res:/ui/texture/icons/relic.png")?,
-                                    &cache.path_of("res:/ui/texture/icons/relic_overlay.png")?,
-                                    &cache.path_of(icon_resource)?,
-                                    techoverlay.as_ref(),
-                                    &icon_dir.join(index_key)
-                                )?;
-                            }
-                        } else if REACTION_GROUPS.contains(&type_info.group_id) {
-                            let index_key = hash::index_key("png", (
-                                cache.hash_of(&icon_resource)?,
-                                cache.hash_of("res:/ui/texture/icons/reaction.png")?,
-                                cache.hash_of("res:/ui/texture/icons/bpo_overlay.png")?,
-                                techoverlay_cache
-                            ));
-
-                            service_metadata.entry(*type_id).or_default().insert(IconKind::Icon, index_key.clone());
-                            service_metadata.entry(*type_id).or_default().insert(IconKind::Reaction, index_key.clone());
-                            service_metadata.entry(*type_id).or_default().insert(IconKind::Blueprint, index_key.clone());   // Incorrect behaviour of the image service, included for compatibility
-                            if !is_up_to_date(&old_index, &mut new_index, &index_key, force_rebuild) {
-                                // Reaction BG/overlay
-                                composite_blueprint(
-                                    &cache.path_of("res:/ui/texture/icons/reaction.png")?,
-                                    &cache.path_of("res:/ui/texture/icons/bpo_overlay.png")?,
-                                    &cache.path_of(icon_resource)?,
-                                    techoverlay.as_ref(),
-                                    &icon_dir.join(index_key)
-                                )?;
-                            }
-                        } else {
-                            let index_key = hash::index_key("png", (
-                                cache.hash_of(&icon_resource)?,
-                                cache.hash_of("res:/ui/texture/icons/bpo.png")?,
-                                cache.hash_of("res:/ui/texture/icons/bpo_overlay.png")?,
-                                techoverlay_cache
-                            ));
-
-                            // BP & BPC BG/overlay
-                            service_metadata.entry(*type_id).or_default().insert(IconKind::Icon, index_key.clone());
-                            service_metadata.entry(*type_id).or_default().insert(IconKind::Blueprint, index_key.clone());
-                            if !is_up_to_date(&old_index, &mut new_index, &index_key, force_rebuild) {
-                                composite_blueprint(
-                                    &cache.path_of("res:/ui/texture/icons/bpo.png")?,
-                                    &cache.path_of("res:/ui/texture/icons/bpo_overlay.png")?,
-                                    &cache.path_of(icon_resource)?,
-                                    techoverlay.as_ref(),
-                                    &icon_dir.join(index_key)
-                                )?;
-                            }
-
-                            let index_key = hash::index_key("png", (
-                                cache.hash_of(&icon_resource)?,
-                                cache.hash_of("res:/ui/texture/icons/bpc.png")?,
-                                cache.hash_of("res:/ui/texture/icons/bpc_overlay.png")?,
-                                techoverlay_cache
-                            ));
-                            service_metadata.entry(*type_id).or_default().insert(IconKind::BlueprintCopy, index_key.clone());
-                            if !is_up_to_date(&old_index, &mut new_index, &index_key, force_rebuild) {
-                                composite_blueprint(
-                                    &cache.path_of("res:/ui/texture/icons/bpc.png")?,
-                                    &cache.path_of("res:/ui/texture/icons/bpc_overlay.png")?,
-                                    &cache.path_of(icon_resource)?,
-                                    techoverlay.as_ref(),
-                                    &icon_dir.join(index_key)
-                                )?;
-                            }
-                        }
-                    } else {
-                        // Skip missing icons, sometimes they're broken in-game.
-                        if !silent_mode { println!("\tERR: Missing icon for: {}", type_id); }
-                        if let Some(mut log) = log_file { writeln!(log, "\tERR: Missing icon for: {}", type_id)?; }
-                    }
-                } else {
-                    continue; // No icon to be generated here
-                }
-            } else {
-                // Regular item
-                let graphic_info = type_info.graphic_id.and_then(|graphic_id| data.graphics_folders.get(&graphic_id));
-
-                let mut icon_resource;
-                if let Some(folder) = graphic_info.and_then(|g| g.folder.as_ref()) {
-                    icon_resource = format!("{}/{}_64.png", folder.trim_end_matches('/'), type_info.graphic_id.unwrap());
-
-                    // If no graphic, try icon
-                    if !cache.has_resource(&*icon_resource) || !type_info.is_renderable {
-                        if let Some(icon) = type_info.icon_id {
-                            icon_resource = data.icon_files.get(&icon).ok_or(IconError::String(format!("unknown icon id: {}", icon)))?.clone();
-                        } else {
-                            continue;   // No icon
-                        }
-                    }
-
-                    let render_resource = format!("{}/{}_512.jpg", folder.trim_end_matches('/'), type_info.graphic_id.unwrap());
-                    if cache.has_resource(&*render_resource) {
-                        let index_key = hash::index_key("jpg", cache.hash_of(&render_resource)?);
-                        service_metadata.entry(*type_id).or_default().insert(IconKind::Render, index_key.clone());
-                        if !is_up_to_date(&old_index, &mut new_index, &index_key, force_rebuild) {
-                            let _ = fs::copy(cache.path_of(&*render_resource)?, icon_dir.join(index_key)).map_err(IconError::from)?;
-                        }
-                    }
-                } else if let Some(icon) = type_info.icon_id {
-                    icon_resource = data.icon_files.get(&icon).ok_or(IconError::String(format!("unknown icon id: {}", icon)))?.clone();
-                } else if type_info.category_id == 91 {
-                    // SKIN
-                    if let Some(material_id) = data.skin_materials.get(type_id) {
-                        icon_resource = format!("res:/ui/texture/classes/skins/icons/{}.png", material_id);
-                    } else {
-                        continue;   // Some skins are region-exclusive and do not have the resources available on the TQ client, so skip and treat as no-icon types
-                    }
-                } else {
-                    continue; // No icon to be generated here
-                }
-
-                if !cache.has_resource(&icon_resource) {
-                    if !silent_mode { println!("\tERR: Missing icon for: {}", type_id); }
-                    if let Some(mut log) = log_file { writeln!(log, "\tERR: Missing icon for: {}", type_id)?; }
-                    continue; // Skip missing icons, sometimes they're broken in-game.
-                }
-
-                let techoverlay = get_techoverlay(type_info.meta_group_id.unwrap_or(1), icon_config.use_old_overlays).load(cache)?;
-                let moduleoverlay = get_moduleoverlay(type_info.module_slot, icon_config.use_old_overlays).load(cache)?;
-                let cloneoverlay = get_cloneoverlay(type_info.omega_required, icon_config.use_old_overlays).load(cache)?;
-
-                if let (None, None, None) = (&techoverlay, &moduleoverlay, &cloneoverlay) {
-                    // These icons are still resized, and so are copied to the icon-cache folder
-                    let index_key = hash::index_key("png", cache.hash_of(&icon_resource)?);
-                    service_metadata.entry(*type_id).or_default().insert(IconKind::Icon, index_key.clone());
-
-                    if !is_up_to_date(&old_index, &mut new_index, &index_key, force_rebuild) {
-                        let image = ImageReader::open(&cache.path_of(&*icon_resource)?)?.with_guessed_format()?.decode()?.resize_exact(64, 64, FilterType::Lanczos3);
-                        image.save(&icon_dir.join(index_key))?;
-                    }
-                } else {
-                    let (techoverlay_cache, techoverlay) = techoverlay.unzip();
-                    let (moduleoverlay_cache, moduleoverlay) = moduleoverlay.unzip();
-                    let (cloneoverlay_cache, cloneoverlay) = cloneoverlay.unzip();
-
-                    let index_key = hash::index_key("png", (
-                        cache.hash_of(&*icon_resource)?,
-                        techoverlay_cache,
-                        moduleoverlay_cache,
-                        cloneoverlay_cache
-                    ));
-
-                    service_metadata.entry(*type_id).or_default().insert(IconKind::Icon, index_key.clone());
-
-                    if !is_up_to_date(&old_index, &mut new_index, &index_key, force_rebuild) {
-                        let mut image = ImageReader::open(&cache.path_of(&icon_resource)?)?.with_guessed_format()?.decode()?.resize_exact(64, 64, FilterType::Lanczos3);
-                        if let Some(techoverlay) = techoverlay {
-                            imageops::overlay(&mut image, &techoverlay, 0, 0);
-                        }
-                        if let Some(moduleoverlay) = moduleoverlay {
-                            imageops::overlay(&mut image, &moduleoverlay, 48, 48);
-                        }
-                        if let Some(cloneoverlay) = cloneoverlay {
-                            imageops::overlay(&mut image, &cloneoverlay, 48, 0);
-                        }
-                        image.save(&icon_dir.join(index_key))?;
-                    }
-                }
-            }
+        let (icons, missing) = plan_icon_export(data, cache, icon_config)?;
+        for type_id in missing {
+            // Skip missing icons, sometimes they're broken in-game.
+            if !silent_mode { println!("\tERR: Missing icon for: {}", type_id); }
+            if let Some(mut log) = log_file { writeln!(log, "\tERR: Missing icon for: {}", type_id)?; }
         }
+        service_metadata = icon_service_metadata(&icons);
+
+        // Icons shared between types are only built once
+        let to_build = icons.iter()
+            .filter(|icon| new_index.insert(icon.index_key.clone()))
+            .filter(|icon| force_rebuild || !old_index.contains(&icon.index_key))
+            .collect::<Vec<_>>();
+        // Icons are built in two phases; Resources are fetched first, so that any downloads run in parallel separately from image compositing
+        let prefetch_resources = to_build.iter()
+            .flat_map(|icon| icon.recipe.resources())
+            .collect::<HashSet<_>>();
+        prefetch(cache, prefetch_resources)?;
+
+        let overlay_cache = OverlayCache::build(cache, icon_config, to_build.iter().map(|icon| &icon.recipe))?;
+        to_build.into_par_iter()
+            .try_for_each(|icon| icon.recipe.build(cache, icon_config, &overlay_cache, &icon_dir.join(&icon.index_key)))?;
 
         let mut sort_index = new_index.iter().map(String::as_str).collect::<Vec<_>>();
         sort_index.sort();
 
-
-        let mut first = true;
+        let mut index_bytes = Vec::new();
+        write!(index_bytes, "{}{}", INDEX_HEADER_PREFIX, hash::RECIPE_VERSION)?;
         for item in sort_index {
-            if first {
-                first = false;
-            } else {
-                index_bytes.extend(b"\x1E");
-            }
+            index_bytes.extend(b"\x1E");
             index_bytes.extend(item.as_bytes())
         }
 
@@ -804,6 +1235,10 @@ pub fn build_icon_export<C: SharedCache, P: AsRef<Path>>(icon_config: IconConfig
 
                 writer.start_file("service_metadata.json", FileOptions::<()>::default()).map_err(io::Error::other)?;
                 serde_json::to_writer_pretty(&mut writer, &service_metadata).map_err(io::Error::other)?;
+                writer.start_file("service_metadata_full.json", FileOptions::<()>::default()).map_err(io::Error::other)?;
+                serde_json::to_writer_pretty(&mut writer, &icon_file_metadata(&service_metadata, icon_dir)?).map_err(io::Error::other)?;
+                writer.start_file("manifest.json", FileOptions::<()>::default()).map_err(io::Error::other)?;
+                serde_json::to_writer_pretty(&mut writer, &icon_kind_manifest(&service_metadata)).map_err(io::Error::other)?;
 
                 writer.finish().map_err(io::Error::other)?.flush()?;
             }
@@ -822,14 +1257,14 @@ pub fn build_icon_export<C: SharedCache, P: AsRef<Path>>(icon_config: IconConfig
                     for (icon_kind, filename) in icons {
                         match icon_kind {
                             IconKind::Icon => {
-                                let output_name = format!("{}_64.png", type_id);
+                                let output_name = format!("{}_64.{}", type_id, file_extension(filename));
                                 if let Some(mut log) = log_file { writeln!(log, "\t\tType {} ({}) - {} [{}]", type_id, icon_kind, output_name, filename)?; }
                                 writer.start_file(&output_name, FileOptions::<()>::default().compression_method(CompressionMethod::Stored)).map_err(io::Error::other)?;
                                 io::copy(&mut File::open(icon_dir.join(filename))?, &mut writer)?;
                             }
                             IconKind::Blueprint | IconKind::Reaction | IconKind::Relic => { /* None, these are duplicated by IconKind::Icon */ }
                             IconKind::BlueprintCopy => {
-                                let output_name = format!("{}_bpc_64.png", type_id);
+                                let output_name = format!("{}_bpc_64.{}", type_id, file_extension(filename));
                                 if let Some(mut log) = log_file { writeln!(log, "\t\tType {} ({}) - {} [{}]", type_id, icon_kind, output_name, filename)?; }
                                 writer.start_file(&output_name, FileOptions::<()>::default().compression_method(CompressionMethod::Stored)).map_err(io::Error::other)?;
                                 io::copy(&mut File::open(icon_dir.join(filename))?, &mut writer)?;
@@ -843,6 +1278,8 @@ pub fn build_icon_export<C: SharedCache, P: AsRef<Path>>(icon_config: IconConfig
                         }
                     }
                 }
+                writer.start_file("manifest.json", FileOptions::<()>::default()).map_err(io::Error::other)?;
+                serde_json::to_writer_pretty(&mut writer, &icon_kind_manifest(&service_metadata)).map_err(io::Error::other)?;
                 writer.finish().map_err(io::Error::other)?.flush()?;
             }
             OutputMode::Web { out, copy_files, hard_link } => {
@@ -855,7 +1292,8 @@ pub fn build_icon_export<C: SharedCache, P: AsRef<Path>>(icon_config: IconConfig
                 let mode_name = if copy_files { "COPYING" } else if hard_link { "HARD LINK" } else { "SOFT LINK" };
                 if !silent_mode { println!("\tBuilding web folder to {:?} ({})", out, mode_name); }
                 if let Some(mut log) = log_file { writeln!(log, "\tBuilding web folder to {:?} ({})", out, mode_name)?; }
-                let mut created_files = HashMap::<String, String>::new();
+                // Kept in insertion order; service_metadata is sorted by TypeID, so index.json content is stable across runs
+                let mut created_files = Vec::<(String, String)>::new();
 
                 let index_path = out.join("index.json");
                 let old_links = if fs::exists(&index_path)? {
@@ -874,10 +1312,10 @@ pub fn build_icon_export<C: SharedCache, P: AsRef<Path>>(icon_config: IconConfig
                     if force_rebuild || old_links.get(&json_name) != Some(&json_content) {
                         fs::write(&json_filename, json_content.as_bytes())?;
                     }
-                    created_files.insert(json_name, json_content);
+                    created_files.push((json_name, json_content));
 
                     for (icon_kind, filename) in icons {
-                        let link_name = format!("{}_{}.{}", type_id, icon_kind.name(), if IconKind::Render == *icon_kind { "jpg" } else { "png" });
+                        let link_name = format!("{}_{}.{}", type_id, icon_kind.name(), file_extension(filename));
                         let link_source = std::path::absolute(icon_dir.join(filename))?;
                         let link_file = std::path::absolute(out.join(&link_name))?;
 
@@ -900,12 +1338,15 @@ pub fn build_icon_export<C: SharedCache, P: AsRef<Path>>(icon_config: IconConfig
                         } else {
                             if let Some(mut log) = log_file { writeln!(log, "\t\tSKIP: {}", &link_name)?; }
                         }
-                        created_files.insert(link_name, filename.clone());
+                        created_files.push((link_name, filename.clone()));
                     }
                 }
 
-                for entry in old_links.keys() {
-                    if !created_files.contains_key(entry) {
+                let created_names = created_files.iter().map(|(name, _)| name.as_str()).collect::<HashSet<_>>();
+                let mut old_entries = old_links.keys().collect::<Vec<_>>();
+                old_entries.sort();
+                for entry in old_entries {
+                    if !created_names.contains(entry.as_str()) {
                         if let Some(mut log) = log_file { writeln!(log, "\t\tRemoved: {}", &entry)?; }
                         match fs::remove_file(out.join(entry)) {
                             Ok(()) => Ok(()),
@@ -914,21 +1355,59 @@ pub fn build_icon_export<C: SharedCache, P: AsRef<Path>>(icon_config: IconConfig
                         }?;
                     }
                 }
-                serde_json::to_writer(File::create(&index_path)?, &created_files).map_err(io::Error::other)?;
+                let mut index_writer = BufWriter::new(File::create(&index_path)?);
+                serde_json::Serializer::new(&mut index_writer)
+                    .collect_map(created_files.iter().map(|(name, content)| (name, content)))
+                    .map_err(io::Error::other)?;
+                index_writer.flush()?;
             }
             OutputMode::Checksum { out } => {
                 // Checksum is never skipped
                 assert!(DO_INDEX_UPDATE);
-                let checksum = md5::compute(&index_bytes);
+                let checksum = icon_checksum(&service_metadata);
                 if let Some(mut log) = log_file { writeln!(log, "Checksum:{:x}", checksum)?; }
                 if let Some(outfile) = out {
                     if !silent_mode { println!("\tWriting checksum to {:?}", outfile); }
                     fs::write(outfile, format!("{:x}", checksum))?
                 } else {
                     assert!(silent_mode);
-                    print!("{:x}", md5::compute(&index_bytes))
+                    print!("{:x}", checksum)
                 }
             },
+            OutputMode::DeltaBundle { out, base_index } => {
+                // Delta bundles are relative to the base index rather than the previous run, so are never skipped
+                if !silent_mode { println!("\tWriting Delta Bundle to {:?} (base: {:?})", out, base_index); }
+                if let Some(mut log) = log_file { writeln!(log, "\tWriting Delta Bundle to {:?} (base: {:?})", out, base_index)?; }
+                let (_, base) = read_index(base_index)?;
+
+                #[derive(Serialize)]
+                struct DeltaManifest<'a> {
+                    added: Vec<&'a str>,
+                    removed: Vec<&'a str>,
+                }
+                let mut manifest = DeltaManifest {
+                    added: new_index.iter().filter(|key| !base.contains(*key)).map(String::as_str).collect(),
+                    removed: base.iter().filter(|key| !new_index.contains(*key)).map(String::as_str).collect(),
+                };
+                manifest.added.sort();
+                manifest.removed.sort();
+
+                let mut writer = ZipWriter::new(File::create(out)?);
+                for filename in &manifest.added {
+                    if let Some(mut log) = log_file { writeln!(log, "\t\tAdded: {}", filename)?; }
+                    writer.start_file(*filename, FileOptions::<()>::default().compression_method(CompressionMethod::Stored)).map_err(io::Error::other)?;
+                    io::copy(&mut File::open(icon_dir.join(filename))?, &mut writer)?;
+                }
+
+                writer.start_file("service_metadata.json", FileOptions::<()>::default()).map_err(io::Error::other)?;
+                serde_json::to_writer_pretty(&mut writer, &service_metadata).map_err(io::Error::other)?;
+                writer.start_file("service_metadata_full.json", FileOptions::<()>::default()).map_err(io::Error::other)?;
+                serde_json::to_writer_pretty(&mut writer, &icon_file_metadata(&service_metadata, icon_dir)?).map_err(io::Error::other)?;
+                writer.start_file("delta_manifest.json", FileOptions::<()>::default()).map_err(io::Error::other)?;
+                serde_json::to_writer_pretty(&mut writer, &manifest).map_err(io::Error::other)?;
+
+                writer.finish().map_err(io::Error::other)?.flush()?;
+            }
             // Auxiliary outputs don't use the icon cache, but updating/checking it is quite fast so these outputs don't skip it
             OutputMode::AuxShipTreeRenders { out } => {
                 if !silent_mode { println!("\tWriting Auxiliary Ship Tree Render archive to {:?}", out); }
@@ -974,6 +1453,27 @@ pub fn build_icon_export<C: SharedCache, P: AsRef<Path>>(icon_config: IconConfig
                     }
                 }
             }
+            OutputMode::Renders { out } => {
+                if !silent_mode { println!("\tWriting Render archive to {:?}", out); }
+                if let Some(mut log) = log_file { writeln!(log, "\tWriting Render archive to {:?}", out)?; }
+                let mut writer = ZipWriter::new(File::create(out)?);
+
+                let mut type_ids = data.types.keys().copied().collect::<Vec<_>>();
+                type_ids.sort();
+                for type_id in type_ids {
+                    // Same renders as the service bundle, without building the other icons
+                    for planned_icon in plan_type_icons(type_id, &data.types[&type_id], data, icon_config, |resource| cache.has_resource(resource))? {
+                        let PlannedIcon::Build { kinds, recipe: IconRecipe::Copy { resource } } = planned_icon else { continue };
+                        if !kinds.contains(&IconKind::Render) { continue; }
+
+                        let output_name = format!("{}_512.jpg", type_id);
+                        if let Some(mut log) = log_file { writeln!(log, "\t\tType {} - {} [{}]", type_id, output_name, resource)?; }
+                        writer.start_file(&output_name, FileOptions::<()>::default().compression_method(CompressionMethod::Stored)).map_err(io::Error::other)?;
+                        io::copy(&mut File::open(cache.path_of(&resource)?)?, &mut writer)?;
+                    }
+                }
+                writer.finish().map_err(io::Error::other)?.flush()?;
+            }
             OutputMode::AuxIcons { out } => {
                 if !silent_mode { println!("\tWriting Auxiliary Icon dump archive to {:?}", out); }
                 if let Some(mut log) = log_file { writeln!(log, "\tWriting Auxiliary Icon dump archive to {:?}", out)?; }
@@ -992,6 +1492,28 @@ pub fn build_icon_export<C: SharedCache, P: AsRef<Path>>(icon_config: IconConfig
                 }
                 writer.finish().map_err(io::Error::other)?.flush()?;
             }
+            OutputMode::AuxFactionLogos { out } => {
+                if !silent_mode { println!("\tWriting Auxiliary Faction Logo archive to {:?}", out); }
+                if let Some(mut log) = log_file { writeln!(log, "\tWriting Auxiliary Faction Logo archive to {:?}", out)?; }
+                let mut writer = ZipWriter::new(File::create(out)?);
+
+                let mut faction_ids = data.faction_icons.keys().copied().collect::<Vec<_>>();
+                faction_ids.sort();
+                for faction_id in faction_ids {
+                    let icon_id = data.faction_icons[&faction_id];
+                    let Some(resource) = data.icon_files.get(&icon_id).filter(|resource| cache.has_resource(resource)) else {
+                        if !silent_mode { println!("\t\tERR: Missing logo for faction: {}", faction_id); }
+                        if let Some(mut log) = log_file { writeln!(log, "\t\tERR: Missing logo for faction: {}", faction_id)?; }
+                        continue;
+                    };
+
+                    let output_name = format!("{}.{}", faction_id, file_extension(resource));
+                    if let Some(mut log) = log_file { writeln!(log, "\t\tFaction {} - {} [{}]", faction_id, output_name, resource)?; }
+                    writer.start_file(&output_name, FileOptions::<()>::default().compression_method(CompressionMethod::Stored)).map_err(io::Error::other)?;
+                    io::copy(&mut File::open(cache.path_of(resource)?)?, &mut writer)?;
+                }
+                writer.finish().map_err(io::Error::other)?.flush()?;
+            }
             OutputMode::AuxImages { out, incl_character } => {
                 if !silent_mode { println!("\tWriting Auxiliary All-Images dump archive to {:?}", out); }
                 if let Some(mut log) = log_file { writeln!(log, "\tWriting Auxiliary All-Images dump archive to {:?}", out)?; }
@@ -1027,3 +1549,293 @@ pub fn build_icon_export<C: SharedCache, P: AsRef<Path>>(icon_config: IconConfig
 
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use evesharedcache::cache::{MemoryCache, ResourceInfo};
+
+    const CONFIG: IconConfig = IconConfig { use_old_overlays: false, module_overlays: false, clone_overlays: false, icon_size: DEFAULT_ICON_SIZE, format: IconFormat::Png, overlay_blend: BlendMode::Additive, jpeg_quality: DEFAULT_JPEG_QUALITY };
+
+    /// Temporary directory for a test, removed when dropped so that failing tests also clean up
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> io::Result<TempDir> {
+            let dir = std::env::temp_dir().join(format!("eveicongenerator_{}_{}", name, std::process::id()));
+            fs::create_dir_all(&dir)?;
+            Ok(TempDir(dir))
+        }
+    }
+
+    impl std::ops::Deref for TempDir {
+        type Target = Path;
+
+        fn deref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn type_info(group_id: ids::GroupID, category_id: ids::CategoryID, icon_id: Option<ids::IconID>, graphic_id: Option<ids::GraphicID>) -> TypeInfo {
+        TypeInfo { group_id, category_id, icon_id, graphic_id, meta_group_id: None, is_renderable: true, module_slot: None, omega_required: None }
+    }
+
+    fn build_data() -> IconBuildData {
+        IconBuildData {
+            types: HashMap::new(),
+            icon_files: HashMap::from([
+                (1, "res:/ui/texture/icons/1.png".to_string()),
+                (2, "res:/ui/texture/icons/missing.png".to_string()),
+            ]),
+            graphics_folders: HashMap::from([
+                (10, GraphicInfo { folder: Some("res:/graphics/10".to_string()), hull: None }),
+            ]),
+            skin_materials: HashMap::from([(500, 7)]),
+            faction_icons: HashMap::new(),
+        }
+    }
+
+    fn has_resource(resource: &str) -> bool {
+        [
+            "res:/ui/texture/icons/1.png",
+            "res:/graphics/10/10_64.png",
+            "res:/graphics/10/10_512.jpg",
+            "res:/graphics/10/10_64_bp.png",
+            "res:/ui/texture/classes/skins/icons/7.png",
+        ].contains(&resource)
+    }
+
+    fn resize(resource: &str) -> IconRecipe {
+        IconRecipe::Resize { resource: resource.to_string(), tech_overlay: IconOverlay::None, module_overlay: IconOverlay::None, clone_overlay: IconOverlay::None }
+    }
+
+    fn composite(background: &'static str, overlay: &'static str) -> IconRecipe {
+        IconRecipe::Composite { background, overlay, resource: "res:/ui/texture/icons/1.png".to_string(), tech_overlay: IconOverlay::None }
+    }
+
+    #[test]
+    fn test_plan_type_icons() {
+        let data = build_data();
+
+        let cases: [(&str, ids::TypeID, TypeInfo, Vec<PlannedIcon>); 9] = [
+            ("no icon", 1, type_info(1, 1, None, None), vec![]),
+            ("icon", 1, type_info(1, 1, Some(1), None), vec![
+                PlannedIcon::Build { kinds: &[IconKind::Icon], recipe: resize("res:/ui/texture/icons/1.png") }
+            ]),
+            ("graphic with render", 1, type_info(1, 6, Some(1), Some(10)), vec![
+                PlannedIcon::Build { kinds: &[IconKind::Render], recipe: IconRecipe::Copy { resource: "res:/graphics/10/10_512.jpg".to_string() } },
+                PlannedIcon::Build { kinds: &[IconKind::Icon], recipe: resize("res:/graphics/10/10_64.png") },
+            ]),
+            ("missing icon", 1, type_info(1, 1, Some(2), None), vec![
+                PlannedIcon::Missing { resource: "res:/ui/texture/icons/missing.png".to_string() }
+            ]),
+            ("skin", 500, type_info(1950, 91, None, None), vec![
+                PlannedIcon::Build { kinds: &[IconKind::Icon], recipe: resize("res:/ui/texture/classes/skins/icons/7.png") }
+            ]),
+            ("blueprint graphic", 1, type_info(1, 9, None, Some(10)), vec![
+                PlannedIcon::Build { kinds: &[IconKind::Icon, IconKind::Blueprint], recipe: resize("res:/graphics/10/10_64_bp.png") }
+            ]),
+            ("blueprint icon", 1, type_info(1, 9, Some(1), None), vec![
+                PlannedIcon::Build { kinds: &[IconKind::Icon, IconKind::Blueprint], recipe: composite("res:/ui/texture/icons/bpo.png", "res:/ui/texture/icons/bpo_overlay.png") },
+                PlannedIcon::Build { kinds: &[IconKind::BlueprintCopy], recipe: composite("res:/ui/texture/icons/bpc.png", "res:/ui/texture/icons/bpc_overlay.png") },
+            ]),
+            ("reaction", 1, type_info(REACTION_GROUPS[0], 9, Some(1), None), vec![
+                PlannedIcon::Build { kinds: &[IconKind::Icon, IconKind::Reaction, IconKind::Blueprint], recipe: composite("res:/ui/texture/icons/reaction.png", "res:/ui/texture/icons/bpo_overlay.png") },
+            ]),
+            ("relic", 1, type_info(1, 34, Some(1), None), vec![
+                PlannedIcon::Build { kinds: &[IconKind::Icon, IconKind::Relic], recipe: composite("res:/ui/texture/icons/relic.png", "res:/ui/texture/icons/relic_overlay.png") },
+            ]),
+        ];
+
+        for (name, type_id, info, expected) in cases {
+            let planned = plan_type_icons(type_id, &info, &data, CONFIG, has_resource).expect("planning should not fail");
+            assert_eq!(planned, expected, "case: {}", name);
+        }
+    }
+
+    #[test]
+    fn test_alpha_skill_without_dogma() -> Result<(), IconError> {
+        let sde = SDE_Full {
+            groups: [(255, serde_json::from_str(r#"{"_key": 255, "anchorable": false, "anchored": false, "categoryID": 16, "fittableNonSingleton": false, "name": {"en": "Gunnery"}, "published": true, "useBasePrice": false}"#).unwrap())].into_iter().collect(),
+            types: [
+                (3300, serde_json::from_str(r#"{"_key": 3300, "groupID": 255, "iconID": 33, "name": {"en": "Gunnery"}, "portionSize": 1, "published": true}"#).unwrap()),
+                (3301, serde_json::from_str(r#"{"_key": 3301, "groupID": 255, "iconID": 33, "name": {"en": "Small Hybrid Turret"}, "portionSize": 1, "published": true}"#).unwrap()),
+            ].into_iter().collect(),
+            type_lists: [(RENDERABLE_TYPELIST, serde_json::from_str(r#"{"_key": 140, "name": "RenderableTypeIDs"}"#).unwrap())].into_iter().collect(),
+            clone_grades: [(1, serde_json::from_str(r#"{"_key": 1, "name": "Alpha", "skills": [{"typeID": 3300, "level": 4}]}"#).unwrap())].into_iter().collect(),
+            ..SDE_Full::default()
+        };
+
+        let data = IconBuildData::from_sde(&sde, IconConfig { clone_overlays: true, ..CONFIG })?;
+        assert_eq!(data.types[&3300].omega_required, Some(false));
+        assert_eq!(data.types[&3301].omega_required, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_plan_unknown_icon() {
+        let result = plan_type_icons(1, &type_info(1, 1, Some(3), None), &build_data(), CONFIG, has_resource);
+        assert!(matches!(result, Err(IconError::UnknownIcon(3))));
+    }
+
+    #[test]
+    fn test_icon_size() -> Result<(), IconError> {
+        let dir = TempDir::new("test_icon_size")?;
+        let source = dir.join("source.png");
+        DynamicImage::new_rgba8(64, 64).save(&source)?;
+        let overlay = DynamicImage::new_rgba8(CONFIG.overlay_size(), CONFIG.overlay_size());
+
+        for icon_size in [32, DEFAULT_ICON_SIZE, 128] {
+            let icon = dir.join(format!("icon_{}.png", icon_size));
+            composite_icon(&source, Some(&overlay), Some(&overlay), Some(&overlay), icon_size, &icon)?;
+            assert_eq!(image::image_dimensions(&icon)?, (icon_size, icon_size));
+
+            let blueprint = dir.join(format!("blueprint_{}.png", icon_size));
+            let background = load_resized(&source, icon_size)?;
+            composite_blueprint(&background, &background, &source, Some(&overlay), icon_size, BlendMode::Additive, &blueprint)?;
+            assert_eq!(image::image_dimensions(&blueprint)?, (icon_size, icon_size));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_webp_output() -> Result<(), IconError> {
+        let dir = TempDir::new("test_webp")?;
+        let source = dir.join("source.png");
+        DynamicImage::new_rgba8(64, 64).save(&source)?;
+
+        let icon = dir.join(format!("icon.{}", IconFormat::WebP.extension()));
+        composite_icon(&source, None, None, None, DEFAULT_ICON_SIZE, &icon)?;
+        assert_eq!(ImageReader::open(&icon)?.with_guessed_format()?.format(), Some(ImageFormat::WebP));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_render() -> Result<(), IconError> {
+        let dir = TempDir::new("test_copy_render")?;
+
+        let mut jpeg = Vec::new();
+        DynamicImage::new_rgb8(64, 64).write_to(&mut Cursor::new(&mut jpeg), ImageFormat::Jpeg)?;
+        let mut png = Vec::new();
+        DynamicImage::new_rgba8(64, 64).write_to(&mut Cursor::new(&mut png), ImageFormat::Png)?;
+        let cache = MemoryCache::new("0", HashMap::from([
+            ("res:/graphics/1/1_512.jpg".to_string(), jpeg.clone()),
+            ("res:/graphics/2/2_512.jpg".to_string(), png),
+        ]));
+
+        // JPEG source is copied without re-encoding
+        let out = dir.join("1.jpg");
+        IconRecipe::Copy { resource: "res:/graphics/1/1_512.jpg".to_string() }.build(&cache, CONFIG, &OverlayCache::default(), &out)?;
+        assert_eq!(fs::read(&out)?, jpeg);
+
+        // Non-JPEG source with a JPEG resource name is converted
+        let out = dir.join("2.jpg");
+        IconRecipe::Copy { resource: "res:/graphics/2/2_512.jpg".to_string() }.build(&cache, CONFIG, &OverlayCache::default(), &out)?;
+        assert_eq!(ImageReader::open(&out)?.with_guessed_format()?.format(), Some(ImageFormat::Jpeg));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_icon_file_metadata() -> Result<(), IconError> {
+        let dir = TempDir::new("test_file_metadata")?;
+        DynamicImage::new_rgba8(64, 64).save(dir.join("A.png"))?;
+        DynamicImage::new_rgb8(512, 512).save(dir.join("B.jpg"))?;
+
+        let service_metadata = BTreeMap::from([
+            (1, BTreeMap::from([(IconKind::Icon, "A.png".to_string()), (IconKind::Render, "B.jpg".to_string())])),
+            (2, BTreeMap::from([(IconKind::Icon, "A.png".to_string())])),
+        ]);
+        let metadata = icon_file_metadata(&service_metadata, &dir)?;
+        assert_eq!(metadata[&1][&IconKind::Render], IconFileInfo { filename: "B.jpg", width: 512, height: 512, format: "jpg" });
+        assert_eq!(metadata[&2][&IconKind::Icon], IconFileInfo { filename: "A.png", width: 64, height: 64, format: "png" });
+
+        Ok(())
+    }
+
+    /// In-memory cache with resource hashes only, for planning icons
+    struct HashOnlyCache(HashMap<String, String>);
+
+    impl SharedCache for HashOnlyCache {
+        fn client_version(&self) -> &str { "0" }
+        fn iter_resources(&self) -> impl Iterator<Item=&str> { self.0.keys().map(String::as_str) }
+        fn has_resource(&self, resource: &str) -> bool { self.0.contains_key(resource) }
+        fn fetch(&self, resource: &str) -> Result<Vec<u8>, CacheError> { Err(CacheError::ResourceNotFound(resource.to_string())) }
+        fn try_fetch(&self, _resource: &str) -> Result<Option<Vec<u8>>, CacheError> { Ok(None) }
+        fn path_of(&self, resource: &str) -> Result<PathBuf, CacheError> { Err(CacheError::ResourceNotFound(resource.to_string())) }
+        fn hash_of(&self, resource: &str) -> Result<&str, CacheError> {
+            self.0.get(resource).map(String::as_str).ok_or_else(|| CacheError::ResourceNotFound(resource.to_string()))
+        }
+        fn resource_size(&self, resource: &str) -> Result<u64, CacheError> { Err(CacheError::ResourceNotFound(resource.to_string())) }
+        fn resource_info(&self, _resource: &str) -> Option<ResourceInfo> { None }
+        fn verify(&self, _resource: &str) -> Result<bool, CacheError> { Ok(false) }
+    }
+
+    #[test]
+    fn test_icon_checksum() -> Result<(), IconError> {
+        let cache = HashOnlyCache(
+            [
+                "res:/ui/texture/icons/1.png",
+                "res:/graphics/10/10_64.png",
+                "res:/graphics/10/10_512.jpg",
+                "res:/graphics/10/10_64_bp.png",
+                "res:/ui/texture/icons/bpo.png",
+                "res:/ui/texture/icons/bpo_overlay.png",
+                "res:/ui/texture/icons/bpc.png",
+                "res:/ui/texture/icons/bpc_overlay.png",
+            ].into_iter()
+                .map(|resource| (resource.to_string(), format!("{:x}", md5::compute(resource))))
+                .collect()
+        );
+        let mut data = build_data();
+        data.types = HashMap::from([
+            (1, type_info(1, 1, Some(1), None)),
+            (2, type_info(1, 6, None, Some(10))),
+            (3, type_info(1, 9, None, Some(10))),
+            (4, type_info(1, 9, Some(1), None)),
+            (5, type_info(1, 1, Some(2), None)),
+        ]);
+
+        let (icons, missing) = plan_icon_export(&data, &cache, CONFIG)?;
+        assert_eq!(missing, [5]);
+        let checksum = icon_checksum(&icon_service_metadata(&icons));
+        assert_eq!(format!("{:x}", checksum), "9a956cf7ef6bd68fbc0623bea39f46bb");
+        Ok(())
+    }
+
+    /// Deterministic test image with gradients in each channel
+    fn gradient_image(size: u32, seed: u32) -> DynamicImage {
+        DynamicImage::ImageRgba8(image::RgbaImage::from_fn(size, size, |x, y| {
+            image::Rgba([(x * 4 + seed) as u8, (y * 4 + seed) as u8, ((x + y) * 2) as u8, (255 - (x * y) % 256) as u8])
+        }))
+    }
+
+    #[test]
+    fn test_blueprint_blend_reference() -> Result<(), IconError> {
+        let dir = TempDir::new("test_blend")?;
+        let (background, overlay, icon) = (dir.join("background.png"), dir.join("overlay.png"), dir.join("icon.png"));
+        gradient_image(64, 0).save(&background)?;
+        gradient_image(64, 100).save(&overlay)?;
+        gradient_image(64, 50).save(&icon)?;
+
+        let out = dir.join("blueprint.png");
+        composite_blueprint(&load_resized(&background, DEFAULT_ICON_SIZE)?, &load_resized(&overlay, DEFAULT_ICON_SIZE)?, &icon, None, DEFAULT_ICON_SIZE, BlendMode::Additive, &out)?;
+
+        let result = ImageReader::open(&out)?.with_guessed_format()?.decode()?;
+        assert_eq!(result.color(), image::ColorType::Rgba8, "alpha channel must be preserved");
+
+        let mut reference = ImageReader::new(Cursor::new(include_bytes!("./rsc/test/blueprint_additive.png")));
+        reference.set_format(ImageFormat::Png);
+        assert!(result.into_rgba8() == reference.decode()?.into_rgba8(), "blueprint compositing differs from reference image");
+
+        Ok(())
+    }
+}
@@ -2,17 +2,18 @@ pub const CRATE_NAME: &'static str = env!("CARGO_PKG_NAME");
 pub const CRATE_VERSION: &'static str = env!("CARGO_PKG_VERSION");
 pub const CRATE_REPO: &'static str = env!("CARGO_PKG_REPOSITORY");
 
-use crate::icons::{IconBuildData, IconConfig, IconError, OutputMode};
-use evesharedcache::cache::CacheDownloader;
+use crate::icons::{BlendMode, IconBuildData, IconConfig, IconError, IconFormat, OutputMode};
+use evesharedcache::cache::{CacheDownloader, ClientPlatform, DownloadOptions, GameServer};
 use std::time::Instant;
-use std::{fs, io};
+use std::fs;
 use std::fs::File;
 use std::path::PathBuf;
 use std::sync::OnceLock;
-use clap::{Arg, ArgAction, Command};
+use clap::{value_parser, Arg, ArgAction, Command};
 use clap::builder::ValueParser;
 use std::io::Write;
 use evestaticdata::sde::load::SDELoader;
+use evestaticdata::util::user_agent::UserAgent;
 
 pub mod icons;
 
@@ -48,6 +49,10 @@ fn do_main() -> Result<(), IconError> {
                 .required_unless_present("user_agent")
                 .conflicts_with("user_agent")
                 .value_parser(ValueParser::path_buf()),
+            Arg::new("strict_user_agent")
+                .long("strict_user_agent")
+                .help("Reject malformed User Agents or User Agents without contact information, rather than printing a warning")
+                .action(ArgAction::SetTrue),
             Arg::new("cache_folder")
                 .short('c')
                 .long("cache_folder")
@@ -96,6 +101,26 @@ fn do_main() -> Result<(), IconError> {
                 .long("clone_overlays")
                 .help("Add clone restriction overlays (CUSTOM)")
                 .action(ArgAction::SetTrue),
+            Arg::new("icon_size")
+                .long("icon_size")
+                .help("Width and height of generated icons, in pixels")
+                .default_value("64")
+                .value_parser(value_parser!(u32).range(4..)),
+            Arg::new("format")
+                .long("format")
+                .help("Image format of generated icons, renders are always JPEG")
+                .value_parser(["png", "webp"])
+                .default_value("png"),
+            Arg::new("jpeg_quality")
+                .long("jpeg_quality")
+                .help("Quality (1-100) of renders that must be converted to JPEG, renders already in JPEG format are copied as-is")
+                .default_value("90")
+                .value_parser(value_parser!(u8).range(1..=100)),
+            Arg::new("overlay_blend")
+                .long("overlay_blend")
+                .help("Blend mode for blueprint overlays")
+                .value_parser(["additive", "screen", "normal"])
+                .default_value("additive"),
             Arg::new("no_purge")
                 .long("no_purge")
                 .help("Do not purge icon cache folder")
@@ -156,6 +181,23 @@ fn do_main() -> Result<(), IconError> {
                         .value_name("FILE")
                         .value_parser(ValueParser::path_buf())
                 ),
+            Command::new("delta_bundle")
+                .about("Image Service hosting bundle, containing only icons changed since a previous icon index")
+                .args([
+                    Arg::new("out")
+                        .short('o')
+                        .long("out")
+                        .required(true)
+                        .help("Output file")
+                        .value_name("FILE")
+                        .value_parser(ValueParser::path_buf()),
+                    Arg::new("base_index")
+                        .long("base_index")
+                        .required(true)
+                        .help("Icon index (cache.csv) of the previous bundle")
+                        .value_name("FILE")
+                        .value_parser(ValueParser::path_buf()),
+                ]),
             Command::new("aux_shiptree")
                 .about("Auxiliary Ship Tree Render dump (zip)")
                 .arg(
@@ -167,6 +209,17 @@ fn do_main() -> Result<(), IconError> {
                         .value_name("FILE")
                         .value_parser(ValueParser::path_buf())
                 ),
+            Command::new("renders")
+                .about("512px type renders only (zip), skips building icons")
+                .arg(
+                    Arg::new("out")
+                        .short('o')
+                        .long("out")
+                        .required(true)
+                        .help("Output file")
+                        .value_name("FILE")
+                        .value_parser(ValueParser::path_buf())
+                ),
             Command::new("aux_icons")
                 .about("Auxiliary Icon dump (zip)")
                 .arg(
@@ -178,6 +231,17 @@ fn do_main() -> Result<(), IconError> {
                         .value_name("FILE")
                         .value_parser(ValueParser::path_buf())
                 ),
+            Command::new("aux_factions")
+                .about("Auxiliary Faction Logo dump (zip)")
+                .arg(
+                    Arg::new("out")
+                        .short('o')
+                        .long("out")
+                        .required(true)
+                        .help("Output file")
+                        .value_name("FILE")
+                        .value_parser(ValueParser::path_buf())
+                ),
             Command::new("aux_all")
                 .about("Auxiliary All-Images dump (zip)")
                 .args([
@@ -205,6 +269,18 @@ fn do_main() -> Result<(), IconError> {
                         .help("Output Image Export Collection")
                         .value_name("FILE")
                         .value_parser(ValueParser::path_buf()),
+                    Arg::new("delta_bundle")
+                        .long("delta_bundle")
+                        .help("Output delta service bundle")
+                        .value_name("FILE")
+                        .requires("delta_base")
+                        .value_parser(ValueParser::path_buf()),
+                    Arg::new("delta_base")
+                        .long("delta_base")
+                        .help("(delta_bundle) Icon index (cache.csv) of the previous bundle")
+                        .value_name("FILE")
+                        .requires("delta_bundle")
+                        .value_parser(ValueParser::path_buf()),
                     Arg::new("web_dir")
                         .long("web_dir")
                         .help("Prepare a directory for web hosting")
@@ -237,11 +313,21 @@ fn do_main() -> Result<(), IconError> {
                         .help("Output Auxiliary Ship Tree Render dump")
                         .value_name("FILE")
                         .value_parser(ValueParser::path_buf()),
+                    Arg::new("renders")
+                        .long("renders")
+                        .help("Output type render archive")
+                        .value_name("FILE")
+                        .value_parser(ValueParser::path_buf()),
                     Arg::new("aux_icons")
                         .long("aux_icons")
                         .help("Output Auxiliary Icon dump")
                         .value_name("FILE")
                         .value_parser(ValueParser::path_buf()),
+                    Arg::new("aux_factions")
+                        .long("aux_factions")
+                        .help("Output Auxiliary Faction Logo dump")
+                        .value_name("FILE")
+                        .value_parser(ValueParser::path_buf()),
                     Arg::new("aux_all")
                         .long("aux_all")
                         .help("Output Auxiliary All-Images dump")
@@ -266,7 +352,7 @@ fn do_main() -> Result<(), IconError> {
             if !fs::exists(out)? {
                 fs::create_dir_all(out)?;
             } else if fs::metadata(out)?.is_file() {
-                Err(io::Error::other(format!("Output must be a directory! ({})", out.to_string_lossy())))?;
+                Err(IconError::BadOutputPath(out.to_path_buf()))?;
             }
             vec![OutputMode::Web {
                 out,
@@ -274,9 +360,15 @@ fn do_main() -> Result<(), IconError> {
                 hard_link: command_args.get_flag("hardlink")
             }]
         },
+        "delta_bundle" => vec![OutputMode::DeltaBundle {
+            out: command_args.get_one::<PathBuf>("out").expect("out is required"),
+            base_index: command_args.get_one::<PathBuf>("base_index").expect("base_index is required")
+        }],
         "checksum" => { vec![OutputMode::Checksum { out: command_args.get_one::<PathBuf>("out").map(PathBuf::as_path) }] },
         "aux_shiptree" => vec![OutputMode::AuxShipTreeRenders { out: &command_args.get_one::<PathBuf>("out").expect("out is required") }],
+        "renders" => vec![OutputMode::Renders { out: command_args.get_one::<PathBuf>("out").expect("out is required") }],
         "aux_icon" => vec![OutputMode::AuxIcons { out: &command_args.get_one::<PathBuf>("out").expect("out is required") }],
+        "aux_factions" => vec![OutputMode::AuxFactionLogos { out: command_args.get_one::<PathBuf>("out").expect("out is required") }],
         "aux_all" => {
             vec![OutputMode::AuxImages {
                 out: &command_args.get_one::<PathBuf>("out").expect("out is required"),
@@ -294,6 +386,10 @@ fn do_main() -> Result<(), IconError> {
                 output_modes.push(OutputMode::IEC { out })
             }
 
+            if let Some(out) = command_args.get_one::<PathBuf>("delta_bundle") {
+                output_modes.push(OutputMode::DeltaBundle { out, base_index: command_args.get_one::<PathBuf>("delta_base").expect("delta_base is required by delta_bundle") })
+            }
+
             if let Some(out) = command_args.get_one::<PathBuf>("web_dir") {
                 output_modes.push(OutputMode::Web {
                     out,
@@ -302,6 +398,10 @@ fn do_main() -> Result<(), IconError> {
                 })
             }
 
+            if let Some(out) = command_args.get_one::<PathBuf>("renders") {
+                output_modes.push(OutputMode::Renders { out })
+            }
+
             if let Some(out) = command_args.get_one::<PathBuf>("aux_icons") {
                 output_modes.push(OutputMode::AuxIcons { out })
             }
@@ -310,6 +410,10 @@ fn do_main() -> Result<(), IconError> {
                 output_modes.push(OutputMode::AuxShipTreeRenders { out })
             }
 
+            if let Some(out) = command_args.get_one::<PathBuf>("aux_factions") {
+                output_modes.push(OutputMode::AuxFactionLogos { out })
+            }
+
             if let Some(out) = command_args.get_one::<PathBuf>("aux_all") {
                 output_modes.push(OutputMode::AuxImages { out, incl_character: command_args.get_flag("incl_character") })
             }
@@ -347,14 +451,23 @@ fn do_main() -> Result<(), IconError> {
     let mut user_agent = match (arg_matches.get_one::<String>("user_agent"), arg_matches.get_one::<PathBuf>("user_agent_file")) {
         (Some(_), Some(_)) => unreachable!("Only one UA option may be set"),
         (Some(ua), None) => ua.clone(),
-        (None, Some(ua_file)) => fs::read_to_string(ua_file).map_err(|err| IconError::String(format!("could not read User Agent file: {}", err)))?,
+        (None, Some(ua_file)) => fs::read_to_string(ua_file).map_err(|err| IconError::Other(format!("could not read User Agent file: {}", err)))?,
         (None, None) => unreachable!("At least one UA option must be set"),
     };
 
+    let silent_mode = arg_matches.get_flag("silent"); // icons::build_icon_export overrides this to `true` if "checksum to stdout" is present
+
+    if let Err(err) = UserAgent::parse(&user_agent) {
+        if arg_matches.get_flag("strict_user_agent") {
+            Err(IconError::Other(format!("invalid User Agent: {}", err)))?;
+        }
+        if !silent_mode { println!("WARNING: {}, your requests may be blocked", err); }
+        if let Some(mut log) = log_file { writeln!(log, "WARNING: {}, your requests may be blocked", err)?; }
+    }
+
     use std::fmt::Write;    // Write into string
     write!(&mut user_agent, " turtletools:{}/{} +{}", CRATE_NAME, CRATE_VERSION, CRATE_REPO).expect("write into string should not fail!");
 
-    let silent_mode = arg_matches.get_flag("silent"); // icons::build_icon_export overrides this to `true` if "checksum to stdout" is present
     let skip_if_fresh = arg_matches.get_flag("skip_if_fresh");
     let no_purge = arg_matches.get_flag("no_purge");
 
@@ -362,6 +475,17 @@ fn do_main() -> Result<(), IconError> {
         use_old_overlays: arg_matches.get_flag("old_overlays"),
         module_overlays: arg_matches.get_flag("module_overlays"),
         clone_overlays: arg_matches.get_flag("clone_overlays"),
+        icon_size: *arg_matches.get_one::<u32>("icon_size").expect("icon_size has a default value"),
+        format: match arg_matches.get_one::<String>("format").map(String::as_str) {
+            Some("webp") => IconFormat::WebP,
+            _ => IconFormat::Png
+        },
+        overlay_blend: match arg_matches.get_one::<String>("overlay_blend").map(String::as_str) {
+            Some("screen") => BlendMode::Screen,
+            Some("normal") => BlendMode::Normal,
+            _ => BlendMode::Additive
+        },
+        jpeg_quality: *arg_matches.get_one::<u8>("jpeg_quality").expect("jpeg_quality has a default value"),
     };
 
     let start = Instant::now();
@@ -369,8 +493,10 @@ fn do_main() -> Result<(), IconError> {
     if let Some(mut log) = log_file { writeln!(log, "Initializing cache (UA:`{}`)", user_agent)?; }
     let cache = CacheDownloader::initialize(
         arg_matches.get_one::<PathBuf>("cache_folder").expect("cache_folder is a required argument"),
-        false,
-        &*user_agent
+        GameServer::Tranquility,
+        ClientPlatform::Windows,
+        &*user_agent,
+        DownloadOptions::default()
     )?;
     let cache_init_duration = start.elapsed();
 
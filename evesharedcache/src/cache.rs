@@ -4,9 +4,12 @@ use std::collections::hash_map::Keys;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 use std::fs::File;
-use std::io::{BufReader, Write};
+use std::io::{BufReader, Read, Seek, SeekFrom, Write};
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::{Duration, Instant};
 use md5::{Digest, Md5};
 use serde::Deserialize;
@@ -17,6 +20,8 @@ pub enum CacheError {
     DownloadIntoGameInstall,
     /// [`CacheReader`] may only be used on the sharedcache directory of a game install
     NotGameInstall,
+    /// [`CacheReader::discover`] found no game install, lists the directories tried
+    GameInstallNotFound(Vec<PathBuf>),
     /// Attempt to use CacheDownloader with a "protected" game server
     GameServerProtected,
     /// Cache index file could not be parsed, usually indicates out-of-date library
@@ -31,6 +36,8 @@ pub enum CacheError {
     ///
     /// If using [`CacheReader`], ensure the game install is up-to-date and set to "download full game client"
     ResourceNotFound(String),
+    /// [`HybridCache`] was created with a game install and CDN cache of different client versions
+    ClientVersionMismatch { local: String, remote: String },
 }
 
 impl Display for CacheError {
@@ -38,12 +45,20 @@ impl Display for CacheError {
         match self {
             CacheError::DownloadIntoGameInstall => write!(f, "CacheDownloader cannot be used on a game install; Use CacheReader instead"),
             CacheError::NotGameInstall => write!(f, "CacheReader must be used on the game install `SharedCache` folder"),
+            CacheError::GameInstallNotFound(tried) => {
+                write!(f, "no game install found, tried:")?;
+                for path in tried {
+                    write!(f, " {:?}", path)?;
+                }
+                Ok(())
+            }
             CacheError::ResourceNotFound(resource) => write!(f, "resource not found: `{}`", resource),
             CacheError::MalformedIndexFile => write!(f, "malformed index file"),
             CacheError::Reqwest(err) => write!(f, "HTTP error: {}", err),
             CacheError::IO(err) => write!(f, "IO error: {}", err),
             CacheError::JSON(err) => write!(f, "JSON parsing error: {}", err),
-            CacheError::GameServerProtected => write!(f, "Game Server Protected")
+            CacheError::GameServerProtected => write!(f, "Game Server Protected"),
+            CacheError::ClientVersionMismatch { local, remote } => write!(f, "client version mismatch: game install is version {}, CDN is version {}", local, remote)
         }
     }
 }
@@ -53,12 +68,14 @@ impl Error for CacheError {
         match self {
             CacheError::DownloadIntoGameInstall => None,
             CacheError::NotGameInstall => None,
+            CacheError::GameInstallNotFound(_) => None,
             CacheError::MalformedIndexFile => None,
             CacheError::ResourceNotFound(_) => None,
             CacheError::Reqwest(err) => Some(err),
             CacheError::IO(err) => Some(err),
             CacheError::JSON(err) => Some(err),
-            CacheError::GameServerProtected => None
+            CacheError::GameServerProtected => None,
+            CacheError::ClientVersionMismatch { .. } => None
         }
     }
 }
@@ -91,15 +108,40 @@ struct IndexEntry {
     compressed: u64
 }
 
+/// Index metadata of a resource, see [`SharedCache::resource_info`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ResourceInfo {
+    /// Path of the resource file, relative to the cache directory
+    pub path: String,
+    /// md5 hash of the resource, as hexadecimal string
+    pub md5: String,
+    /// Uncompressed size of the resource in bytes
+    pub size: u64,
+    /// Size of the resource on the CDN in bytes
+    pub compressed: u64,
+}
+
+impl From<&IndexEntry> for ResourceInfo {
+    fn from(entry: &IndexEntry) -> Self {
+        ResourceInfo { path: entry.path.clone(), md5: entry.md5.clone(), size: entry.size, compressed: entry.compressed }
+    }
+}
+
 impl IndexEntry {
+    fn md5_value(&self) -> Result<u128, CacheError> {
+        u128::from_str_radix(&self.md5, 16).map_err(|_| CacheError::MalformedIndexFile)
+    }
+
     fn load_index(index_text: &str, index: &mut HashMap<String, IndexEntry>) -> Result<(), CacheError> {
+        // Index files may have a UTF-8 byte order mark and CRLF line endings
+        let index_text = index_text.strip_prefix('\u{FEFF}').unwrap_or(index_text);
         for line in index_text.lines() {
             if line.trim().is_empty() {
                 continue;
             }
 
             // skip 6th field, which are the filesystem permissions
-            let mut split = line.splitn(6, ',');
+            let mut split = line.splitn(6, ',').map(str::trim);
             if let (Some(resource), Some(path), Some(md5), Some(size), Some(compressed)) = (split.next(), split.next(), split.next(), split.next(), split.next()) {
                 index.insert(
                     resource.replace('\\', "/").to_ascii_lowercase(),
@@ -119,6 +161,59 @@ impl IndexEntry {
     }
 }
 
+/// Namespace of a resource, the prefix of the resource name
+///
+/// * `app:/` resources are application files, listed in the client's application index, and hosted on the server's binaries CDN
+/// * `res:/` resources are game data files, listed in `app:/resfileindex.txt`, and hosted on the server's resources CDN
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ResourceNamespace {
+    App,
+    Res
+}
+
+impl ResourceNamespace {
+    /// Returns the namespace of a resource, or None if the resource has no known namespace prefix
+    pub fn of(resource: &str) -> Option<ResourceNamespace> {
+        let (prefix, _) = resource.split_once(":/")?;
+        if prefix.eq_ignore_ascii_case("app") {
+            Some(ResourceNamespace::App)
+        } else if prefix.eq_ignore_ascii_case("res") {
+            Some(ResourceNamespace::Res)
+        } else {
+            None
+        }
+    }
+
+    /// Resource name prefix for this namespace
+    pub fn prefix(self) -> &'static str {
+        match self {
+            ResourceNamespace::App => "app:/",
+            ResourceNamespace::Res => "res:/",
+        }
+    }
+}
+
+impl Display for ResourceNamespace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.prefix())
+    }
+}
+
+/// Parses a client version string as a numeric build number
+fn build_number(client_version: &str) -> Option<u64> {
+    client_version.trim().parse().ok()
+}
+
+/// Returns true if client version `a` is a newer build than client version `b`
+///
+/// Versions are compared by their numeric build number; Returns false if either version is not a build number
+pub fn is_newer(a: &str, b: &str) -> bool {
+    match (build_number(a), build_number(b)) {
+        (Some(a), Some(b)) => a > b,
+        _ => false
+    }
+}
+
 /// Trait to abstract over different SharedCache data sources
 /// * [`CacheReader`] provides READ-ONLY access to a locally-installed copy of the game
 /// * [`CacheDownloader`]  provides access to the game file CDN, creating a local on-disk cache
@@ -127,8 +222,36 @@ pub trait SharedCache {
     /// for [`CacheReader`] this is the currently-installed version
     /// for [`CacheDownloader`] this is the version currently on the CDN
     fn client_version(&self) -> &str;
+    /// Numeric build number of [`SharedCache::client_version`], or `None` if the client version is not a build number
+    fn build_number(&self) -> Option<u64> {
+        build_number(self.client_version())
+    }
     /// Iterator view on all resources known in this SharedCache
+    ///
+    /// Resources are in arbitrary order; for [`CacheDownloader`] a resource listed in both the app and res index is yielded twice. See [`SharedCache::resources_sorted`] for a stable listing
     fn iter_resources(&self) -> impl Iterator<Item=&str>;
+    /// All resources known in this SharedCache, sorted and without duplicates
+    fn resources_sorted(&self) -> Vec<&str> {
+        let mut resources = self.iter_resources().collect::<Vec<_>>();
+        resources.sort_unstable();
+        resources.dedup();
+        resources
+    }
+    /// Namespaces of resources in this SharedCache
+    fn namespaces(&self) -> HashSet<ResourceNamespace> {
+        self.iter_resources().filter_map(ResourceNamespace::of).collect()
+    }
+    /// Iterator view on all resources in the specified namespace
+    fn iter_namespace(&self, namespace: ResourceNamespace) -> impl Iterator<Item=&str> {
+        self.iter_resources().filter(move |resource| ResourceNamespace::of(resource) == Some(namespace))
+    }
+    /// Iterator view on all resources starting with the specified prefix
+    ///
+    /// The prefix is normalized the same way as resource lookups; Matching is case-insensitive and `\\` is treated as `/`
+    fn iter_resources_with_prefix<'a>(&'a self, prefix: &str) -> impl Iterator<Item=&'a str> + use<'a, Self> {
+        let prefix = normalize_resource(prefix);
+        self.iter_resources().filter(move |resource| resource.starts_with(&prefix))
+    }
     /// Returns true if the resource is available in this SharedCache
     /// for [`CacheReader`] this returns true if a resource is listed in the index file but not yet downloaded by the game launcher
     fn has_resource(&self, resource: &str) -> bool;
@@ -148,6 +271,98 @@ pub trait SharedCache {
     /// Retrieves the md5 hash of a resource
     /// Downloading the file is not necessary
     fn hash_of(&self, resource: &str) -> Result<&str, CacheError>;
+    /// Retrieves the uncompressed size of a resource in bytes
+    /// Downloading the file is not necessary
+    fn resource_size(&self, resource: &str) -> Result<u64, CacheError>;
+    /// Retrieves the index metadata of a resource, or `None` if the resource is not known in this SharedCache
+    /// Downloading the file is not necessary
+    fn resource_info(&self, resource: &str) -> Option<ResourceInfo>;
+    /// Opens a resource for streaming reads, avoids loading the entire resource into memory
+    ///
+    /// for [`CacheDownloader`] the resource is downloaded if not yet cached
+    fn fetch_reader(&self, resource: &str) -> Result<Box<dyn Read>, CacheError> {
+        Ok(Box::new(BufReader::new(File::open(self.path_of(resource)?)?)))
+    }
+    /// Checks the local copy of a resource against the md5 hash in the index
+    ///
+    /// Returns false if there is no local copy of the resource, for [`CacheDownloader`] this does not download the resource
+    fn verify(&self, resource: &str) -> Result<bool, CacheError>;
+    /// Retrieves a byte range of a resource, ranges past the end of the resource are truncated
+    ///
+    /// for [`CacheDownloader`] only the requested range is downloaded if the resource is not yet cached
+    fn fetch_range(&self, resource: &str, range: Range<u64>) -> Result<Vec<u8>, CacheError> {
+        let bytes = self.fetch(resource)?;
+        let end = usize::try_from(range.end).unwrap_or(usize::MAX).min(bytes.len());
+        let start = usize::try_from(range.start).unwrap_or(usize::MAX).min(end);
+        Ok(bytes[start..end].to_vec())
+    }
+}
+
+/// Reads a byte range of a local file, ranges past the end of the file are truncated
+fn read_file_range(path: &Path, range: Range<u64>) -> Result<Vec<u8>, io::Error> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(range.start))?;
+    let mut buffer = Vec::new();
+    file.take(range.end.saturating_sub(range.start)).read_to_end(&mut buffer)?;
+    Ok(buffer)
+}
+
+/// Counter to give each temporary file within this process a unique name, see [`temp_path_of`]
+static TEMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Unique temporary file that [`write_atomic`] writes to before renaming into place, `{file}.{process id}-{counter}.tmp`
+///
+/// The temporary file is in the same directory as the file, as renames are only atomic within the same filesystem.
+/// Concurrent writers of the same file each write their own temporary file, so a writer never renames a file another writer is still writing.
+/// Temporary files left behind by an interrupted process are not listed in any index, and are removed by [`CacheDownloader::purge`]
+fn temp_path_of(path: &Path) -> PathBuf {
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(format!(".{}-{}.tmp", std::process::id(), TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)));
+    path.with_file_name(temp_name)
+}
+
+/// Writes a file by writing to a temporary file and renaming it into place, so that the file is never observed partially written
+///
+/// Parent directories are created if needed
+fn write_atomic(path: &Path, data: &[u8]) -> Result<(), CacheError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let temp_path = temp_path_of(path);
+    fs::write(&temp_path, data)
+        .and_then(|_| fs::rename(&temp_path, path))
+        .inspect_err(|_| { let _ = fs::remove_file(&temp_path); })?;
+    Ok(())
+}
+
+/// Computes the md5 hash of a file
+fn file_md5(path: &Path) -> io::Result<u128> {
+    let mut md5 = Md5::new();
+
+    // Rustcrypto is being dumb and moved ::Write implementations to a whole separate crate. This isn't NPM >.>
+    struct MD5Writer<'a>(&'a mut Md5);
+    impl Write for MD5Writer<'_> {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            Digest::update(self.0, buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    std::io::copy(&mut BufReader::new(File::open(path)?), &mut MD5Writer(&mut md5))?;
+    Ok(u128::from_be_bytes(md5.finalize().into()))
+}
+
+/// Checks a local file against the md5 hash in its index entry, returns false if the file does not exist
+fn verify_file(path: &Path, entry: &IndexEntry) -> Result<bool, CacheError> {
+    if fs::exists(path)? {
+        Ok(file_md5(path)? == entry.md5_value()?)
+    } else {
+        Ok(false)
+    }
 }
 
 /// Provides READ-ONLY access to a locally-installed copy of the game
@@ -162,7 +377,7 @@ impl CacheReader {
     ///
     /// # Arguments
     ///
-    /// * `directory`: Directory to load, must be the `SharedCache` folder of a game install
+    /// * `directory`: Directory to load, must be the `SharedCache` folder of a game install; See [`CacheReader::load_from_install_root`] to load from the install folder
     ///
     /// returns: Result<CacheReader, CacheError>
     pub fn load<T: Into<PathBuf>>(directory: T) -> Result<CacheReader, CacheError> {
@@ -175,7 +390,8 @@ impl CacheReader {
             .next()
             .ok_or(CacheError::NotGameInstall)?
             .strip_prefix("build = ")
-            .unwrap();
+            .unwrap()
+            .trim();
 
         let res_dir = cache_dir.join("ResFiles");
         if !fs::exists(&res_dir)? {
@@ -193,6 +409,51 @@ impl CacheReader {
 
         Ok(reader)
     }
+
+    /// Loads the cache of a game install, from either the `SharedCache` folder or the install folder containing it
+    pub fn load_from_install_root<T: Into<PathBuf>>(directory: T) -> Result<CacheReader, CacheError> {
+        let directory = directory.into();
+        if fs::exists(directory.join("tq/start.ini"))? {
+            CacheReader::load(directory)
+        } else if fs::exists(directory.join("SharedCache"))? {
+            CacheReader::load(directory.join("SharedCache"))
+        } else {
+            Err(CacheError::NotGameInstall)
+        }
+    }
+
+    /// Loads the cache of a game install from the first default install location that contains one
+    ///
+    /// Tries the launcher and Steam default locations for the current OS, see [`CacheReader::install_locations`].
+    /// Fails with [`CacheError::GameInstallNotFound`] listing the locations tried if none can be loaded.
+    pub fn discover() -> Result<CacheReader, CacheError> {
+        let locations = CacheReader::install_locations();
+        for location in &locations {
+            if let Ok(reader) = CacheReader::load_from_install_root(location) {
+                return Ok(reader);
+            }
+        }
+        Err(CacheError::GameInstallNotFound(locations))
+    }
+
+    /// Default game install locations for the current OS, tried by [`CacheReader::discover`]
+    pub fn install_locations() -> Vec<PathBuf> {
+        let env_path = |var: &str| std::env::var_os(var).map(PathBuf::from);
+        let mut locations = Vec::new();
+        if cfg!(target_os = "windows") {
+            locations.push(PathBuf::from("C:\\EVE"));
+            locations.extend(env_path("LOCALAPPDATA").map(|path| path.join("CCP\\EVE")));
+            locations.extend(env_path("ProgramFiles(x86)").map(|path| path.join("Steam\\steamapps\\common\\Eve Online")));
+        } else if cfg!(target_os = "macos") {
+            locations.extend(env_path("HOME").map(|path| path.join("Library/Application Support/EVE Online")));
+            locations.extend(env_path("HOME").map(|path| path.join("Library/Application Support/Steam/steamapps/common/Eve Online")));
+        } else {
+            // Steam (Proton) installs
+            locations.extend(env_path("HOME").map(|path| path.join(".local/share/Steam/steamapps/common/Eve Online")));
+            locations.extend(env_path("HOME").map(|path| path.join(".steam/steam/steamapps/common/Eve Online")));
+        }
+        locations
+    }
 }
 
 impl SharedCache for CacheReader {
@@ -259,6 +520,180 @@ impl SharedCache for CacheReader {
             .ok_or_else(|| CacheError::ResourceNotFound(resource))?;
         Ok(md5)
     }
+
+    fn resource_size(&self, resource: &str) -> Result<u64, CacheError> {
+        let resource = resource.to_ascii_lowercase().replace('\\', "/");
+        let IndexEntry { size, .. } = self.index.get(&resource)
+            .ok_or_else(|| CacheError::ResourceNotFound(resource))?;
+        Ok(*size)
+    }
+
+    fn resource_info(&self, resource: &str) -> Option<ResourceInfo> {
+        let resource = resource.to_ascii_lowercase().replace('\\', "/");
+        self.index.get(&resource).map(ResourceInfo::from)
+    }
+
+    fn verify(&self, resource: &str) -> Result<bool, CacheError> {
+        let resource = resource.to_ascii_lowercase().replace('\\', "/");
+        let entry = self.index.get(&resource)
+            .ok_or_else(|| CacheError::ResourceNotFound(resource))?;
+        verify_file(&self.res_dir.join(&entry.path), entry)
+    }
+
+    fn fetch_range(&self, resource: &str, range: Range<u64>) -> Result<Vec<u8>, CacheError> {
+        Ok(read_file_range(&self.path_of(resource)?, range)?)
+    }
+}
+
+/// Game client platform, determines which build of the game client is downloaded
+///
+/// Each platform has its own application index, which lists that platform's `app:/resfileindex.txt`; The `res:/` resources of a downloader are therefore always those of its platform.
+/// Both platforms share the same resources CDN, as resources are addressed by their hash.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ClientPlatform {
+    Windows,
+    MacOS
+}
+
+impl ClientPlatform {
+    /// Filename of the application index for the specified client version
+    pub fn app_index_name(self, client_version: &str) -> String {
+        match self {
+            ClientPlatform::Windows => format!("eveonline_{}.txt", client_version),
+            ClientPlatform::MacOS => format!("eveonlinemacOS_{}.txt", client_version),
+        }
+    }
+}
+
+impl Display for ClientPlatform {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientPlatform::Windows => write!(f, "Windows"),
+            ClientPlatform::MacOS => write!(f, "macOS"),
+        }
+    }
+}
+
+/// HTTP options for [`CacheDownloader`]
+#[derive(Debug, Copy, Clone)]
+pub struct DownloadOptions {
+    /// Timeout for each individual HTTP request
+    pub request_timeout: Duration,
+    /// Amount of times a download is retried after a timeout or server error (5xx status)
+    ///
+    /// Retries use exponential backoff, starting at [`DownloadOptions::retry_delay`]
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for each subsequent retry
+    pub retry_delay: Duration,
+}
+
+impl Default for DownloadOptions {
+    fn default() -> Self {
+        DownloadOptions {
+            request_timeout: Duration::from_secs(60),
+            max_retries: 3,
+            retry_delay: Duration::from_millis(500),
+        }
+    }
+}
+
+impl DownloadOptions {
+    /// Delay before the specified retry attempt, starting at 0
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.retry_delay.saturating_mul(2u32.saturating_pow(attempt))
+    }
+}
+
+/// Returns true if a failed request may succeed when retried
+fn is_transient(err: &reqwest::Error) -> bool {
+    err.is_timeout() || err.status().is_some_and(|status| status.is_server_error())
+}
+
+/// Game server to download the game client of
+///
+/// Serenity hosts differ and may need separate index files; Its endpoints are not built in, and must be provided by the caller.
+/// Cache directories should not be shared between servers.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum GameServer {
+    Tranquility,
+    Singularity,
+    /// Serenity (the Chinese server, operated by NetEase)
+    Serenity {
+        /// URL of the client version JSON
+        client_version_url: &'static str,
+        /// Base URL of the binaries CDN, serving `app:/` files and index files
+        binaries_url: &'static str,
+        /// Base URL of the resources CDN, serving `res:/` files
+        resources_url: &'static str,
+    }
+}
+
+impl GameServer {
+    /// URL of the client version JSON for this server
+    pub fn client_version_url(self) -> &'static str {
+        match self {
+            GameServer::Tranquility => "https://binaries.eveonline.com/eveclient_TQ.json",
+            GameServer::Singularity => "https://binaries.eveonline.com/eveclient_SISI.json",
+            GameServer::Serenity { client_version_url, .. } => client_version_url,
+        }
+    }
+
+    /// CDN url of a file
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace`: Namespace of the resource, determines which CDN host serves the file
+    /// * `path`: Path of the file, as listed in the index file
+    pub fn cdn_url(self, namespace: ResourceNamespace, path: &str) -> String {
+        match (self, namespace) {
+            (GameServer::Tranquility | GameServer::Singularity, ResourceNamespace::App) => format!("https://binaries.eveonline.com/{}", path),
+            (GameServer::Tranquility | GameServer::Singularity, ResourceNamespace::Res) => format!("https://resources.eveonline.com/{}", path),
+            (GameServer::Serenity { binaries_url, .. }, ResourceNamespace::App) => format!("{}/{}", binaries_url.trim_end_matches('/'), path),
+            (GameServer::Serenity { resources_url, .. }, ResourceNamespace::Res) => format!("{}/{}", resources_url.trim_end_matches('/'), path),
+        }
+    }
+}
+
+impl Display for GameServer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameServer::Tranquility => write!(f, "Tranquility"),
+            GameServer::Singularity => write!(f, "Singularity"),
+            GameServer::Serenity { .. } => write!(f, "Serenity"),
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Deserialize)]
+struct ClientVersion {
+    buildNumber: String,
+    protected: Option<bool>
+}
+
+/// User Agent for CDN requests, appends this library's name and repository to the user's User Agent
+fn http_user_agent(user_agent: &str) -> String {
+    format!("{} turtletools:{}/{} +{}", user_agent, crate::CRATE_NAME, crate::CRATE_VERSION, crate::CRATE_REPO)
+}
+
+/// Normalizes a resource name to the form used as index key
+fn normalize_resource(resource: &str) -> String {
+    resource.to_ascii_lowercase().replace('\\', "/")
+}
+
+/// Looks up a resource in the index for its namespace
+fn locate<'a>(app_index: &'a HashMap<String, IndexEntry>, res_index: &'a HashMap<String, IndexEntry>, resource: &str) -> Result<(&'a IndexEntry, ResourceNamespace), CacheError> {
+    let resource = normalize_resource(resource);
+    let namespace = ResourceNamespace::of(&resource);
+    let entry = match namespace {
+        Some(ResourceNamespace::App) => app_index.get(&resource),
+        Some(ResourceNamespace::Res) => res_index.get(&resource),
+        None => None
+    };
+    match (entry, namespace) {
+        (Some(entry), Some(namespace)) => Ok((entry, namespace)),
+        _ => Err(CacheError::ResourceNotFound(resource))
+    }
 }
 
 /// Provides access to the game file CDN, creating a local on-disk cache
@@ -266,8 +701,13 @@ pub struct CacheDownloader {
     cache_dir: PathBuf,
     http_client: reqwest::blocking::Client,
     client_version: String,
+    game_server: GameServer,
+    client_platform: ClientPlatform,
+    options: DownloadOptions,
     app_index: HashMap<String, IndexEntry>,
-    res_index: HashMap<String, IndexEntry>
+    res_index: HashMap<String, IndexEntry>,
+    /// Per-file locks for downloads in progress, so concurrent requests for the same resource only download it once
+    downloads: Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>
 }
 
 impl CacheDownloader {
@@ -276,27 +716,22 @@ impl CacheDownloader {
     /// # Arguments
     ///
     /// * `directory`: Directory for local caching of downloaded files, created if not existing
-    /// * `use_macos_build`: If true, download macOS build of the game, if false, download Windows files
+    /// * `game_server`: Server to download the game client of
+    /// * `client_platform`: Build of the game to download, Windows or macOS
     /// * `user_agent`: User Agent to use with HTTP requests
+    /// * `options`: HTTP timeout and retry options, see [`DownloadOptions::default`]
     ///
     /// returns: Result<CacheDownloader, CacheError>
-    pub fn initialize<T: Into<PathBuf>>(directory: T, use_macos_build: bool, user_agent: &str) -> Result<CacheDownloader, CacheError> {
+    pub fn initialize<T: Into<PathBuf>>(directory: T, game_server: GameServer, client_platform: ClientPlatform, user_agent: &str, options: DownloadOptions) -> Result<CacheDownloader, CacheError> {
         let cache_dir = directory.into();
         fs::create_dir_all(&cache_dir)?;
-        let http_client = reqwest::blocking::Client::builder().user_agent(format!("{} turtletools:{}/{} +{}", user_agent, crate::CRATE_NAME, crate::CRATE_VERSION, crate::CRATE_REPO)).build()?;
+        let http_client = reqwest::blocking::Client::builder().user_agent(http_user_agent(user_agent)).timeout(options.request_timeout).build()?;
 
         if fs::exists(cache_dir.join("updater.exe"))? || fs::exists(cache_dir.join("tq"))? {
             return Err(CacheError::DownloadIntoGameInstall);
         }
 
-        #[allow(non_snake_case)]
-        #[derive(Deserialize)]
-        pub struct ClientVersion {
-            buildNumber: String,
-            protected: Option<bool>
-        }
-
-        let client_version = http_client.get("https://binaries.eveonline.com/eveclient_TQ.json")
+        let client_version = http_client.get(game_server.client_version_url())
             .send()?
             .error_for_status()?
             .json::<ClientVersion>()?;
@@ -308,18 +743,19 @@ impl CacheDownloader {
         let mut downloader = CacheDownloader {
             cache_dir,
             http_client,
-            client_version: client_version.buildNumber,
+            client_version: client_version.buildNumber.trim().to_string(),
+            game_server,
+            client_platform,
+            options,
             app_index: HashMap::new(),
-            res_index: HashMap::new()
+            res_index: HashMap::new(),
+            downloads: Mutex::new(HashMap::new())
         };
 
-        let file = downloader.cache_dir.join(format!("eveonline_{}.txt", downloader.client_version));
-
-        let url = if use_macos_build {
-            format!("https://binaries.eveonline.com/eveonlinemacOS_{}.txt", downloader.client_version)
-        } else {
-            format!("https://binaries.eveonline.com/eveonline_{}.txt", downloader.client_version)
-        };
+        // Index files are named per-platform, so a cache folder previously used for the other platform does not re-use the wrong index
+        let index_name = client_platform.app_index_name(&downloader.client_version);
+        let file = downloader.cache_dir.join(&index_name);
+        let url = game_server.cdn_url(ResourceNamespace::App, &index_name);
 
         IndexEntry::load_index(&*String::from_utf8(downloader.fetch_file(file, url)?).map_err(io::Error::other)?, &mut downloader.app_index)?;
         IndexEntry::load_index(&*String::from_utf8(downloader.fetch("app:/resfileindex.txt")?).map_err(io::Error::other)?, &mut downloader.res_index)?;
@@ -327,27 +763,75 @@ impl CacheDownloader {
         Ok(downloader)
     }
 
-    fn ensure_cached<P: AsRef<Path>, U: reqwest::IntoUrl>(&self, file: P, url: U) -> Result<Option<Vec<u8>>, CacheError> {
-        let file = file.as_ref();
-        if fs::exists(&file)? {
-            Ok(None)
-        } else {
-            let response = self.http_client.get(url)
-                .send()?
-                .error_for_status()?;
+    /// Downloads a file, retrying transient failures as configured in [`DownloadOptions`]
+    fn download(&self, url: &str) -> Result<Vec<u8>, CacheError> {
+        self.download_range(url, None).map(|(_, buffer)| buffer)
+    }
 
-            let buffer = response.bytes()?;
+    /// Downloads a file, or only the specified byte range of it, retrying transient failures as configured in [`DownloadOptions`]
+    ///
+    /// Returns whether the server responded with only the requested range, servers that do not support range requests respond with the entire file
+    fn download_range(&self, url: &str, range: Option<&Range<u64>>) -> Result<(bool, Vec<u8>), CacheError> {
+        let mut attempt = 0;
+        loop {
+            let mut request = self.http_client.get(url);
+            if let Some(range) = range {
+                request = request.header(reqwest::header::RANGE, format!("bytes={}-{}", range.start, range.end - 1));
+            }
+            let result = request.send()
+                .and_then(reqwest::blocking::Response::error_for_status)
+                .and_then(|response| {
+                    let partial = response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+                    response.bytes().map(|buffer| (partial, buffer))
+                });
+
+            match result {
+                Ok((partial, buffer)) => return Ok((partial, buffer.to_vec())),
+                Err(err) if attempt < self.options.max_retries && is_transient(&err) => {
+                    std::thread::sleep(self.options.backoff(attempt));
+                    attempt += 1;
+                }
+                Err(err) => return Err(err.into())
+            }
+        }
+    }
 
-            if let Some(parent) = file.parent() {
-                fs::create_dir_all(parent)?;
+    /// Downloads a file if it is not yet cached, returning the downloaded bytes or None if the file was already cached
+    ///
+    /// Concurrent calls for the same file wait for a single download, rather than each downloading the file
+    fn ensure_cached<P: AsRef<Path>>(&self, file: P, url: String) -> Result<Option<Vec<u8>>, CacheError> {
+        let file = file.as_ref();
+        if fs::exists(file)? {
+            return Ok(None);
+        }
+
+        self.with_download_lock(file, || {
+            // Another thread may have completed the download while we were waiting
+            if fs::exists(file)? {
+                Ok(None)
+            } else {
+                self.download(&url)
+                    .and_then(|buffer| write_atomic(file, &buffer).map(|_| Some(buffer)))
             }
-            fs::write(file, &buffer)?;
+        })
+    }
 
-            Ok(Some(buffer.to_vec()))
+    /// Runs `f` while holding the download lock for `file`, so that only one thread at a time downloads or writes a file
+    fn with_download_lock<T>(&self, file: &Path, f: impl FnOnce() -> Result<T, CacheError>) -> Result<T, CacheError> {
+        let lock = Arc::clone(self.downloads.lock().unwrap().entry(file.to_path_buf()).or_default());
+        let result = {
+            let _guard = lock.lock().unwrap();
+            f()
+        };
+
+        let mut downloads = self.downloads.lock().unwrap();
+        if Arc::strong_count(&lock) == 2 {  // No other threads waiting on this download
+            downloads.remove(file);
         }
+        result
     }
 
-    fn fetch_file<P: AsRef<Path>, U: reqwest::IntoUrl>(&self, file: P, url: U) -> Result<Vec<u8>, CacheError> {
+    fn fetch_file<P: AsRef<Path>>(&self, file: P, url: String) -> Result<Vec<u8>, CacheError> {
         self.ensure_cached(file.as_ref(), url)
             .and_then(|buffer_opt| {
                 if let Some(buffer) = buffer_opt {
@@ -358,6 +842,16 @@ impl CacheDownloader {
             })
     }
 
+    /// Game server this downloader was initialized for
+    pub fn game_server(&self) -> GameServer {
+        self.game_server
+    }
+
+    /// Platform of the game client this downloader was initialized for
+    pub fn client_platform(&self) -> ClientPlatform {
+        self.client_platform
+    }
+
     /// Pre-download files into the local directory, performs downloads in a single thread
     ///
     /// # Arguments
@@ -369,7 +863,7 @@ impl CacheDownloader {
     pub fn preload(&self, max_items: u64, sleep: Option<Duration>) -> Result<u64, CacheError> {
         let mut downloaded = 0;
         for  IndexEntry { path, .. } in self.res_index.values() {
-            if self.ensure_cached(self.cache_dir.join(path), format!("https://resources.eveonline.com/{}", path))?.is_some() { downloaded += 1 };
+            if self.ensure_cached(self.cache_dir.join(path), self.game_server.cdn_url(ResourceNamespace::Res, path))?.is_some() { downloaded += 1 };
             if downloaded >= max_items {
                 break;
             }
@@ -381,6 +875,40 @@ impl CacheDownloader {
         Ok(downloaded)
     }
 
+    /// Pre-download files into the local directory with progress reporting, performs downloads in a single thread
+    ///
+    /// Unlike [`CacheDownloader::preload`], this includes `app:/` resources
+    ///
+    /// # Arguments
+    ///
+    /// * `max_items`: Maximum amount of items to download
+    /// * `sleep`: Time spent waiting between downloads, set to None for no wait
+    /// * `on_file`: Called after each file with the resource name, the amount of files processed so far, and the total amount of files
+    ///
+    /// returns: Amount of files downloaded
+    pub fn preload_with_progress<F: FnMut(&str, u64, u64)>(&self, max_items: u64, sleep: Option<Duration>, mut on_file: F) -> Result<u64, CacheError> {
+        let total = (self.res_index.len() + self.app_index.len()) as u64;
+        let entries = self.app_index.iter().map(|(resource, entry)| (resource, entry, ResourceNamespace::App))
+            .chain(self.res_index.iter().map(|(resource, entry)| (resource, entry, ResourceNamespace::Res)));
+
+        let mut downloaded = 0;
+        for (completed, (resource, IndexEntry { path, .. }, namespace)) in (1..).zip(entries) {
+            let is_download = self.ensure_cached(self.cache_dir.join(path), self.game_server.cdn_url(namespace, path))?.is_some();
+            on_file(resource, completed, total);
+            if is_download {
+                downloaded += 1;
+                if downloaded >= max_items {
+                    break;
+                }
+
+                if let Some(sleep_duration) = sleep {
+                    std::thread::sleep(sleep_duration);
+                }
+            }
+        }
+        Ok(downloaded)
+    }
+
     pub fn validate(&self) -> Result<(usize, usize), CacheError> {
         let mut valid = 0usize;
         let mut invalid = 0usize;
@@ -402,24 +930,7 @@ impl CacheDownloader {
                             .replace('\\', "/");
 
                         if let Some(index_entry) = path_map.get(&*resource_path) {
-                            let mut md5 = Md5::new();
-
-                            // Rustcrypto is being dumb and moved ::Write implementations to a whole separate crate. This isn't NPM >.>
-                            struct MD5Writer<'a>(&'a mut Md5);
-                            impl Write for MD5Writer<'_> {
-                                fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-                                    Digest::update(self.0, buf);
-                                    Ok(buf.len())
-                                }
-
-                                fn flush(&mut self) -> io::Result<()> {
-                                    Ok(())
-                                }
-                            }
-
-                            std::io::copy(&mut BufReader::new(File::open(&path)?), &mut MD5Writer(&mut md5))?;
-
-                            if u128::from_be_bytes(md5.finalize().into()) == u128::from_str_radix(&*index_entry.md5, 16).map_err(|_| CacheError::MalformedIndexFile)? {
+                            if file_md5(&path)? == index_entry.md5_value()? {
                                 valid += 1;
                             } else {
                                 invalid += 1;
@@ -435,19 +946,60 @@ impl CacheDownloader {
         Ok((valid, invalid))
     }
 
-    /// Remove local directory files not in the current sharedcache index
+    /// Re-downloads cached files that do not match the md5 hash in the index, such as files left incomplete by an interrupted download
     ///
-    /// Used to clean up files from older versions of the game
+    /// Files that are not cached are not downloaded
     ///
-    /// WARNING: Deletes files in the directory this instance of [`CacheDownloader`] has been initialized to, including any not created by this tool
-    pub fn purge(&self, keep_files: &[&str]) -> Result<(), io::Error> {
+    /// returns: Amount of files repaired
+    pub fn repair(&self) -> Result<usize, CacheError> {
+        let mut repaired = 0;
+        let entries = self.app_index.values().map(|entry| (entry, ResourceNamespace::App))
+            .chain(self.res_index.values().map(|entry| (entry, ResourceNamespace::Res)));
+
+        for (entry, namespace) in entries {
+            let file = self.cache_dir.join(&entry.path);
+            if fs::exists(&file)? && !verify_file(&file, entry)? {
+                fs::remove_file(&file)?;
+                self.ensure_cached(&file, self.game_server.cdn_url(namespace, &entry.path))?;
+                repaired += 1;
+            }
+        }
+        Ok(repaired)
+    }
+
+    /// Total size of all files in the cache directory, in bytes
+    pub fn disk_usage(&self) -> io::Result<u64> {
+        fn dir_size(dir: &Path) -> io::Result<u64> {
+            let mut size = 0;
+            for entry in fs::read_dir(dir)? {
+                let entry = entry?;
+                let metadata = entry.metadata()?;
+                if metadata.is_dir() {
+                    size += dir_size(&entry.path())?;
+                } else {
+                    size += metadata.len();
+                }
+            }
+            Ok(size)
+        }
+
+        dir_size(&self.cache_dir)
+    }
+
+    /// Files that [`CacheDownloader::purge`] would remove, without removing them
+    ///
+    /// # Arguments
+    ///
+    /// * `keep_files`: Names of files in the top level of the cache directory to keep
+    pub fn purge_plan(&self, keep_files: &[&str]) -> Result<Vec<PathBuf>, io::Error> {
         let valid_paths = self.res_index.values()
             .chain(self.app_index.values())
             .map(|entry| &*entry.path)
             .collect::<HashSet<&str>>();
 
-        let client_index = format!("eveonline_{}.txt", self.client_version);
+        let client_index = self.client_platform.app_index_name(&self.client_version);
 
+        let mut plan = Vec::new();
         for parent_entry in fs::read_dir(&self.cache_dir)? {
             let parent_entry = parent_entry?;
             let parent_dir = parent_entry.file_name();  // Split for ownership
@@ -459,16 +1011,29 @@ impl CacheDownloader {
                     let file_path = format!("{}/{}", parent_name, &file_entry.file_name().to_str().unwrap());
 
                     if !valid_paths.contains(&*file_path.to_ascii_lowercase()) {
-                        fs::remove_file(file_entry.path())?;
+                        plan.push(file_entry.path());
                     }
                 }
             } else {
                 if parent_name != client_index && !keep_files.contains(&parent_name) {
-                    fs::remove_file(parent_entry.path())?;
+                    plan.push(parent_entry.path());
                 }
             }
         }
 
+        Ok(plan)
+    }
+
+    /// Remove local directory files not in the current sharedcache index
+    ///
+    /// Used to clean up files from older versions of the game, or files from a different [`ClientPlatform`]
+    ///
+    /// WARNING: Deletes files in the directory this instance of [`CacheDownloader`] has been initialized to, including any not created by this tool.
+    /// Use [`CacheDownloader::purge_plan`] to list the files that would be deleted.
+    pub fn purge(&self, keep_files: &[&str]) -> Result<(), io::Error> {
+        for file in self.purge_plan(keep_files)? {
+            fs::remove_file(file)?;
+        }
         Ok(())
     }
 }
@@ -482,53 +1047,821 @@ impl SharedCache for CacheDownloader {
         Keys::chain(self.app_index.keys(), self.res_index.keys()).map(String::as_str)
     }
 
+    fn iter_resources_with_prefix<'a>(&'a self, prefix: &str) -> impl Iterator<Item=&'a str> + use<'a> {
+        let prefix = normalize_resource(prefix);
+        // Only search the index of the prefix' namespace, if it has one
+        let (app_index, res_index) = match ResourceNamespace::of(&prefix) {
+            Some(ResourceNamespace::App) => (Some(&self.app_index), None),
+            Some(ResourceNamespace::Res) => (None, Some(&self.res_index)),
+            None => (Some(&self.app_index), Some(&self.res_index))
+        };
+        app_index.into_iter().flat_map(HashMap::keys)
+            .chain(res_index.into_iter().flat_map(HashMap::keys))
+            .map(String::as_str)
+            .filter(move |resource| resource.starts_with(&prefix))
+    }
+
     fn has_resource(&self, resource: &str) -> bool {
-        let resource = resource.to_ascii_lowercase().replace('\\', "/");
-        self.app_index.contains_key(&resource) || self.res_index.contains_key(&resource)
+        locate(&self.app_index, &self.res_index, resource).is_ok()
     }
 
     fn fetch(&self, resource: &str) -> Result<Vec<u8>, CacheError> {
-        let resource = resource.to_ascii_lowercase().replace('\\', "/");
-        if let Some(IndexEntry { path, .. }) = self.app_index.get(&resource) {
-            self.fetch_file(self.cache_dir.join(path), format!("https://binaries.eveonline.com/{}", path))
-        } else if let Some(IndexEntry { path, ..}) = self.res_index.get(&resource) {
-            self.fetch_file(self.cache_dir.join(path), format!("https://resources.eveonline.com/{}", path))
+        let (IndexEntry { path, .. }, namespace) = locate(&self.app_index, &self.res_index, resource)?;
+        self.fetch_file(self.cache_dir.join(path), self.game_server.cdn_url(namespace, path))
+    }
+
+    fn try_fetch(&self, resource: &str) -> Result<Option<Vec<u8>>, CacheError> {
+        match locate(&self.app_index, &self.res_index, resource) {
+            Ok((IndexEntry { path, .. }, namespace)) => self.fetch_file(self.cache_dir.join(path), self.game_server.cdn_url(namespace, path)).map(Option::Some),
+            Err(CacheError::ResourceNotFound(_)) => Ok(None),
+            Err(err) => Err(err)
+        }
+    }
+
+    fn path_of(&self, resource: &str) -> Result<PathBuf, CacheError> {
+        let (IndexEntry { path, .. }, namespace) = locate(&self.app_index, &self.res_index, resource)?;
+        let path_buf = self.cache_dir.join(path);
+        self.ensure_cached(path_buf.as_path(), self.game_server.cdn_url(namespace, path))
+            .map(|_| path_buf)
+    }
+
+    fn hash_of(&self, resource: &str) -> Result<&str, CacheError> {
+        let (IndexEntry { md5, .. }, _) = locate(&self.app_index, &self.res_index, resource)?;
+        Ok(md5)
+    }
+
+    fn resource_size(&self, resource: &str) -> Result<u64, CacheError> {
+        let (IndexEntry { size, .. }, _) = locate(&self.app_index, &self.res_index, resource)?;
+        Ok(*size)
+    }
+
+    fn resource_info(&self, resource: &str) -> Option<ResourceInfo> {
+        locate(&self.app_index, &self.res_index, resource).ok().map(|(entry, _)| ResourceInfo::from(entry))
+    }
+
+    fn verify(&self, resource: &str) -> Result<bool, CacheError> {
+        let (entry, _) = locate(&self.app_index, &self.res_index, resource)?;
+        verify_file(&self.cache_dir.join(&entry.path), entry)
+    }
+
+    fn fetch_range(&self, resource: &str, range: Range<u64>) -> Result<Vec<u8>, CacheError> {
+        let (IndexEntry { path, size, .. }, namespace) = locate(&self.app_index, &self.res_index, resource)?;
+        let url = self.game_server.cdn_url(namespace, path);
+
+        let file = self.cache_dir.join(path);
+        if fs::exists(&file)? {
+            return Ok(read_file_range(&file, range)?);
+        }
+        // Truncate to the resource size, requesting a range past the end of the file is an error
+        let range = range.start..range.end.min(*size);
+        if range.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (partial, buffer) = self.download_range(&url, Some(&range))?;
+        if partial {
+            Ok(buffer)
         } else {
-            Err(CacheError::ResourceNotFound(resource))
+            // Range not supported by server, cache the entire file
+            self.with_download_lock(&file, || if fs::exists(&file)? { Ok(()) } else { write_atomic(&file, &buffer) })?;
+
+            let end = usize::try_from(range.end).unwrap_or(usize::MAX).min(buffer.len());
+            let start = usize::try_from(range.start).unwrap_or(usize::MAX).min(end);
+            Ok(buffer[start..end].to_vec())
         }
     }
+}
+
+/// Reads resources from a game install, falling back to the CDN for resources not yet downloaded by the game launcher
+pub struct HybridCache {
+    reader: CacheReader,
+    downloader: CacheDownloader
+}
+
+impl HybridCache {
+    /// Combines a game install and CDN cache
+    ///
+    /// # Arguments
+    ///
+    /// * `reader`: Game install
+    /// * `downloader`: CDN cache, must be of the same client version as the game install
+    ///
+    /// returns: Result<HybridCache, CacheError>, with [`CacheError::ClientVersionMismatch`] if the client versions differ
+    pub fn new(reader: CacheReader, downloader: CacheDownloader) -> Result<HybridCache, CacheError> {
+        if reader.client_version() != downloader.client_version() {
+            return Err(CacheError::ClientVersionMismatch { local: reader.client_version, remote: downloader.client_version });
+        }
+        Ok(HybridCache { reader, downloader })
+    }
+
+    /// Returns true if the resource is available in the game install
+    fn is_local(&self, resource: &str) -> bool {
+        self.reader.path_of(resource).is_ok()
+    }
+}
+
+impl SharedCache for HybridCache {
+    fn client_version(&self) -> &str {
+        self.reader.client_version()
+    }
+
+    fn iter_resources(&self) -> impl Iterator<Item=&str> {
+        self.downloader.iter_resources()
+    }
+
+    fn has_resource(&self, resource: &str) -> bool {
+        self.downloader.has_resource(resource)
+    }
+
+    fn fetch(&self, resource: &str) -> Result<Vec<u8>, CacheError> {
+        if self.is_local(resource) { self.reader.fetch(resource) } else { self.downloader.fetch(resource) }
+    }
 
     fn try_fetch(&self, resource: &str) -> Result<Option<Vec<u8>>, CacheError> {
-        let resource = resource.to_ascii_lowercase().replace('\\', "/");
-        if let Some(IndexEntry { path, .. }) = self.app_index.get(&resource) {
-            self.fetch_file(self.cache_dir.join(path), format!("https://binaries.eveonline.com/{}", path)).map(Option::Some)
-        } else if let Some(IndexEntry { path, ..}) = self.res_index.get(&resource) {
-            self.fetch_file(self.cache_dir.join(path), format!("https://resources.eveonline.com/{}", path)).map(Option::Some)
-        } else {
-            Ok(None)
+        if self.is_local(resource) { self.reader.try_fetch(resource) } else { self.downloader.try_fetch(resource) }
+    }
+
+    fn path_of(&self, resource: &str) -> Result<PathBuf, CacheError> {
+        if self.is_local(resource) { self.reader.path_of(resource) } else { self.downloader.path_of(resource) }
+    }
+
+    fn hash_of(&self, resource: &str) -> Result<&str, CacheError> {
+        self.downloader.hash_of(resource)
+    }
+
+    fn resource_size(&self, resource: &str) -> Result<u64, CacheError> {
+        self.downloader.resource_size(resource)
+    }
+
+    fn resource_info(&self, resource: &str) -> Option<ResourceInfo> {
+        self.downloader.resource_info(resource)
+    }
+
+    fn verify(&self, resource: &str) -> Result<bool, CacheError> {
+        if self.is_local(resource) { self.reader.verify(resource) } else { self.downloader.verify(resource) }
+    }
+
+    fn fetch_range(&self, resource: &str, range: Range<u64>) -> Result<Vec<u8>, CacheError> {
+        if self.is_local(resource) { self.reader.fetch_range(resource, range) } else { self.downloader.fetch_range(resource, range) }
+    }
+}
+
+/// In-memory SharedCache with a fixed set of resources, for testing and sandboxing without a game install or network access
+///
+/// [`SharedCache::path_of`] writes resources to a temporary directory on demand, which is removed when the MemoryCache is dropped
+pub struct MemoryCache {
+    client_version: String,
+    resources: HashMap<String, (Vec<u8>, String)>,
+    temp_dir: PathBuf,
+    paths: Mutex<HashMap<String, PathBuf>>
+}
+
+impl MemoryCache {
+    /// Creates a MemoryCache from resource contents
+    ///
+    /// # Arguments
+    ///
+    /// * `client_version`: Client version reported by [`SharedCache::client_version`]
+    /// * `resources`: Resource contents by resource name, e.g. `res:/ui/texture/icons/1_64_1.png`
+    pub fn new<T: Into<String>>(client_version: T, resources: HashMap<String, Vec<u8>>) -> MemoryCache {
+        static INSTANCE: AtomicUsize = AtomicUsize::new(0);
+
+        let resources = resources.into_iter()
+            .map(|(resource, data)| {
+                let md5 = format!("{:032x}", u128::from_be_bytes(Md5::digest(&data).into()));
+                (normalize_resource(&resource), (data, md5))
+            })
+            .collect();
+        let temp_dir = std::env::temp_dir().join(format!("evesharedcache_memory_{}_{}", std::process::id(), INSTANCE.fetch_add(1, Ordering::Relaxed)));
+
+        MemoryCache { client_version: client_version.into(), resources, temp_dir, paths: Mutex::new(HashMap::new()) }
+    }
+
+    /// Creates a MemoryCache from the files in a zip archive
+    ///
+    /// Files are named by their resource name with the namespace as top-level directory, e.g. `res/ui/texture/icons/1_64_1.png` for `res:/ui/texture/icons/1_64_1.png`
+    ///
+    /// # Arguments
+    ///
+    /// * `client_version`: Client version reported by [`SharedCache::client_version`]
+    /// * `reader`: Zip archive
+    #[cfg(feature = "zip")]
+    pub fn from_zip<T: Into<String>, R: Read + Seek>(client_version: T, reader: R) -> Result<MemoryCache, CacheError> {
+        let mut archive = zip::ZipArchive::new(reader).map_err(io::Error::from)?;
+        let mut resources = HashMap::with_capacity(archive.len());
+        for index in 0..archive.len() {
+            let mut file = archive.by_index(index).map_err(io::Error::from)?;
+            if !file.is_file() {
+                continue;
+            }
+            let Some((namespace, path)) = file.name().split_once('/') else { continue };
+            let resource = format!("{}:/{}", namespace, path);
+
+            let mut data = Vec::with_capacity(file.size() as usize);
+            file.read_to_end(&mut data)?;
+            resources.insert(resource, data);
+        }
+        Ok(MemoryCache::new(client_version, resources))
+    }
+
+    /// Path of a resource relative to the temporary directory
+    fn relative_path(resource: &str) -> String {
+        match resource.split_once(":/") {
+            Some((namespace, path)) => format!("{}/{}", namespace, path),
+            None => resource.to_string()
         }
     }
 
+    fn get(&self, resource: &str) -> Result<(&Vec<u8>, &String), CacheError> {
+        let resource = normalize_resource(resource);
+        match self.resources.get(&resource) {
+            Some((data, md5)) => Ok((data, md5)),
+            None => Err(CacheError::ResourceNotFound(resource))
+        }
+    }
+}
+
+impl Drop for MemoryCache {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.temp_dir);
+    }
+}
+
+impl SharedCache for MemoryCache {
+    fn client_version(&self) -> &str {
+        &self.client_version
+    }
+
+    fn iter_resources(&self) -> impl Iterator<Item=&str> {
+        self.resources.keys().map(String::as_str)
+    }
+
+    fn has_resource(&self, resource: &str) -> bool {
+        self.resources.contains_key(&normalize_resource(resource))
+    }
+
+    fn fetch(&self, resource: &str) -> Result<Vec<u8>, CacheError> {
+        self.get(resource).map(|(data, _)| data.clone())
+    }
+
+    fn try_fetch(&self, resource: &str) -> Result<Option<Vec<u8>>, CacheError> {
+        Ok(self.get(resource).ok().map(|(data, _)| data.clone()))
+    }
+
     fn path_of(&self, resource: &str) -> Result<PathBuf, CacheError> {
-        let resource = resource.to_ascii_lowercase().replace('\\', "/");
-        if let Some(IndexEntry { path, .. }) = self.app_index.get(&resource) {
-            let path_buf = self.cache_dir.join(path);
-            self.ensure_cached(path_buf.as_path(), format!("https://binaries.eveonline.com/{}", path))
-                .map(|_| path_buf)
-        } else if let Some(IndexEntry { path, ..}) = self.res_index.get(&resource) {
-            let path_buf = self.cache_dir.join(path);
-            self.ensure_cached(path_buf.as_path(), format!("https://resources.eveonline.com/{}", path))
-                .map(|_| path_buf)
-        } else {
-            Err(CacheError::ResourceNotFound(resource))
+        let (data, _) = self.get(resource)?;
+        let resource = normalize_resource(resource);
+
+        let mut paths = self.paths.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(path) = paths.get(&resource) {
+            return Ok(path.clone());
+        }
+        let path = self.temp_dir.join(Self::relative_path(&resource));
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
         }
+        fs::write(&path, data)?;
+        paths.insert(resource, path.clone());
+        Ok(path)
     }
 
     fn hash_of(&self, resource: &str) -> Result<&str, CacheError> {
-        let resource = resource.to_ascii_lowercase().replace('\\', "/");
-        let IndexEntry { md5, .. } = self.app_index.get(&resource)
-            .or_else(|| self.res_index.get(&resource))
-            .ok_or_else(|| CacheError::ResourceNotFound(resource.to_string()))?;
+        self.get(resource).map(|(_, md5)| md5.as_str())
+    }
+
+    fn resource_size(&self, resource: &str) -> Result<u64, CacheError> {
+        self.get(resource).map(|(data, _)| data.len() as u64)
+    }
+
+    fn resource_info(&self, resource: &str) -> Option<ResourceInfo> {
+        let (data, md5) = self.get(resource).ok()?;
+        Some(ResourceInfo {
+            path: Self::relative_path(&normalize_resource(resource)),
+            md5: md5.clone(),
+            size: data.len() as u64,
+            compressed: data.len() as u64
+        })
+    }
+
+    fn fetch_reader(&self, resource: &str) -> Result<Box<dyn Read>, CacheError> {
+        Ok(Box::new(io::Cursor::new(self.fetch(resource)?)))
+    }
+
+    fn verify(&self, resource: &str) -> Result<bool, CacheError> {
+        self.get(resource).map(|_| true)
+    }
+}
+
+/// Async variant of [`CacheDownloader`], using tokio for file IO
+///
+/// Shares the on-disk cache layout with [`CacheDownloader`]; Both may be used on the same directory.
+#[cfg(feature = "async")]
+pub struct AsyncCacheDownloader {
+    cache_dir: PathBuf,
+    http_client: reqwest::Client,
+    client_version: String,
+    game_server: GameServer,
+    client_platform: ClientPlatform,
+    options: DownloadOptions,
+    app_index: HashMap<String, IndexEntry>,
+    res_index: HashMap<String, IndexEntry>,
+    /// Per-file locks for in-progress downloads, see [`CacheDownloader`]
+    downloads: Mutex<HashMap<PathBuf, Arc<tokio::sync::Mutex<()>>>>
+}
+
+#[cfg(feature = "async")]
+impl AsyncCacheDownloader {
+    /// Provides access to the game file CDN, creating a local on-disk cache
+    ///
+    /// # Arguments
+    ///
+    /// * `directory`: Directory for local caching of downloaded files, created if not existing
+    /// * `game_server`: Server to download the game client of
+    /// * `client_platform`: Build of the game to download, Windows or macOS
+    /// * `user_agent`: User Agent to use with HTTP requests
+    /// * `options`: HTTP timeout and retry options, see [`DownloadOptions::default`]
+    ///
+    /// returns: Result<AsyncCacheDownloader, CacheError>
+    pub async fn initialize<T: Into<PathBuf>>(directory: T, game_server: GameServer, client_platform: ClientPlatform, user_agent: &str, options: DownloadOptions) -> Result<AsyncCacheDownloader, CacheError> {
+        let cache_dir = directory.into();
+        tokio::fs::create_dir_all(&cache_dir).await?;
+        let http_client = reqwest::Client::builder().user_agent(http_user_agent(user_agent)).timeout(options.request_timeout).build()?;
+
+        if tokio::fs::try_exists(cache_dir.join("updater.exe")).await? || tokio::fs::try_exists(cache_dir.join("tq")).await? {
+            return Err(CacheError::DownloadIntoGameInstall);
+        }
+
+        let client_version = http_client.get(game_server.client_version_url())
+            .send().await?
+            .error_for_status()?
+            .json::<ClientVersion>().await?;
+
+        if client_version.protected == Some(true) {
+            Err(CacheError::GameServerProtected)?;
+        }
+
+        let mut downloader = AsyncCacheDownloader {
+            cache_dir,
+            http_client,
+            client_version: client_version.buildNumber.trim().to_string(),
+            game_server,
+            client_platform,
+            options,
+            app_index: HashMap::new(),
+            res_index: HashMap::new(),
+            downloads: Mutex::new(HashMap::new())
+        };
+
+        let index_name = client_platform.app_index_name(&downloader.client_version);
+        let file = downloader.cache_dir.join(&index_name);
+        let url = game_server.cdn_url(ResourceNamespace::App, &index_name);
+
+        let app_index = String::from_utf8(downloader.fetch_file(file, url).await?).map_err(io::Error::other)?;
+        IndexEntry::load_index(&app_index, &mut downloader.app_index)?;
+        let res_index = String::from_utf8(downloader.fetch("app:/resfileindex.txt").await?).map_err(io::Error::other)?;
+        IndexEntry::load_index(&res_index, &mut downloader.res_index)?;
+
+        Ok(downloader)
+    }
+
+    /// Downloads a file, retrying transient failures as configured in [`DownloadOptions`]
+    async fn download(&self, url: &str) -> Result<Vec<u8>, CacheError> {
+        let mut attempt = 0;
+        loop {
+            let result = match self.http_client.get(url).send().await.and_then(reqwest::Response::error_for_status) {
+                Ok(response) => response.bytes().await,
+                Err(err) => Err(err)
+            };
+
+            match result {
+                Ok(buffer) => return Ok(buffer.to_vec()),
+                Err(err) if attempt < self.options.max_retries && is_transient(&err) => {
+                    tokio::time::sleep(self.options.backoff(attempt)).await;
+                    attempt += 1;
+                }
+                Err(err) => return Err(err.into())
+            }
+        }
+    }
+
+    /// Downloads a file if it is not yet cached, returning the downloaded bytes or None if the file was already cached
+    ///
+    /// Concurrent calls for the same file wait for a single download, rather than each downloading the file
+    async fn ensure_cached(&self, file: &Path, url: String) -> Result<Option<Vec<u8>>, CacheError> {
+        if tokio::fs::try_exists(file).await? {
+            return Ok(None);
+        }
+
+        let lock = Arc::clone(self.downloads.lock().unwrap().entry(file.to_path_buf()).or_default());
+        let result = {
+            let _guard = lock.lock().await;
+            self.download_into(file, &url).await
+        };
+
+        let mut downloads = self.downloads.lock().unwrap();
+        if Arc::strong_count(&lock) == 2 {  // No other tasks waiting on this download
+            downloads.remove(file);
+        }
+        result
+    }
+
+    /// Downloads a file into the cache, must be called while holding the download lock for `file`
+    async fn download_into(&self, file: &Path, url: &str) -> Result<Option<Vec<u8>>, CacheError> {
+        // Another task may have completed the download while we were waiting
+        if tokio::fs::try_exists(file).await? {
+            Ok(None)
+        } else {
+            let buffer = self.download(url).await?;
+
+            if let Some(parent) = file.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            // Write to a temporary file and rename, see `write_atomic`
+            let temp_path = temp_path_of(file);
+            let written = match tokio::fs::write(&temp_path, &buffer).await {
+                Ok(()) => tokio::fs::rename(&temp_path, file).await,
+                Err(err) => Err(err)
+            };
+            if let Err(err) = written {
+                let _ = tokio::fs::remove_file(&temp_path).await;
+                return Err(err.into());
+            }
+
+            Ok(Some(buffer))
+        }
+    }
+
+    async fn fetch_file(&self, file: PathBuf, url: String) -> Result<Vec<u8>, CacheError> {
+        match self.ensure_cached(&file, url).await? {
+            Some(buffer) => Ok(buffer),
+            None => Ok(tokio::fs::read(file).await?)
+        }
+    }
+
+    /// Game server this downloader was initialized for
+    pub fn game_server(&self) -> GameServer {
+        self.game_server
+    }
+
+    /// Platform of the game client this downloader was initialized for
+    pub fn client_platform(&self) -> ClientPlatform {
+        self.client_platform
+    }
+
+    /// See [`SharedCache::client_version`]
+    pub fn client_version(&self) -> &str {
+        &self.client_version
+    }
+
+    /// See [`SharedCache::build_number`]
+    pub fn build_number(&self) -> Option<u64> {
+        build_number(&self.client_version)
+    }
+
+    /// See [`SharedCache::iter_resources`]
+    pub fn iter_resources(&self) -> impl Iterator<Item=&str> {
+        Keys::chain(self.app_index.keys(), self.res_index.keys()).map(String::as_str)
+    }
+
+    /// See [`SharedCache::resources_sorted`]
+    pub fn resources_sorted(&self) -> Vec<&str> {
+        let mut resources = self.iter_resources().collect::<Vec<_>>();
+        resources.sort_unstable();
+        resources.dedup();
+        resources
+    }
+
+    /// See [`SharedCache::has_resource`]
+    pub fn has_resource(&self, resource: &str) -> bool {
+        locate(&self.app_index, &self.res_index, resource).is_ok()
+    }
+
+    /// See [`SharedCache::fetch`]
+    pub async fn fetch(&self, resource: &str) -> Result<Vec<u8>, CacheError> {
+        let (IndexEntry { path, .. }, namespace) = locate(&self.app_index, &self.res_index, resource)?;
+        self.fetch_file(self.cache_dir.join(path), self.game_server.cdn_url(namespace, path)).await
+    }
+
+    /// See [`SharedCache::try_fetch`]
+    pub async fn try_fetch(&self, resource: &str) -> Result<Option<Vec<u8>>, CacheError> {
+        match locate(&self.app_index, &self.res_index, resource) {
+            Ok((IndexEntry { path, .. }, namespace)) => self.fetch_file(self.cache_dir.join(path), self.game_server.cdn_url(namespace, path)).await.map(Option::Some),
+            Err(CacheError::ResourceNotFound(_)) => Ok(None),
+            Err(err) => Err(err)
+        }
+    }
+
+    /// See [`SharedCache::path_of`]
+    pub async fn path_of(&self, resource: &str) -> Result<PathBuf, CacheError> {
+        let (IndexEntry { path, .. }, namespace) = locate(&self.app_index, &self.res_index, resource)?;
+        let path_buf = self.cache_dir.join(path);
+        self.ensure_cached(&path_buf, self.game_server.cdn_url(namespace, path)).await
+            .map(|_| path_buf)
+    }
+
+    /// See [`SharedCache::hash_of`]
+    ///
+    /// Hashes are read from the in-memory index, this function does not perform IO
+    pub fn hash_of(&self, resource: &str) -> Result<&str, CacheError> {
+        let (IndexEntry { md5, .. }, _) = locate(&self.app_index, &self.res_index, resource)?;
         Ok(md5)
     }
+
+    /// See [`SharedCache::resource_size`]
+    pub fn resource_size(&self, resource: &str) -> Result<u64, CacheError> {
+        let (IndexEntry { size, .. }, _) = locate(&self.app_index, &self.res_index, resource)?;
+        Ok(*size)
+    }
+
+    /// See [`SharedCache::resource_info`]
+    pub fn resource_info(&self, resource: &str) -> Option<ResourceInfo> {
+        locate(&self.app_index, &self.res_index, resource).ok().map(|(entry, _)| ResourceInfo::from(entry))
+    }
+
+    /// See [`SharedCache::verify`]
+    ///
+    /// Returns false if there is no local copy of the resource, this does not download the resource
+    pub async fn verify(&self, resource: &str) -> Result<bool, CacheError> {
+        use tokio::io::AsyncReadExt;
+
+        let (entry, _) = locate(&self.app_index, &self.res_index, resource)?;
+        let path = self.cache_dir.join(&entry.path);
+        if !tokio::fs::try_exists(&path).await? {
+            return Ok(false);
+        }
+
+        let mut md5 = Md5::new();
+        let mut file = tokio::fs::File::open(&path).await?;
+        let mut buf = vec![0; 64 * 1024];
+        loop {
+            let read = file.read(&mut buf).await?;
+            if read == 0 { break; }
+            md5.update(&buf[..read]);
+        }
+        Ok(u128::from_be_bytes(md5.finalize().into()) == entry.md5_value()?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use std::fs;
+    use std::io;
+    use std::ops::Deref;
+    use std::path::{Path, PathBuf};
+    use crate::cache::{is_newer, temp_path_of, write_atomic, CacheDownloader, CacheError, CacheReader, ClientPlatform, DownloadOptions, GameServer, IndexEntry, MemoryCache, SharedCache};
+
+    /// Temporary directory for a test, removed when dropped so that failing tests also clean up
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> io::Result<TempDir> {
+            let dir = std::env::temp_dir().join(format!("evesharedcache_{}_{}", name, std::process::id()));
+            fs::create_dir_all(&dir)?;
+            Ok(TempDir(dir))
+        }
+    }
+
+    impl Deref for TempDir {
+        type Target = Path;
+
+        fn deref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    /// Downloader with the specified indices, without fetching the client version or index files
+    fn test_downloader(cache_dir: PathBuf, app_index: HashMap<String, IndexEntry>, res_index: HashMap<String, IndexEntry>) -> CacheDownloader {
+        CacheDownloader {
+            cache_dir,
+            http_client: reqwest::blocking::Client::new(),
+            client_version: "123".to_string(),
+            game_server: GameServer::Tranquility,
+            client_platform: ClientPlatform::Windows,
+            options: DownloadOptions::default(),
+            app_index,
+            res_index,
+            downloads: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    #[test]
+    fn test_build_number() {
+        assert_eq!(MemoryCache::new("2955811", HashMap::new()).build_number(), Some(2955811));
+        assert_eq!(MemoryCache::new("2955811\r\n", HashMap::new()).build_number(), Some(2955811));
+        assert_eq!(MemoryCache::new("test", HashMap::new()).build_number(), None);
+
+        assert!(is_newer("2955811", "2954000"));
+        assert!(is_newer(" 10000000", "9999999"));    // Numeric, rather than lexicographic comparison
+        assert!(!is_newer("2955811", "2955811"));
+        assert!(!is_newer("2954000", "2955811"));
+        assert!(!is_newer("2955811", "unknown"));
+    }
+
+    #[test]
+    fn test_iter_resources_with_prefix() {
+        let mut index = HashMap::new();
+        IndexEntry::load_index(
+            "res:/UI/Texture/Icons/1_64_1.png,a1/a1b2_c3,d41d8cd98f00b204e9800998ecf8427e,0,0\n\
+            res:/ui/texture/icons/2_64_1.png,a1/a1b2_c4,d41d8cd98f00b204e9800998ecf8427e,0,0\n\
+            res:/ui/texture/classes/fitting/filtericons.png,a1/a1b2_c5,d41d8cd98f00b204e9800998ecf8427e,0,0\n\
+            app:/resfileindex.txt,a1/a1b2_c6,d41d8cd98f00b204e9800998ecf8427e,0,0\n",
+            &mut index
+        ).unwrap();
+        let reader = CacheReader { res_dir: PathBuf::new(), client_version: "0".to_string(), index };
+
+        let mut icons = reader.iter_resources_with_prefix("RES:\\ui\\Texture\\ICONS\\").collect::<Vec<_>>();
+        icons.sort();
+        assert_eq!(icons, ["res:/ui/texture/icons/1_64_1.png", "res:/ui/texture/icons/2_64_1.png"]);
+
+        assert_eq!(reader.iter_resources_with_prefix("res:/").count(), 3);
+        assert_eq!(reader.iter_resources_with_prefix("").count(), 4);
+        assert_eq!(reader.iter_resources_with_prefix("res:/ui/texture/icons/3").count(), 0);
+    }
+    #[test]
+    fn test_load_index_bom_crlf() {
+        let mut index = HashMap::new();
+        IndexEntry::load_index(
+            "\u{FEFF}app:/resfileindex.txt,a1/a1b2_c6,d41d8cd98f00b204e9800998ecf8427e,10,5\r\n\
+            res:/ui/texture/icons/1_64_1.png,a1/a1b2_c3,d41d8cd98f00b204e9800998ecf8427e,20,7,33206\r\n\
+            res:/ui/texture/icons/2_64_1.png,a1/a1b2_c4,d41d8cd98f00b204e9800998ecf8427e,30,9\r",
+            &mut index
+        ).unwrap();
+
+        assert_eq!(index.len(), 3);
+        assert_eq!(index["app:/resfileindex.txt"].size, 10);
+        assert_eq!(index["res:/ui/texture/icons/1_64_1.png"].compressed, 7);
+        assert_eq!(index["res:/ui/texture/icons/2_64_1.png"].compressed, 9);
+    }
+
+    #[test]
+    fn test_resource_size() {
+        let mut index = HashMap::new();
+        IndexEntry::load_index("res:/ui/texture/icons/1_64_1.png,a1/a1b2_c3,d41d8cd98f00b204e9800998ecf8427e,4096,1024\n", &mut index).unwrap();
+        let reader = CacheReader { res_dir: PathBuf::new(), client_version: "0".to_string(), index };
+
+        assert_eq!(reader.resource_size("res:/UI/Texture/Icons/1_64_1.png").unwrap(), 4096);
+        assert!(matches!(reader.resource_size("res:/ui/texture/icons/2_64_1.png"), Err(CacheError::ResourceNotFound(_))));
+
+        let info = reader.resource_info("res:/UI/Texture/Icons/1_64_1.png").unwrap();
+        assert_eq!((&*info.path, info.size, info.compressed), ("a1/a1b2_c3", 4096, 1024));
+        assert_eq!(reader.resource_info("res:/ui/texture/icons/2_64_1.png"), None);
+    }
+
+    #[test]
+    fn test_memory_cache() {
+        let cache = MemoryCache::new("123", HashMap::from([
+            ("res:/UI/Texture/Icons/1_64_1.png".to_string(), b"icon".to_vec()),
+            ("app:/resfileindex.txt".to_string(), Vec::new()),
+        ]));
+
+        assert_eq!(cache.client_version(), "123");
+        assert_eq!(cache.iter_resources().count(), 2);
+        assert_eq!(cache.fetch("res:/ui/texture/icons/1_64_1.png").unwrap(), b"icon");
+        assert_eq!(cache.hash_of("app:/resfileindex.txt").unwrap(), "d41d8cd98f00b204e9800998ecf8427e");
+        assert!(matches!(cache.try_fetch("res:/ui/texture/icons/2_64_1.png"), Ok(None)));
+
+        let path = cache.path_of("res:/ui/texture/icons/1_64_1.png").unwrap();
+        assert!(path.ends_with("res/ui/texture/icons/1_64_1.png"));
+        assert_eq!(fs::read(&path).unwrap(), b"icon");
+        assert_eq!(cache.path_of("res:/ui/texture/icons/1_64_1.png").unwrap(), path);
+
+        drop(cache);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_write_atomic() {
+        let dir = TempDir::new("write_atomic").unwrap();
+        let file = dir.join("a1").join("a1b2_c3");
+        assert_ne!(temp_path_of(&file), temp_path_of(&file));
+        write_atomic(&file, b"first").unwrap();
+        write_atomic(&file, b"second").unwrap();
+
+        assert_eq!(fs::read(&file).unwrap(), b"second");
+        assert_eq!(fs::read_dir(dir.join("a1")).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_resources_sorted() {
+        let mut app_index = HashMap::new();
+        IndexEntry::load_index(
+            "app:/resfileindex.txt,a1/a1b2_c4,d41d8cd98f00b204e9800998ecf8427e,0,0\n\
+            res:/ui/texture/icons/1_64_1.png,a1/a1b2_c3,d41d8cd98f00b204e9800998ecf8427e,0,0\n",
+            &mut app_index
+        ).unwrap();
+        let mut res_index = HashMap::new();
+        IndexEntry::load_index(
+            "res:/ui/texture/icons/2_64_1.png,a1/a1b2_c5,d41d8cd98f00b204e9800998ecf8427e,0,0\n\
+            res:/ui/texture/icons/1_64_1.png,a1/a1b2_c3,d41d8cd98f00b204e9800998ecf8427e,0,0\n",
+            &mut res_index
+        ).unwrap();
+        let downloader = test_downloader(std::env::temp_dir(), app_index, res_index);
+
+        assert_eq!(downloader.iter_resources().count(), 4);
+        assert_eq!(downloader.resources_sorted(), ["app:/resfileindex.txt", "res:/ui/texture/icons/1_64_1.png", "res:/ui/texture/icons/2_64_1.png"]);
+    }
+
+    #[test]
+    fn test_purge_plan() {
+        let cache_dir = TempDir::new("purge_plan").unwrap();
+        fs::create_dir_all(cache_dir.join("a1")).unwrap();
+        for file in ["a1/a1b2_c3", "a1/a1b2_c4", "a1/stale", "eveonline_123.txt", "eveonline_122.txt", "keep.txt"] {
+            fs::write(cache_dir.join(file), b"").unwrap();
+        }
+
+        let mut app_index = HashMap::new();
+        IndexEntry::load_index("app:/resfileindex.txt,a1/a1b2_c4,d41d8cd98f00b204e9800998ecf8427e,0,0\n", &mut app_index).unwrap();
+        let mut res_index = HashMap::new();
+        IndexEntry::load_index("res:/ui/texture/icons/1_64_1.png,a1/a1b2_c3,d41d8cd98f00b204e9800998ecf8427e,0,0\n", &mut res_index).unwrap();
+        let downloader = test_downloader(cache_dir.to_path_buf(), app_index, res_index);
+
+        let mut plan = downloader.purge_plan(&["keep.txt"]).unwrap();
+        plan.sort();
+        assert_eq!(plan, [cache_dir.join("a1").join("stale"), cache_dir.join("eveonline_122.txt")]);
+    }
+
+    #[test]
+    fn test_fetch_range_past_end() {
+        let mut res_index = HashMap::new();
+        IndexEntry::load_index("res:/ui/texture/icons/1_64_1.png,a1/a1b2_c3,d41d8cd98f00b204e9800998ecf8427e,4096,1024\n", &mut res_index).unwrap();
+        let downloader = test_downloader(std::env::temp_dir().join(format!("evesharedcache_fetch_range_{}", std::process::id())), HashMap::new(), res_index);
+
+        // Ranges entirely past the end of the resource are not requested from the CDN
+        assert_eq!(downloader.fetch_range("res:/ui/texture/icons/1_64_1.png", 4096..8192).unwrap(), Vec::<u8>::new());
+        assert_eq!(downloader.fetch_range("res:/ui/texture/icons/1_64_1.png", 5000..6000).unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn test_async_downloader() {
+        use std::io::{Read, Write};
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use crate::cache::AsyncCacheDownloader;
+
+        // Local CDN that responds slowly, so that concurrent requests overlap
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let cdn_url: &'static str = format!("http://{}", listener.local_addr().unwrap()).leak();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let server_requests = Arc::clone(&requests);
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                server_requests.fetch_add(1, Ordering::SeqCst);
+                let mut request = Vec::new();
+                let mut buf = [0; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    match stream.read(&mut buf) {
+                        Ok(0) | Err(_) => break,
+                        Ok(read) => request.extend_from_slice(&buf[..read]),
+                    }
+                }
+                std::thread::sleep(std::time::Duration::from_millis(200));
+                let _ = stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\nConnection: close\r\n\r\nhello");
+            }
+        });
+
+        let cache_dir = TempDir::new("async_downloader").unwrap();
+        let mut res_index = HashMap::new();
+        IndexEntry::load_index("res:/ui/texture/icons/1_64_1.png,a1/a1b2_c3,5d41402abc4b2a76b9719d911017c592,5,5\n", &mut res_index).unwrap();
+        let downloader = Arc::new(AsyncCacheDownloader {
+            cache_dir: cache_dir.to_path_buf(),
+            http_client: reqwest::Client::new(),
+            client_version: "123".to_string(),
+            game_server: GameServer::Serenity { client_version_url: cdn_url, binaries_url: cdn_url, resources_url: cdn_url },
+            client_platform: ClientPlatform::Windows,
+            options: DownloadOptions::default(),
+            app_index: HashMap::new(),
+            res_index,
+            downloads: std::sync::Mutex::new(HashMap::new()),
+        });
+        assert_eq!(downloader.resource_size("res:/ui/texture/icons/1_64_1.png").unwrap(), 5);
+
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        runtime.block_on(async {
+            assert!(!downloader.verify("res:/ui/texture/icons/1_64_1.png").await.unwrap());
+
+            let tasks = (0..4)
+                .map(|_| {
+                    let downloader = Arc::clone(&downloader);
+                    tokio::spawn(async move { downloader.path_of("res:/ui/texture/icons/1_64_1.png").await })
+                })
+                .collect::<Vec<_>>();
+            for task in tasks {
+                assert_eq!(task.await.unwrap().unwrap(), cache_dir.join("a1/a1b2_c3"));
+            }
+
+            assert!(downloader.verify("res:/ui/texture/icons/1_64_1.png").await.unwrap());
+        });
+        // Concurrent requests for the same file share a single download
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+        assert_eq!(fs::read(cache_dir.join("a1/a1b2_c3")).unwrap(), b"hello");
+        assert!(downloader.downloads.lock().unwrap().is_empty());
+    }
 }
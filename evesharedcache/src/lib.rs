@@ -1,9 +1,15 @@
 /// General SharedCache module
 ///
-/// Provides the [`cache::SharedCache`] trait for reading the EVE Online game file cache, with two implementations:
+/// Provides the [`cache::SharedCache`] trait for reading the EVE Online game file cache, with the following implementations:
 /// * [`cache::CacheReader`] provides READ-ONLY access to a locally-installed copy of the game
 /// * [`cache::CacheDownloader`] provides access to the game file CDN, creating a local on-disk cache
+/// * [`cache::HybridCache`] reads from a game install, downloading resources the game install does not have
+/// * [`cache::MemoryCache`] holds a fixed set of resources in memory, for testing without a game install or network access
+///
+/// With the `async` feature, [`cache::AsyncCacheDownloader`] provides an async variant of `CacheDownloader`
 pub mod cache;
+/// Loading of game localization strings from the sharedcache, see [`localization::load_localization`]
+pub mod localization;
 
 pub const CRATE_NAME: &'static str = env!("CARGO_PKG_NAME");
 pub const CRATE_VERSION: &'static str = env!("CARGO_PKG_VERSION");
@@ -16,7 +22,7 @@ pub mod test {
 
     #[test]
     fn test() -> Result<(), Box<dyn Error>> {
-        let downloader = cache::CacheDownloader::initialize("./cache", false, "")?;
+        let downloader = cache::CacheDownloader::initialize("./cache", cache::GameServer::Tranquility, cache::ClientPlatform::Windows, "", cache::DownloadOptions::default())?;
 
         let (valid, invalid) = downloader.validate()?;
 
@@ -0,0 +1,267 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use crate::cache::{CacheError, SharedCache};
+
+/// Error loading localization data
+#[derive(Debug)]
+pub enum LocalizationError {
+    /// Error fetching the localization resource from the sharedcache
+    Cache(CacheError),
+    /// Localization resource could not be parsed, usually indicates out-of-date library
+    MalformedPickle(String),
+}
+
+impl Display for LocalizationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LocalizationError::Cache(err) => write!(f, "cache error: {}", err),
+            LocalizationError::MalformedPickle(description) => write!(f, "malformed localization pickle: {}", description),
+        }
+    }
+}
+
+impl Error for LocalizationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LocalizationError::Cache(err) => Some(err),
+            LocalizationError::MalformedPickle(_) => None,
+        }
+    }
+}
+
+impl From<CacheError> for LocalizationError {
+    fn from(value: CacheError) -> Self {
+        LocalizationError::Cache(value)
+    }
+}
+
+/// Resource name of the localization strings for the specified language
+pub fn localization_resource(language: &str) -> String {
+    format!("res:/localizationfsd/localization_fsd_{}.pickle", language)
+}
+
+/// Load all localization strings for a language, mapping LocalizationStringIDs to text
+///
+/// `language` is the client's language code, e.g. "en-us", "de", "fr", "ja", "ru", "zh", "ko", "es"
+pub fn load_localization<C: SharedCache>(cache: &C, language: &str) -> Result<HashMap<u64, String>, LocalizationError> {
+    let data = cache.fetch(&localization_resource(language))?;
+    parse_localization(&data)
+}
+
+/// Parse a localization pickle, formatted as `(language, {message_id: (text, ...)})`
+fn parse_localization(data: &[u8]) -> Result<HashMap<u64, String>, LocalizationError> {
+    let messages = match pickle::decode(data)? {
+        pickle::Value::Tuple(mut items) | pickle::Value::List(mut items) if items.len() == 2 => items.pop().unwrap(),
+        dict @ pickle::Value::Dict(_) => dict,
+        _ => return Err(LocalizationError::MalformedPickle("expected (language, messages) tuple".to_string()))
+    };
+    let pickle::Value::Dict(entries) = messages else {
+        return Err(LocalizationError::MalformedPickle("expected messages dict".to_string()));
+    };
+
+    let mut strings = HashMap::with_capacity(entries.len());
+    for (key, value) in entries {
+        let pickle::Value::Int(message_id) = key else {
+            return Err(LocalizationError::MalformedPickle(format!("non-integer message ID {:?}", key)));
+        };
+        let text = match value {
+            pickle::Value::Tuple(items) | pickle::Value::List(items) => items.into_iter().next(),
+            value => Some(value)
+        };
+        match text {
+            Some(pickle::Value::String(text)) => { strings.insert(message_id as u64, text); }
+            Some(pickle::Value::None) | None => {}
+            Some(other) => return Err(LocalizationError::MalformedPickle(format!("non-string text for message {}: {:?}", message_id, other)))
+        }
+    }
+    Ok(strings)
+}
+
+/// Minimal decoder for the subset of the python pickle format used by localization data
+mod pickle {
+    use std::collections::HashMap;
+    use super::LocalizationError;
+
+    #[derive(Debug, Clone)]
+    #[allow(dead_code)] // Not all payloads are used by localization data, but are kept for error messages
+    pub enum Value {
+        None,
+        Bool(bool),
+        Int(i64),
+        Float(f64),
+        String(String),
+        Bytes(Vec<u8>),
+        Tuple(Vec<Value>),
+        List(Vec<Value>),
+        Dict(Vec<(Value, Value)>),
+    }
+
+    struct Reader<'a> {
+        data: &'a [u8],
+        position: usize,
+    }
+
+    impl<'a> Reader<'a> {
+        fn take(&mut self, len: usize) -> Result<&'a [u8], LocalizationError> {
+            let end = self.position.checked_add(len)
+                .filter(|end| *end <= self.data.len())
+                .ok_or_else(|| LocalizationError::MalformedPickle("unexpected end of data".to_string()))?;
+            let bytes = &self.data[self.position..end];
+            self.position = end;
+            Ok(bytes)
+        }
+
+        fn array<const N: usize>(&mut self) -> Result<[u8; N], LocalizationError> {
+            Ok(self.take(N)?.try_into().expect("slice of length N"))
+        }
+
+        fn u8(&mut self) -> Result<u8, LocalizationError> {
+            Ok(self.array::<1>()?[0])
+        }
+
+        fn u32(&mut self) -> Result<u32, LocalizationError> {
+            Ok(u32::from_le_bytes(self.array()?))
+        }
+
+        fn string(&mut self, len: usize) -> Result<Value, LocalizationError> {
+            let bytes = self.take(len)?;
+            String::from_utf8(bytes.to_vec())
+                .map(Value::String)
+                .map_err(|err| LocalizationError::MalformedPickle(format!("invalid UTF-8 string: {}", err)))
+        }
+    }
+
+    fn malformed(description: &str) -> LocalizationError {
+        LocalizationError::MalformedPickle(description.to_string())
+    }
+
+    pub fn decode(data: &[u8]) -> Result<Value, LocalizationError> {
+        let mut reader = Reader { data, position: 0 };
+        let mut stack: Vec<Value> = Vec::new();
+        let mut marks: Vec<usize> = Vec::new();
+        let mut memo: HashMap<u32, Value> = HashMap::new();
+
+        macro_rules! pop {
+            () => { stack.pop().ok_or_else(|| malformed("stack underflow"))? };
+        }
+        macro_rules! pop_mark {
+            () => {{
+                let mark = marks.pop().ok_or_else(|| malformed("missing mark"))?;
+                if mark > stack.len() { return Err(malformed("stack underflow")); }
+                stack.split_off(mark)
+            }};
+        }
+
+        loop {
+            match reader.u8()? {
+                0x80 => { reader.u8()?; }                           // PROTO
+                0x95 => { reader.take(8)?; }                        // FRAME
+                b'(' => marks.push(stack.len()),                    // MARK
+                b'.' => return stack.pop().ok_or_else(|| malformed("empty stack at STOP")),
+                b'N' => stack.push(Value::None),
+                0x88 => stack.push(Value::Bool(true)),              // NEWTRUE
+                0x89 => stack.push(Value::Bool(false)),             // NEWFALSE
+                b'J' => stack.push(Value::Int(i32::from_le_bytes(reader.array()?) as i64)),
+                b'K' => stack.push(Value::Int(reader.u8()? as i64)),
+                b'M' => stack.push(Value::Int(u16::from_le_bytes(reader.array()?) as i64)),
+                0x8a => {                                           // LONG1
+                    let len = reader.u8()? as usize;
+                    let bytes = reader.take(len)?;
+                    if len > 8 { return Err(malformed("integer too large")); }
+                    let fill = if bytes.last().is_some_and(|b| b & 0x80 != 0) { 0xFF } else { 0x00 };
+                    let mut buf = [fill; 8];
+                    buf[..len].copy_from_slice(bytes);
+                    stack.push(Value::Int(i64::from_le_bytes(buf)));
+                }
+                b'G' => stack.push(Value::Float(f64::from_be_bytes(reader.array()?))),
+                b'X' => { let len = reader.u32()? as usize; stack.push(reader.string(len)?); }
+                0x8c => { let len = reader.u8()? as usize; stack.push(reader.string(len)?); }  // SHORT_BINUNICODE
+                0x8d => { let len = u64::from_le_bytes(reader.array()?) as usize; stack.push(reader.string(len)?); }  // BINUNICODE8
+                b'T' => { let len = reader.u32()? as usize; stack.push(reader.string(len)?); }  // BINSTRING (python 2 str)
+                b'U' => { let len = reader.u8()? as usize; stack.push(reader.string(len)?); }   // SHORT_BINSTRING
+                b'B' => { let len = reader.u32()? as usize; stack.push(Value::Bytes(reader.take(len)?.to_vec())); }
+                b'C' => { let len = reader.u8()? as usize; stack.push(Value::Bytes(reader.take(len)?.to_vec())); }
+                b')' => stack.push(Value::Tuple(Vec::new())),
+                b']' => stack.push(Value::List(Vec::new())),
+                b'}' => stack.push(Value::Dict(Vec::new())),
+                b't' => { let items = pop_mark!(); stack.push(Value::Tuple(items)); }
+                0x85 => { let a = pop!(); stack.push(Value::Tuple(vec![a])); }
+                0x86 => { let b = pop!(); let a = pop!(); stack.push(Value::Tuple(vec![a, b])); }
+                0x87 => { let c = pop!(); let b = pop!(); let a = pop!(); stack.push(Value::Tuple(vec![a, b, c])); }
+                b'l' => { let items = pop_mark!(); stack.push(Value::List(items)); }
+                b'd' => {
+                    let mut items = pop_mark!().into_iter();
+                    let mut entries = Vec::with_capacity(items.len() / 2);
+                    while let (Some(key), Some(value)) = (items.next(), items.next()) {
+                        entries.push((key, value));
+                    }
+                    stack.push(Value::Dict(entries));
+                }
+                b'a' => {
+                    let item = pop!();
+                    match stack.last_mut() {
+                        Some(Value::List(list)) => list.push(item),
+                        _ => return Err(malformed("APPEND to non-list"))
+                    }
+                }
+                b'e' => {
+                    let items = pop_mark!();
+                    match stack.last_mut() {
+                        Some(Value::List(list)) => list.extend(items),
+                        _ => return Err(malformed("APPENDS to non-list"))
+                    }
+                }
+                b's' => {
+                    let value = pop!();
+                    let key = pop!();
+                    match stack.last_mut() {
+                        Some(Value::Dict(dict)) => dict.push((key, value)),
+                        _ => return Err(malformed("SETITEM on non-dict"))
+                    }
+                }
+                b'u' => {
+                    let mut items = pop_mark!().into_iter();
+                    match stack.last_mut() {
+                        Some(Value::Dict(dict)) => {
+                            while let (Some(key), Some(value)) = (items.next(), items.next()) {
+                                dict.push((key, value));
+                            }
+                        }
+                        _ => return Err(malformed("SETITEMS on non-dict"))
+                    }
+                }
+                b'q' => { let index = reader.u8()? as u32; memo.insert(index, stack.last().ok_or_else(|| malformed("empty stack at PUT"))?.clone()); }
+                b'r' => { let index = reader.u32()?; memo.insert(index, stack.last().ok_or_else(|| malformed("empty stack at PUT"))?.clone()); }
+                0x94 => { let index = memo.len() as u32; memo.insert(index, stack.last().ok_or_else(|| malformed("empty stack at MEMOIZE"))?.clone()); }
+                b'h' => { let index = reader.u8()? as u32; stack.push(memo.get(&index).ok_or_else(|| malformed("unknown memo index"))?.clone()); }
+                b'j' => { let index = reader.u32()?; stack.push(memo.get(&index).ok_or_else(|| malformed("unknown memo index"))?.clone()); }
+                opcode => return Err(LocalizationError::MalformedPickle(format!("unsupported opcode 0x{:02X} at offset {}", opcode, reader.position - 1)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::parse_localization;
+
+    #[test]
+    fn test_parse_localization() {
+        // pickle.dumps(('en-us', {1: ('Hello', None, None), 70000: (u'Wörld', None, None), 3: (None, None, None)}), protocol=2)
+        let data: &[u8] = &[
+            0x80, 0x02,
+            0x58, 5, 0, 0, 0, b'e', b'n', b'-', b'u', b's', 0x71, 0,
+            0x7D, 0x71, 1, 0x28,
+            0x4B, 1, 0x58, 5, 0, 0, 0, b'H', b'e', b'l', b'l', b'o', 0x71, 2, 0x4E, 0x4E, 0x87, 0x71, 3,
+            0x4A, 0x70, 0x11, 0x01, 0x00, 0x58, 6, 0, 0, 0, b'W', 0xC3, 0xB6, b'r', b'l', b'd', 0x71, 4, 0x4E, 0x4E, 0x87, 0x71, 5,
+            0x4B, 3, 0x4E, 0x4E, 0x4E, 0x87, 0x71, 6,
+            0x75, 0x86, 0x71, 7, 0x2E
+        ];
+
+        let strings = parse_localization(data).unwrap();
+        assert_eq!(strings.len(), 2);
+        assert_eq!(strings[&1], "Hello");
+        assert_eq!(strings[&70000], "Wörld");
+    }
+}
@@ -15,34 +15,23 @@ pub fn export<W: std::io::Write>(out: W) {
         ("MAX_TARGETING_RANGE", magic_constants::MAX_TARGETING_RANGE)
     ]);
     
-    let holds = IndexMap::from([
-        ("SMB", cargo::SHIP_MAINTENANCE_BAY),
-        ("SMB_RORQ", cargo::SHIP_MAINTENANCE_BAY_RORQUAL),
-        ("FLEET", cargo::FLEET_HANGAR),
-        ("FUEL", cargo::FUEL_BAY),
-        ("MINING", cargo::MINING_HOLD),
-        ("GAS", cargo::GAS_HOLD),
-        ("MINERAL", cargo::MINERAL_HOLD),
-        ("AMMO", cargo::AMMO_HOLD),
-        ("COMMAND_CENTER", cargo::COMMAND_CENTER_HOLD),
-        ("PI", cargo::PLANETARY_COMMODITIES_HOLD),
-        ("QUAFE", cargo::QUAFE_HOLD),
-        ("CORPSE", cargo::CORPSE_HOLD),
-        ("BOOSTER", cargo::BOOSTER_HOLD),
-        ("SUBSYSTEM", cargo::SUBSYSTEM_HOLD),
-        ("ICE", cargo::ICE_HOLD),
-        ("DEPOT", cargo::MOBILE_DEPOT_HOLD),
-        ("INFRASTRUCTURE", cargo::INFRASTRUCTURE_HOLD),
-    ]);
-    
+    let holds = cargo::HoldKind::ALL.into_iter()
+        .map(|kind| (kind.export_name(), kind.hold_type()))
+        .collect::<IndexMap<_, _>>();
+
+    let wormhole_classes = wormhole::WORMHOLE_CLASSES.iter()
+        .map(|(class_id, class)| (*class_id, class))
+        .collect::<IndexMap<_, _>>();
+
     #[derive(serde::Serialize)]
     struct Exports {
         constants: IndexMap<&'static str, f64>,
-        holds: IndexMap<&'static str, cargo::CargoHoldType<'static>>
+        holds: IndexMap<&'static str, &'static cargo::CargoHoldType<'static>>,
+        wormhole_classes: IndexMap<crate::types::ids::WormholeClassID, &'static wormhole::WormholeClass>
     }
 
     use indexmap::IndexMap;
-    serde_json::to_writer_pretty(out, &Exports { constants, holds }).unwrap();
+    serde_json::to_writer_pretty(out, &Exports { constants, holds, wormhole_classes }).unwrap();
 }
 
 pub mod magic_constants {
@@ -79,6 +68,107 @@ pub mod cargo {
     use crate::util::item_list::TypeList;
     use crate::types::ids::AttributeID;
 
+    /// Ship cargo holds, other than the regular cargo bay
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    pub enum HoldKind {
+        ShipMaintenanceBay,
+        /// Rorqual ship maintenance bay, restricted to industrial ships
+        ShipMaintenanceBayRorqual,
+        FleetHangar,
+        FuelBay,
+        MiningHold,
+        GasHold,
+        MineralHold,
+        AmmoHold,
+        CommandCenterHold,
+        PlanetaryCommoditiesHold,
+        QuafeHold,
+        CorpseHold,
+        BoosterHold,
+        SubsystemHold,
+        IceHold,
+        MobileDepotHold,
+        InfrastructureHold,
+    }
+
+    impl HoldKind {
+        pub const ALL: [HoldKind; 17] = [
+            HoldKind::ShipMaintenanceBay,
+            HoldKind::ShipMaintenanceBayRorqual,
+            HoldKind::FleetHangar,
+            HoldKind::FuelBay,
+            HoldKind::MiningHold,
+            HoldKind::GasHold,
+            HoldKind::MineralHold,
+            HoldKind::AmmoHold,
+            HoldKind::CommandCenterHold,
+            HoldKind::PlanetaryCommoditiesHold,
+            HoldKind::QuafeHold,
+            HoldKind::CorpseHold,
+            HoldKind::BoosterHold,
+            HoldKind::SubsystemHold,
+            HoldKind::IceHold,
+            HoldKind::MobileDepotHold,
+            HoldKind::InfrastructureHold,
+        ];
+
+        /// Hold data for this kind of hold
+        pub fn hold_type(self) -> &'static CargoHoldType<'static> {
+            match self {
+                HoldKind::ShipMaintenanceBay => &SHIP_MAINTENANCE_BAY,
+                HoldKind::ShipMaintenanceBayRorqual => &SHIP_MAINTENANCE_BAY_RORQUAL,
+                HoldKind::FleetHangar => &FLEET_HANGAR,
+                HoldKind::FuelBay => &FUEL_BAY,
+                HoldKind::MiningHold => &MINING_HOLD,
+                HoldKind::GasHold => &GAS_HOLD,
+                HoldKind::MineralHold => &MINERAL_HOLD,
+                HoldKind::AmmoHold => &AMMO_HOLD,
+                HoldKind::CommandCenterHold => &COMMAND_CENTER_HOLD,
+                HoldKind::PlanetaryCommoditiesHold => &PLANETARY_COMMODITIES_HOLD,
+                HoldKind::QuafeHold => &QUAFE_HOLD,
+                HoldKind::CorpseHold => &CORPSE_HOLD,
+                HoldKind::BoosterHold => &BOOSTER_HOLD,
+                HoldKind::SubsystemHold => &SUBSYSTEM_HOLD,
+                HoldKind::IceHold => &ICE_HOLD,
+                HoldKind::MobileDepotHold => &MOBILE_DEPOT_HOLD,
+                HoldKind::InfrastructureHold => &INFRASTRUCTURE_HOLD,
+            }
+        }
+
+        /// Name of this hold in the [`super::export`] output
+        pub fn export_name(self) -> &'static str {
+            match self {
+                HoldKind::ShipMaintenanceBay => "SMB",
+                HoldKind::ShipMaintenanceBayRorqual => "SMB_RORQ",
+                HoldKind::FleetHangar => "FLEET",
+                HoldKind::FuelBay => "FUEL",
+                HoldKind::MiningHold => "MINING",
+                HoldKind::GasHold => "GAS",
+                HoldKind::MineralHold => "MINERAL",
+                HoldKind::AmmoHold => "AMMO",
+                HoldKind::CommandCenterHold => "COMMAND_CENTER",
+                HoldKind::PlanetaryCommoditiesHold => "PI",
+                HoldKind::QuafeHold => "QUAFE",
+                HoldKind::CorpseHold => "CORPSE",
+                HoldKind::BoosterHold => "BOOSTER",
+                HoldKind::SubsystemHold => "SUBSYSTEM",
+                HoldKind::IceHold => "ICE",
+                HoldKind::MobileDepotHold => "DEPOT",
+                HoldKind::InfrastructureHold => "INFRASTRUCTURE",
+            }
+        }
+
+        /// Ship attribute holding the capacity of this hold, see [`CargoHoldType::attribute_id`]
+        pub fn attribute_id(self) -> Option<AttributeID> {
+            self.hold_type().attribute_id
+        }
+    }
+
+    /// Kind and capacity attribute of all holds, see [`HoldKind`]
+    pub fn ship_holds() -> impl Iterator<Item=(HoldKind, Option<AttributeID>)> {
+        HoldKind::ALL.into_iter().map(|kind| (kind, kind.attribute_id()))
+    }
+
     #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     pub struct CargoHoldType<'a> {
         pub attribute_id: Option<AttributeID>,
@@ -397,4 +487,52 @@ pub mod wormhole {
     }
 
     // `WORMHOLE_EFFECTS` is removed; Data back in the SDE
+
+    /// Wormhole class, as referenced by the `wormholeClassID` of regions, constellations, and solar systems
+    ///
+    /// The most specific `wormholeClassID` applies; A solar system's class overrides that of its constellation, which overrides that of its region
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
+    pub struct WormholeClass {
+        /// Common name of this class, as used by players
+        pub name: &'static str,
+        /// Short description of the space this class covers
+        pub description: &'static str,
+        /// Whether this is a "shattered" wormhole class, without planets or moons
+        ///
+        /// Note: Shattered C1-C6 systems share their class with regular systems of that class, see their `WormholeEffect`/Star instead
+        pub is_shattered: bool,
+        /// Whether this is Thera
+        pub is_thera: bool,
+    }
+
+    const fn class(name: &'static str, description: &'static str) -> WormholeClass {
+        WormholeClass { name, description, is_shattered: false, is_thera: false }
+    }
+
+    pub const WORMHOLE_CLASSES: &[(ids::WormholeClassID, WormholeClass)] = &[
+        (1, class("C1", "Class 1 wormhole space; Always has a static connection to known space, smallest sleeper sites")),
+        (2, class("C2", "Class 2 wormhole space; Has a static connection to known space and a static to wormhole space")),
+        (3, class("C3", "Class 3 wormhole space; Always has a static connection to known space")),
+        (4, class("C4", "Class 4 wormhole space; Static connections to wormhole space only")),
+        (5, class("C5", "Class 5 wormhole space; Static connection to wormhole space, capital-sized sleeper sites")),
+        (6, class("C6", "Class 6 wormhole space; Static connection to wormhole space, largest sleeper sites")),
+        (7, class("High-sec", "Known space, high security")),
+        (8, class("Low-sec", "Known space, low security")),
+        (9, class("Null-sec", "Known space, null security")),
+        (12, WormholeClass { name: "Thera", description: "Thera; Large number of random connections to known space, no static connections, fortified sleeper sites", is_shattered: false, is_thera: true }),
+        (13, WormholeClass { name: "C13", description: "Small shattered wormhole space; Only frigate-sized ships may enter", is_shattered: true, is_thera: false }),
+        (14, class("Sentinel", "Drifter wormhole space (Sentinel MZ)")),
+        (15, class("Barbican", "Drifter wormhole space (Liberated Barbican)")),
+        (16, class("Vidette", "Drifter wormhole space (Sanctified Vidette)")),
+        (17, class("Conflux", "Drifter wormhole space (Conflux Eyrie)")),
+        (18, class("Redoubt", "Drifter wormhole space (Azdaja Redoubt)")),
+        (25, class("Pochven", "Triglavian space; Connected to known space through conduits and filaments")),
+    ];
+
+    /// Returns the [`WormholeClass`] for the specified `wormholeClassID`, or None if the class is not known
+    pub fn wormhole_class(class_id: ids::WormholeClassID) -> Option<&'static WormholeClass> {
+        WORMHOLE_CLASSES.iter()
+            .find(|(id, _)| *id == class_id)
+            .map(|(_, class)| class)
+    }
 }
\ No newline at end of file
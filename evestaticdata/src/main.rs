@@ -1,13 +1,74 @@
 use std::error::Error;
+use std::fs::File;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use clap::{Arg, ArgAction, Command};
+use clap::builder::ValueParser;
+use evestaticdata::sde::load::{SDELoadError, SDELoader};
+use evestaticdata::sde::update::SdeVersion;
 
-pub fn main() -> Result<(), Box<dyn Error>> {
-    #[cfg(all(feature = "sde_update", feature="sde_load"))] {
-        let version = evestaticdata::sde::update::update_sde("./temp/sde.zip")?;
-        println!("{}", version);
-        let full = evestaticdata::sde::load::SDELoader::new(std::fs::File::open("./temp/sde.zip")?)?.full()?;
-        drop(full);
+pub fn main() -> Result<ExitCode, Box<dyn Error>> {
+    let arg_matches = Command::new("evestaticdata")
+        .about("EVE Online SDE tool; Output is JSON for use in CI pipelines")
+        .args([
+            Arg::new("sde")
+                .long("sde")
+                .help("SDE zip file to use")
+                .default_value("./temp/sde.zip")
+                .value_parser(ValueParser::path_buf()),
+            Arg::new("update")
+                .long("update")
+                .help("Download the latest SDE into the SDE zip file if it is out of date")
+                .action(ArgAction::SetTrue),
+        ])
+        .subcommand_required(true)
+        .subcommands([
+            Command::new("version")
+                .about("Print the SDE version"),
+            Command::new("validate")
+                .about("Load the full SDE, exits with a non-zero exit code and prints the error if loading fails"),
+            Command::new("stats")
+                .about("Print the number of entries in each SDE file"),
+        ])
+        .get_matches();
+
+    let sde_path = arg_matches.get_one::<PathBuf>("sde").expect("sde has a default value");
+    if arg_matches.get_flag("update") {
+        evestaticdata::sde::update::update_sde(sde_path)?;
     }
 
+    let exit_code = match arg_matches.subcommand_name() {
+        Some("version") => {
+            println!("{}", serde_json::to_string(&SdeVersion::from_sde_zip(sde_path)?)?);
+            ExitCode::SUCCESS
+        }
+        Some("validate") => {
+            let mut loader = match File::open(sde_path).map_err(SDELoadError::from).and_then(SDELoader::new) {
+                Ok(loader) => loader,
+                Err(err) => {
+                    println!("{}", serde_json::json!({ "valid": false, "buildNumber": null, "error": err.to_string() }));
+                    return Ok(ExitCode::FAILURE);
+                }
+            };
+            match loader.full() {
+                Ok(_) => {
+                    println!("{}", serde_json::json!({ "valid": true, "buildNumber": loader.version() }));
+                    ExitCode::SUCCESS
+                }
+                Err(err) => {
+                    println!("{}", serde_json::json!({ "valid": false, "buildNumber": loader.version(), "error": err.to_string() }));
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Some("stats") => {
+            let sde = SDELoader::new(File::open(sde_path)?)?.full()?;
+            println!("{}", serde_json::to_string_pretty(&sde.table_sizes())?);
+            ExitCode::SUCCESS
+        }
+        _ => unreachable!("subcommand is required")
+    };
+
     // let mut version = SdeVersion::fetch_latest()?;
     // println!("Latest: {}", version.build_number());
     //
@@ -31,6 +92,5 @@ pub fn main() -> Result<(), Box<dyn Error>> {
     //     latest_build_number = prev_build_number;
     // }
 
-
-    Ok(())
+    Ok(exit_code)
 }
@@ -7,7 +7,8 @@ use crate::types::{ids, uuids, values};
 use crate::util;
 use indexmap::IndexMap;
 use serde::de::{DeserializeOwned, SeqAccess, Unexpected, Visitor};
-use serde::{Deserialize, Deserializer, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::error::Error;
 use std::fmt::{Debug, Display, Formatter};
 use std::fs::File;
@@ -15,6 +16,7 @@ use std::hash::Hash;
 use std::io;
 use std::io::{BufRead, BufReader, Read, Seek};
 use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
 use util::units::EVEUnit;
 use zip::ZipArchive;
 use zip::result::ZipError;
@@ -29,7 +31,11 @@ pub enum SDELoadError {
     /// SDE zip file did not contain expected file, did the SDE format change?
     ArchiveFileNotFound(String),
     /// Parsing the JSON content failed, did the SDE schema change?
-    ParseError { file: String, entry: usize, error: serde_json::Error},
+    ///
+    /// Unknown fields are ignored, unless the `sde_strict` feature is enabled
+    ///
+    /// `path` is the JSON path to the field that failed to parse (`.` for the entry itself), `line` the entry's text, truncated to [`PARSE_ERROR_LINE_LENGTH`] characters
+    ParseError { file: String, entry: usize, path: String, line: String, error: serde_json::Error },
     /// Data integrity problem, did the SDE schema change?
     IntegrityError(String)
 }
@@ -40,7 +46,7 @@ impl Display for SDELoadError {
             SDELoadError::IO(err) => write!(f, "IO error: {}", err),
             SDELoadError::Zip(err) => write!(f, "Zip error: {}", err),
             SDELoadError::ArchiveFileNotFound(filename) => write!(f, "SDE did not contain expected file: `{}`", filename),
-            SDELoadError::ParseError { file, entry, error } => write!(f, "Parse error in `{}` entry {}: {}", file, entry, error),
+            SDELoadError::ParseError { file, entry, path, line, error } => write!(f, "Parse error in `{}` entry {}: {} at `{}` (entry: {})", file, entry, error, path, line),
             SDELoadError::IntegrityError(err_description) => write!(f, "SDE data integrity error ({})", err_description)
         }
     }
@@ -58,6 +64,41 @@ impl Error for SDELoadError {
     }
 }
 
+/// Maximum length (in characters) of the entry text included in [`SDELoadError::ParseError`]
+pub const PARSE_ERROR_LINE_LENGTH: usize = 200;
+
+/// Parses a single JSONL entry, recording the JSON path to the failing field on error
+fn parse_entry<T: DeserializeOwned>(file: &str, entry: usize, line: &str) -> Result<T, SDELoadError> {
+    let mut deserializer = serde_json::Deserializer::from_str(line);
+    serde_path_to_error::deserialize(&mut deserializer)
+        .map_err(|error| (error.path().to_string(), error.into_inner()))
+        // Trailing characters after the entry
+        .and_then(|value| deserializer.end().map(|_| value).map_err(|error| (".".to_string(), error)))
+        .map_err(|(path, error)| {
+            let line = line.trim_end();
+            let line = match line.char_indices().nth(PARSE_ERROR_LINE_LENGTH) {
+                Some((truncate_at, _)) => format!("{}...", &line[..truncate_at]),
+                None => line.to_string()
+            };
+            SDELoadError::ParseError { file: file.to_owned(), entry, path, line, error }
+        })
+}
+
+#[test]
+fn test_parse_entry_error() {
+    let Err(SDELoadError::ParseError { file, entry, path, line, .. }) = parse_entry::<ContrabandType>("contrabandTypes.jsonl", 3, r#"{"_key": 11855, "factions": [{"_key": 500001, "attackMinSec": "high", "confiscateMinSec": 5.0, "fineByValue": 1.5, "standingLoss": 1.5}]}"#) else {
+        panic!("expected parse error");
+    };
+    assert_eq!((file.as_str(), entry), ("contrabandTypes.jsonl", 3));
+    assert_eq!(path, "factions[0].attackMinSec");
+    assert!(line.starts_with(r#"{"_key": 11855"#));
+
+    let Err(SDELoadError::ParseError { line, .. }) = parse_entry::<ContrabandType>("contrabandTypes.jsonl", 1, &format!("{{\"_key\": \"{}\"}}", "x".repeat(500))) else {
+        panic!("expected parse error");
+    };
+    assert_eq!(line.chars().count(), PARSE_ERROR_LINE_LENGTH + 3);
+}
+
 impl From<io::Error> for SDELoadError {
     fn from(value: io::Error) -> Self {
         SDELoadError::IO(value)
@@ -98,6 +139,11 @@ fn deserialize_inline_entry_map<'de, K: Deserialize<'de> + Hash + Eq + Ord, V: D
     deserializer.deserialize_seq(EntryVisitor::<K, V>(PhantomData::default(), PhantomData::default()))
 }
 
+/// Serialize an IndexMap of [`InlineEntry`]-trait values as a json-array, see [`deserialize_inline_entry_map`]
+fn serialize_inline_entry_map<K, V: Serialize, S: Serializer>(map: &IndexMap<K, V>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(map.values())
+}
+
 /// Deserialize a json-array of [`ExplicitMapEntry`] values into an IndexMap
 fn deserialize_explicit_entry_map<'de, K: Deserialize<'de> + Hash + Eq + Ord, V: Deserialize<'de>, D: Deserializer<'de>>(deserializer: D) -> Result<IndexMap<K, V>, D::Error> {
     struct EntryVisitor<K, V>(PhantomData<K>, PhantomData<V>);
@@ -121,6 +167,11 @@ fn deserialize_explicit_entry_map<'de, K: Deserialize<'de> + Hash + Eq + Ord, V:
     deserializer.deserialize_seq(EntryVisitor::<K, V>(PhantomData::default(), PhantomData::default()))
 }
 
+/// Serialize an IndexMap as a json-array of [`ExplicitMapEntry`] values, see [`deserialize_explicit_entry_map`]
+fn serialize_explicit_entry_map<K: Serialize, V: Serialize, S: Serializer>(map: &IndexMap<K, V>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(map.iter().map(|(_key, _value)| ExplicitMapEntry { _key, _value }))
+}
+
 // Helper macro for implement into-map collection
 macro_rules! impl_map_collect {
     ($id:ty, $val:ty, $field:ident) => {
@@ -141,7 +192,7 @@ macro_rules! impl_map_collect {
 
 // Generic types
 /// Helper type for JSON maps that are encoded as arrays of object entries
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize)]
 struct ExplicitMapEntry<K, V> {
     _key: K,
     _value: V
@@ -150,7 +201,7 @@ struct ExplicitMapEntry<K, V> {
 /// Position of an object on the New Eden cluster map, units in metres.
 ///
 /// See <https://developers.eveonline.com/docs/guides/map-data/> for detailed explanation
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="docs_export", doc_sde(common_type))]
 pub struct MapPosition {
@@ -165,7 +216,7 @@ pub struct MapPosition {
 /// Position of an object within a [`SolarSystem`], units in metres.
 ///
 /// See <https://developers.eveonline.com/docs/guides/map-data/> for detailed explanation
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="docs_export", doc_sde(common_type))]
 pub struct CelestialPosition {
@@ -180,7 +231,7 @@ pub struct CelestialPosition {
 /// 2D-map position of an object, units in metres.
 ///
 /// Up/down, Left/right directions depend on context, see <https://developers.eveonline.com/docs/guides/map-data/> for detailed explanation
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="docs_export", doc_sde(common_type))]
 pub struct Position2D {
@@ -193,7 +244,7 @@ pub struct Position2D {
 /// String with multiple language variants
 ///
 /// English is always available. Usually, all other languages are also available
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(common_type))]
 pub struct LocalizedString {
@@ -216,48 +267,240 @@ pub struct LocalizedString {
 }
 
 impl LocalizedString {
+    /// String in the specified language if available, else English string
+    pub fn get(&self, language: Language) -> &str {
+        let localized = match language {
+            Language::En => return &self.en,
+            Language::De => &self.de,
+            Language::Es => &self.es,
+            Language::Fr => &self.fr,
+            Language::Ja => &self.ja,
+            Language::Ko => &self.ko,
+            Language::Ru => &self.ru,
+            Language::Zh => &self.zh,
+        };
+        localized.as_ref().unwrap_or(&self.en)
+    }
+
+    /// Languages for which this string is available, always including English
+    pub fn available_languages(&self) -> Vec<Language> {
+        Language::ALL.into_iter()
+            .filter(|language| match language {
+                Language::En => true,
+                Language::De => self.de.is_some(),
+                Language::Es => self.es.is_some(),
+                Language::Fr => self.fr.is_some(),
+                Language::Ja => self.ja.is_some(),
+                Language::Ko => self.ko.is_some(),
+                Language::Ru => self.ru.is_some(),
+                Language::Zh => self.zh.is_some(),
+            })
+            .collect()
+    }
+
     /// German string if available, else English string
     pub fn try_de(&self) -> &str {
-        self.de.as_ref().unwrap_or(&self.en)
+        self.get(Language::De)
     }
 
     /// Spanish string if available, else English string
     pub fn try_es(&self) -> &str {
-        self.es.as_ref().unwrap_or(&self.en)
+        self.get(Language::Es)
     }
 
     /// French string if available, else English string
     pub fn try_fr(&self) -> &str {
-        self.fr.as_ref().unwrap_or(&self.en)
+        self.get(Language::Fr)
     }
 
     /// Japanese string if available, else English string
     pub fn try_ja(&self) -> &str {
-        self.ja.as_ref().unwrap_or(&self.en)
+        self.get(Language::Ja)
     }
 
     /// Korean string if available, else English string
     pub fn try_ko(&self) -> &str {
-        self.ko.as_ref().unwrap_or(&self.en)
+        self.get(Language::Ko)
     }
 
     /// Russian string if available, else English string
     pub fn try_ru(&self) -> &str {
-        self.ru.as_ref().unwrap_or(&self.en)
+        self.get(Language::Ru)
     }
 
     /// Chinese string if available, else English string
     pub fn try_zh(&self) -> &str {
-        self.zh.as_ref().unwrap_or(&self.en)
+        self.get(Language::Zh)
+    }
+
+    /// Drop all translations other than `language`; English is always kept
+    pub fn retain_language(&mut self, language: Language) {
+        for (variant, translation) in [
+            (Language::De, &mut self.de),
+            (Language::Es, &mut self.es),
+            (Language::Fr, &mut self.fr),
+            (Language::Ja, &mut self.ja),
+            (Language::Ko, &mut self.ko),
+            (Language::Ru, &mut self.ru),
+            (Language::Zh, &mut self.zh),
+        ] {
+            if variant != language {
+                *translation = None;
+            }
+        }
+    }
+}
+
+/// Language of a [`LocalizedString`] variant
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Language {
+    /// English
+    En,
+    /// German
+    De,
+    /// Spanish
+    Es,
+    /// French
+    Fr,
+    /// Japanese
+    Ja,
+    /// Korean
+    Ko,
+    /// Russian
+    Ru,
+    /// Chinese
+    Zh,
+}
+
+impl Language {
+    /// All languages, in the order of [`LocalizedString`]'s fields
+    pub const ALL: [Language; 8] = [Language::En, Language::De, Language::Es, Language::Fr, Language::Ja, Language::Ko, Language::Ru, Language::Zh];
+
+    /// Two-letter language code, as used in the SDE
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::En => "en",
+            Language::De => "de",
+            Language::Es => "es",
+            Language::Fr => "fr",
+            Language::Ja => "ja",
+            Language::Ko => "ko",
+            Language::Ru => "ru",
+            Language::Zh => "zh",
+        }
+    }
+}
+
+/// Recursively applies [`LocalizedString::retain_language`] to all localized strings in a value, see [`SDE_Full::retain_language`]
+trait RetainLanguage {
+    fn retain_language(&mut self, language: Language);
+}
+
+impl RetainLanguage for LocalizedString {
+    fn retain_language(&mut self, language: Language) {
+        LocalizedString::retain_language(self, language)
+    }
+}
+
+impl<T: RetainLanguage> RetainLanguage for Option<T> {
+    fn retain_language(&mut self, language: Language) {
+        if let Some(value) = self {
+            value.retain_language(language)
+        }
+    }
+}
+
+impl<T: RetainLanguage> RetainLanguage for Vec<T> {
+    fn retain_language(&mut self, language: Language) {
+        self.iter_mut().for_each(|value| value.retain_language(language))
+    }
+}
+
+impl<K, T: RetainLanguage> RetainLanguage for IndexMap<K, T> {
+    fn retain_language(&mut self, language: Language) {
+        self.values_mut().for_each(|value| value.retain_language(language))
     }
 }
 
+/// Implements [`RetainLanguage`] for SDE types, listing each field that (transitively) holds a [`LocalizedString`]
+macro_rules! impl_retain_language {
+    ($($type:ident { $($field:ident),* }),* $(,)?) => {
+        $(
+            impl RetainLanguage for $type {
+                fn retain_language(&mut self, language: Language) {
+                    $(self.$field.retain_language(language);)*
+                }
+            }
+        )*
+    };
+}
+
+impl_retain_language! {
+    Ancestry { description, name },
+    Archetype { title, description },
+    Bloodline { description, name },
+    Category { name },
+    Certificate { name, description },
+    CharacterAttribute { name },
+    CharacterTitle { name },
+    CorporationActivity { name },
+    WarfareBuff { displayName },
+    Attribute { displayName, tooltipTitle, tooltipDescription },
+    Effect { description, displayName },
+    DogmaUnit { description, displayName },
+    Dungeon { name, description, gameplayDescription },
+    EpicArc { name },
+    Faction { name, description, shortDescription },
+    FreelanceJobSchema { title, description, contributionMultiplier, progressDescription, rewardDescription, targetDescription, maxContributionsPerParticipant, maxProgressPerContribution, parameters },
+    ContributionMultiplier { title, description, unsetDescription },
+    ContributionInfo { title, description, unsetDescription },
+    JobSchemaParameter { boolean, itemDelivery, matcher },
+    JobSchemaParameterBooleanOption { title, description },
+    JobSchemaParameterBoolean { choiceLabel, description, optionFalse, optionTrue, title },
+    JobSchemaParameterItemDeliveryLocation { description, title, unsetDescription },
+    JobSchemaParameterItemDeliveryInventoryType { description, title, unsetDescription },
+    JobSchemaParameterItemDelivery { deliveryLocation, description, inventoryType, title },
+    JobSchemaParameterMatcher { description, title, unsetDescription },
+    Group { name },
+    Landmark { name, description },
+    AsteroidBelt { uniqueName },
+    Constellation { name },
+    Moon { uniqueName },
+    Planet { uniqueName },
+    Region { name, description },
+    SolarSystem { name },
+    MarketGroup { name, description },
+    MercenaryTacticalOperation { name, description },
+    MetaGroup { name, description },
+    MilitaryCampaign { title, subtitle, annotations },
+    MilitaryCampaignAnnotations { briefingGoalDescription, briefingHeader, briefingSuccessDescription, briefingSuccessHeader, briefingFailureDescription, briefingFailureHeader, briefingFinalWords, finishedCampaignEnded, finishedResolutionStateFailure, finishedFailureDescription, finishedResolutionStateSuccess, finishedSuccessDescription, mapHeader, mapSection1Paragraph, mapSection1Title, mapSection2Paragraph, mapSection2Title, mapSection3Paragraph, mapSection3Title, mapSubheader, mapTitle, presentingCharacterName, presentingCharacterSubtitle },
+    MilitaryCampaignObjective { subtitle, title, annotations },
+    MilitaryCampaignObjectiveAnnotations { restrictionTooltip, warning1, warning2 },
+    Mission { name, messages },
+    NpcCharacter { name },
+    CorporationDivision { name, description, leaderTypeName },
+    NpcCorporation { name, description },
+    PlanetSchematic { name },
+    CharacterRace { name, description },
+    ShipTreeElement { name, description },
+    ShipTreeFaction { description },
+    ShipTreeGroup { name, description },
+    SkinMaterial { displayName },
+    StationOperation { operationName, description },
+    StationService { serviceName, description },
+    TypeBonuses { skillBonuses, miscBonuses, roleBonuses },
+    TypeBonus { bonusText },
+    TypeList { displayName, displayDescription },
+    Type { name, description },
+    SDE_Full { ancestries, archetypes, bloodlines, categories, certificates, character_attributes, character_titles, corporation_activities, dbuff_collections, dogma_attributes, dogma_effects, dogma_units, dungeons, factions, freelance_job_schemas, groups, landmarks, map_asteroid_belts, map_constellations, map_moons, map_planets, map_regions, map_solarsystems, market_groups, mercenary_tactical_operations, meta_groups, military_campaigns, military_campaign_objectives, missions, npc_characters, npc_corporation_divisions, npc_corporations, planet_schematics, races, ship_tree_elements, ship_tree_factions, ship_tree_groups, skin_materials, station_operations, station_services, type_bonus, type_lists, types },
+}
+
 // SDE data types
 
 /// Agent (Mission NPC) that is located in space, rather than docked in a station
 ///
 /// Additional Agent information is contained in [`NpcCharacter`] data
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="agentsInSpace"))]
@@ -282,7 +525,7 @@ impl_map_collect!(ids::CharacterID, AgentInSpace, agentID);
 /// The different kinds of agent
 ///
 /// See <https://wiki.eveuniversity.org/Agent#Category> for information about the various kinds of Agent
-#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
 pub enum AgentType {
     NonAgent,
@@ -319,11 +562,95 @@ impl Display for AgentType {
     }
 }
 
+#[test]
+fn test_retain_language() {
+    let group = serde_json::from_str::<Group>(r#"{"_key": 25, "anchorable": false, "anchored": false, "categoryID": 6, "fittableNonSingleton": false, "name": {"en": "Frigate", "de": "Fregatte", "fr": "Frégate", "ja": "フリゲート"}, "published": true, "useBasePrice": false}"#).unwrap();
+    let mut sde = SDE_Full { groups: IndexMap::from([(25, group)]), ..SDE_Full::default() };
+    sde.retain_language(Language::Fr);
+
+    let name = &sde.groups[&25].name;
+    assert_eq!(name.available_languages(), vec![Language::En, Language::Fr]);
+    assert_eq!(name.get(Language::Fr), "Frégate");
+    assert_eq!(name.get(Language::De), "Frigate");
+}
+
+#[test]
+fn test_retain_language_all_tables() {
+    // One entry with a German translation for every table containing localized text
+    const LOCALIZED: &[(&str, &str)] = &[
+        ("ancestries.jsonl", r#"{"_key": 1, "bloodlineID": 1, "charisma": 1, "intelligence": 1, "memory": 1, "perception": 1, "willpower": 1, "description": {"en": "English", "de": "Deutsch"}, "name": {"en": "English", "de": "Deutsch"}}"#),
+        ("archetypes.jsonl", r#"{"_key": 1, "title": {"en": "English", "de": "Deutsch"}, "description": {"en": "English", "de": "Deutsch"}}"#),
+        ("bloodlines.jsonl", r#"{"_key": 1, "corporationID": 1, "description": {"en": "English", "de": "Deutsch"}, "name": {"en": "English", "de": "Deutsch"}, "raceID": 1, "charisma": 1, "intelligence": 1, "memory": 1, "perception": 1, "willpower": 1}"#),
+        ("categories.jsonl", r#"{"_key": 1, "name": {"en": "English", "de": "Deutsch"}, "published": false}"#),
+        ("certificates.jsonl", r#"{"_key": 1, "groupID": 1, "name": {"en": "English", "de": "Deutsch"}, "description": {"en": "English", "de": "Deutsch"}, "skillTypes": []}"#),
+        ("characterAttributes.jsonl", r#"{"_key": 1, "name": {"en": "English", "de": "Deutsch"}, "description": "x", "iconID": 1, "notes": "x", "shortDescription": "x"}"#),
+        ("characterTitles.jsonl", r#"{"_key": "00000000-0000-0000-0000-000000000001", "name": {"en": "English", "de": "Deutsch"}}"#),
+        ("corporationActivities.jsonl", r#"{"_key": 1, "name": {"en": "English", "de": "Deutsch"}}"#),
+        ("dbuffCollections.jsonl", r#"{"_key": 1, "aggregateMode": "Maximum", "developerDescription": "x", "displayName": {"en": "English", "de": "Deutsch"}, "operationName": "PostMul", "showOutputValueInUI": "ShowNormal"}"#),
+        ("dogmaAttributes.jsonl", r#"{"_key": 1, "dataType": 1, "defaultValue": 1, "displayName": {"en": "English", "de": "Deutsch"}, "displayWhenZero": false, "highIsGood": false, "name": "x", "published": false, "stackable": false, "tooltipTitle": {"en": "English", "de": "Deutsch"}, "tooltipDescription": {"en": "English", "de": "Deutsch"}}"#),
+        ("dogmaEffects.jsonl", r#"{"_key": 1, "effectCategoryID": 1, "name": "x", "description": {"en": "English", "de": "Deutsch"}, "disallowAutoRepeat": false, "displayName": {"en": "English", "de": "Deutsch"}, "isAssistance": false, "isOffensive": false, "isWarpSafe": false, "published": false, "electronicChance": false, "rangeChance": false, "propulsionChance": false}"#),
+        ("dogmaUnits.jsonl", r#"{"_key": 1, "name": "x", "description": {"en": "English", "de": "Deutsch"}, "displayName": {"en": "English", "de": "Deutsch"}}"#),
+        ("dungeons.jsonl", r#"{"_key": 1, "archetypeID": 1, "name": {"en": "English", "de": "Deutsch"}, "description": {"en": "English", "de": "Deutsch"}, "gameplayDescription": {"en": "English", "de": "Deutsch"}}"#),
+        ("factions.jsonl", r#"{"_key": 1, "name": {"en": "English", "de": "Deutsch"}, "description": {"en": "English", "de": "Deutsch"}, "shortDescription": {"en": "English", "de": "Deutsch"}, "iconID": 1, "memberRaces": [], "sizeFactor": 1, "solarSystemID": 1, "uniqueName": false}"#),
+        ("freelanceJobSchemas.jsonl", r#"{"_key": 1, "_value": [{"_key": "BoostShield", "title": {"en": "English", "de": "Deutsch"}, "description": {"en": "English", "de": "Deutsch"}, "contentTags": [], "contributionMultiplier": {"title": {"en": "English", "de": "Deutsch"}, "description": {"en": "English", "de": "Deutsch"}, "unsetDescription": {"en": "English", "de": "Deutsch"}, "iconID": "x", "defaultValue": 1, "maxValue": 1, "minValue": 1}, "progressDescription": {"en": "English", "de": "Deutsch"}, "rewardDescription": {"en": "English", "de": "Deutsch"}, "targetDescription": {"en": "English", "de": "Deutsch"}, "iconID": "x", "maxContributionsPerParticipant": {"title": {"en": "English", "de": "Deutsch"}, "description": {"en": "English", "de": "Deutsch"}, "unsetDescription": {"en": "English", "de": "Deutsch"}, "iconID": "x"}, "maxProgressPerContribution": {"title": {"en": "English", "de": "Deutsch"}, "description": {"en": "English", "de": "Deutsch"}, "unsetDescription": {"en": "English", "de": "Deutsch"}, "iconID": "x"}, "parameters": [{"_key": "x", "boolean": {"choiceLabel": {"en": "English", "de": "Deutsch"}, "default": false, "description": {"en": "English", "de": "Deutsch"}, "iconID": "x", "optionFalse": {"title": {"en": "English", "de": "Deutsch"}, "description": {"en": "English", "de": "Deutsch"}}, "optionTrue": {"title": {"en": "English", "de": "Deutsch"}, "description": {"en": "English", "de": "Deutsch"}}, "title": {"en": "English", "de": "Deutsch"}}}]}]}"#),
+        ("groups.jsonl", r#"{"_key": 1, "anchorable": false, "anchored": false, "categoryID": 1, "fittableNonSingleton": false, "name": {"en": "English", "de": "Deutsch"}, "published": false, "useBasePrice": false}"#),
+        ("landmarks.jsonl", r#"{"_key": 1, "name": {"en": "English", "de": "Deutsch"}, "description": {"en": "English", "de": "Deutsch"}, "position": {"x": 1, "y": 1, "z": 1}}"#),
+        ("mapAsteroidBelts.jsonl", r#"{"_key": 1, "uniqueName": {"en": "English", "de": "Deutsch"}, "orbitID": 1, "celestialIndex": 1, "orbitIndex": 1, "position": {"x": 1, "y": 1, "z": 1}, "solarSystemID": 1, "typeID": 1}"#),
+        ("mapConstellations.jsonl", r#"{"_key": 1, "regionID": 1, "position": {"x": 1, "y": 1, "z": 1}, "name": {"en": "English", "de": "Deutsch"}, "solarSystemIDs": []}"#),
+        ("mapMoons.jsonl", r#"{"_key": 1, "uniqueName": {"en": "English", "de": "Deutsch"}, "orbitID": 1, "celestialIndex": 1, "orbitIndex": 1, "position": {"x": 1, "y": 1, "z": 1}, "radius": 1, "solarSystemID": 1, "typeID": 1, "attributes": {"heightMap1": 1, "heightMap2": 1, "shaderPreset": 1}}"#),
+        ("mapPlanets.jsonl", r#"{"_key": 1, "uniqueName": {"en": "English", "de": "Deutsch"}, "attributes": {"heightMap1": 1, "heightMap2": 1, "population": false, "shaderPreset": 1}, "celestialIndex": 1, "position": {"x": 1, "y": 1, "z": 1}, "radius": 1, "solarSystemID": 1, "statistics": {"density": 1, "eccentricity": 1, "escapeVelocity": 1, "locked": false, "massDust": 1, "pressure": 1, "rotationRate": 1, "spectralClass": "x", "temperature": 1}, "typeID": 1}"#),
+        ("mapRegions.jsonl", r#"{"_key": 1, "name": {"en": "English", "de": "Deutsch"}, "description": {"en": "English", "de": "Deutsch"}, "constellationIDs": [], "nebulaID": 1, "position": {"x": 1, "y": 1, "z": 1}}"#),
+        ("mapSolarSystems.jsonl", r#"{"_key": 1, "constellationID": 1, "regionID": 1, "name": {"en": "English", "de": "Deutsch"}, "position": {"x": 1, "y": 1, "z": 1}, "radius": 1, "securityStatus": 1}"#),
+        ("marketGroups.jsonl", r#"{"_key": 1, "name": {"en": "English", "de": "Deutsch"}, "description": {"en": "English", "de": "Deutsch"}, "hasTypes": false}"#),
+        ("mercenaryTacticalOperations.jsonl", r#"{"_key": 1, "anarchyImpact": 1, "developmentImpact": 1, "infomorphBonus": 1, "dungeonID": 1, "name": {"en": "English", "de": "Deutsch"}, "description": {"en": "English", "de": "Deutsch"}}"#),
+        ("metaGroups.jsonl", r#"{"_key": 1, "name": {"en": "English", "de": "Deutsch"}, "description": {"en": "English", "de": "Deutsch"}}"#),
+        ("militaryCampaigns.jsonl", r#"{"_key": "00000000-0000-0000-0000-000000000001", "title": {"en": "English", "de": "Deutsch"}, "subtitle": {"en": "English", "de": "Deutsch"}, "issuer": {"factionID": 1}, "targetProgress": 1, "annotations": {"aoCampaignCardButtonImage": "x", "backgroundVideoLoop": "x", "briefingBackground": "x", "briefingForeground": "x", "briefingMiddleground": "x", "dashboardAmbientBackground": "x", "dashboardBackground": "x", "dashboardForeground": "x", "dashboardMiddleground": "x", "foregroundVideoIntro": "x", "foregroundVideoOutro": "x", "foregroundVideoLoop": "x", "middlegroundVideoIntro": "x", "middlegroundVideoLoop": "x", "middlegroundVideoOutro": "x", "briefingGoalDescription": {"en": "English", "de": "Deutsch"}, "briefingHeader": {"en": "English", "de": "Deutsch"}, "briefingSuccessDescription": {"en": "English", "de": "Deutsch"}, "briefingSuccessHeader": {"en": "English", "de": "Deutsch"}, "briefingFailureDescription": {"en": "English", "de": "Deutsch"}, "briefingFailureHeader": {"en": "English", "de": "Deutsch"}, "briefingFinalWords": {"en": "English", "de": "Deutsch"}, "finishedCampaignEnded": {"en": "English", "de": "Deutsch"}, "finishedResolutionStateFailure": {"en": "English", "de": "Deutsch"}, "finishedFailureDescription": {"en": "English", "de": "Deutsch"}, "finishedResolutionStateSuccess": {"en": "English", "de": "Deutsch"}, "finishedSuccessDescription": {"en": "English", "de": "Deutsch"}, "campaignSet": "x", "mapFocusEntityID": 1, "mapHeader": {"en": "English", "de": "Deutsch"}, "mapSection1Paragraph": {"en": "English", "de": "Deutsch"}, "mapSection1Title": {"en": "English", "de": "Deutsch"}, "mapSection2Paragraph": {"en": "English", "de": "Deutsch"}, "mapSection2Title": {"en": "English", "de": "Deutsch"}, "mapSection3Paragraph": {"en": "English", "de": "Deutsch"}, "mapSection3Title": {"en": "English", "de": "Deutsch"}, "mapSubheader": {"en": "English", "de": "Deutsch"}, "mapTitle": {"en": "English", "de": "Deutsch"}, "presentingCharacterName": {"en": "English", "de": "Deutsch"}, "presentingCharacterSubtitle": {"en": "English", "de": "Deutsch"}, "presentingCharacterTexturePath": "x", "race": "amarr", "themePack": "x", "towCampaignCardButtonImage": "x"}}"#),
+        ("militaryCampaignObjectives.jsonl", r#"{"_key": "00000000-0000-0000-0000-000000000001", "campaignID": "00000000-0000-0000-0000-000000000001", "careerPath": "x", "contentTags": [], "issuer": {"corporationID": 1}, "maxProgressPerParticipant": 1, "presentingCharacterID": 1, "contributionMethodConfiguration": {"name": "x", "parameters": []}, "rewards": {"isk": {"amountPerInterval": 1, "issuer": {"corporationID": 1}, "progressInterval": 1}, "lp": {"amountPerInterval": 1, "issuer": {"corporationID": 1}, "progressInterval": 1}, "standing": {"gainPercentPerInterval": 1, "issuer": {"factionID": 1}, "progressInterval": 1}}, "subtitle": {"en": "English", "de": "Deutsch"}, "title": {"en": "English", "de": "Deutsch"}, "targetProgress": 1, "annotations": {"requiredEnlistmentWithFactionID": 1, "restrictionTooltip": {"en": "English", "de": "Deutsch"}, "warning1": {"en": "English", "de": "Deutsch"}, "warning2": {"en": "English", "de": "Deutsch"}}}"#),
+        ("missions.jsonl", r#"{"_key": 1, "name": {"en": "English", "de": "Deutsch"}, "hasStandingRewards": false, "messages": [{"_key": "x", "en": "English", "de": "Deutsch"}]}"#),
+        ("npcCharacters.jsonl", r#"{"_key": 1, "name": {"en": "English", "de": "Deutsch"}, "raceID": 1, "bloodlineID": 1, "corporationID": 1, "gender": true, "ceo": false, "uniqueName": false}"#),
+        ("npcCorporationDivisions.jsonl", r#"{"_key": 1, "name": {"en": "English", "de": "Deutsch"}, "description": {"en": "English", "de": "Deutsch"}, "internalName": "x", "leaderTypeName": {"en": "English", "de": "Deutsch"}}"#),
+        ("npcCorporations.jsonl", r#"{"_key": 1, "name": {"en": "English", "de": "Deutsch"}, "tickerName": "x", "description": {"en": "English", "de": "Deutsch"}, "deleted": false, "extent": "x", "size": "x", "hasPlayerPersonnelManager": false, "initialPrice": 1, "memberLimit": 1, "minSecurity": 1, "minimumJoinStanding": 1, "shares": 1, "taxRate": 1, "uniqueName": false, "sendCharTerminationMessage": false}"#),
+        ("planetSchematics.jsonl", r#"{"_key": 1, "name": {"en": "English", "de": "Deutsch"}, "cycleTime": 1, "pins": [], "types": []}"#),
+        ("races.jsonl", r#"{"_key": 1, "name": {"en": "English", "de": "Deutsch"}, "description": {"en": "English", "de": "Deutsch"}}"#),
+        ("shipTreeElements.jsonl", r#"{"_key": 1, "name": {"en": "English", "de": "Deutsch"}, "description": {"en": "English", "de": "Deutsch"}, "icon": "x"}"#),
+        ("shipTreeFactions.jsonl", r#"{"_key": 1, "description": {"en": "English", "de": "Deutsch"}, "icon": "x", "elements": []}"#),
+        ("shipTreeGroups.jsonl", r#"{"_key": 1, "name": {"en": "English", "de": "Deutsch"}, "description": {"en": "English", "de": "Deutsch"}, "icon": "x", "iconLarge": "x", "iconSmall": "x", "iconSmallNPC": "x"}"#),
+        ("skinMaterials.jsonl", r#"{"_key": 1, "displayName": {"en": "English", "de": "Deutsch"}, "materialSetID": 1}"#),
+        ("stationOperations.jsonl", r#"{"_key": 1, "activityID": 1, "operationName": {"en": "English", "de": "Deutsch"}, "description": {"en": "English", "de": "Deutsch"}, "services": [], "border": 1, "corridor": 1, "fringe": 1, "hub": 1, "ratio": 1, "manufacturingFactor": 1, "researchFactor": 1}"#),
+        ("stationServices.jsonl", r#"{"_key": 1, "serviceName": {"en": "English", "de": "Deutsch"}, "description": {"en": "English", "de": "Deutsch"}}"#),
+        ("typeBonus.jsonl", r#"{"_key": 1, "types": [{"_key": 1, "_value": [{"importance": 1, "bonusText": {"en": "English", "de": "Deutsch"}}]}], "miscBonuses": [{"importance": 1, "bonusText": {"en": "English", "de": "Deutsch"}}], "roleBonuses": [{"importance": 1, "bonusText": {"en": "English", "de": "Deutsch"}}]}"#),
+        ("typeLists.jsonl", r#"{"_key": 1, "displayName": {"en": "English", "de": "Deutsch"}, "displayDescription": {"en": "English", "de": "Deutsch"}, "name": "x"}"#),
+        ("types.jsonl", r#"{"_key": 1, "groupID": 1, "name": {"en": "English", "de": "Deutsch"}, "description": {"en": "English", "de": "Deutsch"}, "portionSize": 1, "published": false}"#),
+    ];
+    const OTHER: &[&str] = &["agentTypes.jsonl", "agentsInSpace.jsonl", "blueprints.jsonl", "cloneGrades.jsonl", "compressibleTypes.jsonl", "contrabandTypes.jsonl", "controlTowerResources.jsonl", "dogmaAttributeCategories.jsonl", "dynamicItemAttributes.jsonl", "graphics.jsonl", "icons.jsonl", "mapSecondarySuns.jsonl", "mapStargates.jsonl", "mapStars.jsonl", "masteries.jsonl", "npcStations.jsonl", "planetResources.jsonl", "skinLicenses.jsonl", "skins.jsonl", "sovereigntyUpgrades.jsonl", "translationLanguages.jsonl", "typeDogma.jsonl", "typeMaterials.jsonl"];
+
+    let mut writer = zip::ZipWriter::new(io::Cursor::new(Vec::new()));
+    writer.start_file("_sde.jsonl", zip::write::SimpleFileOptions::default()).unwrap();
+    io::Write::write_all(&mut writer, br#"{"_key": "sde", "buildNumber": 3000000, "releaseDate": "2025-01-01T00:00:00Z"}"#).unwrap();
+    for (file, entry) in LOCALIZED {
+        writer.start_file(*file, zip::write::SimpleFileOptions::default()).unwrap();
+        io::Write::write_all(&mut writer, entry.as_bytes()).unwrap();
+    }
+    for file in OTHER {
+        writer.start_file(*file, zip::write::SimpleFileOptions::default()).unwrap();
+    }
+    let mut sde = SDELoader::new(writer.finish().unwrap()).unwrap().full().unwrap();
+
+    let loaded = serde_json::to_value(&sde).unwrap();
+    let non_empty = loaded.as_object().unwrap().values().filter(|table| table.as_object().is_some_and(|t| !t.is_empty())).count();
+    assert_eq!(non_empty, LOCALIZED.len());
+    assert!(loaded.to_string().contains("Deutsch"));
+
+    sde.retain_language(Language::En);
+    let retained = serde_json::to_string(&sde).unwrap();
+    assert!(!retained.contains("Deutsch"), "non-English text remains after retain_language: {}", retained);
+}
+
 // Helper for deserializing
 /// The different kinds of agent
 ///
 /// See <https://wiki.eveuniversity.org/Agent#Category> for information about the various kinds of Agent
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="agentTypes"))]
@@ -337,7 +664,7 @@ struct AgentTypeEntry {
 }
 
 /// Character Ancestry; Now-unused character creation element (Removed from player character creation 2021-03-02)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="ancestries"))]
@@ -370,7 +697,7 @@ pub struct Ancestry {
 impl_map_collect!(ids::AncestryID, Ancestry, ancestryID);
 
 /// Dungeon Archetype
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="archetypes"))]
@@ -387,7 +714,7 @@ pub struct Archetype {
 impl_map_collect!(ids::DungeonArchetypeID, Archetype, archetypeID);
 
 /// Character Bloodline; Character creation element
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="bloodlines"))]
@@ -423,7 +750,7 @@ impl_map_collect!(ids::BloodlineID, Bloodline, bloodlineID);
 ///
 /// Note: The SDE provides Blueprint Copy and Blueprint Original data as 'merged' into a single entry for the Blueprint's typeID.
 /// 'Copying' & 'Research Time/Material' activities are not usable with BPCs, 'Invention' activity is not usable with BPOs.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="blueprints"))]
@@ -444,7 +771,7 @@ pub struct Blueprint {
 }
 
 /// Blueprint activities for a [`Blueprint`]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -482,7 +809,7 @@ impl IntoIterator for BlueprintActivities {
 
 
 /// A single [`Blueprint`] activity
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(external_type))]
@@ -491,30 +818,68 @@ pub struct BPActivity {
     #[cfg_attr(feature="docs_export", doc_sde(alias_type="Vec<BPProduct>"))]
     #[serde(default)]
     #[serde(deserialize_with="deserialize_activity_materials")]
+    #[serde(serialize_with="serialize_activity_materials")]
     pub materials: IndexMap<ids::TypeID, u32>,
     /// Products, quantity, and optional probability for one run of this activity.
     /// Only one product type is allowed per run of this activity; When multiple types of products are available, one must be selected by the player when setting up the industry job
     #[cfg_attr(feature="docs_export", doc_sde(alias_type="Vec<BPProduct>"))]
     #[serde(default)]
     #[serde(deserialize_with="deserialize_activity_products")]
+    #[serde(serialize_with="serialize_activity_products")]
     pub products: IndexMap<ids::TypeID, (u32, Option<f64>)>,
     /// Skills required to set up a run of this activity
     #[cfg_attr(feature="docs_export", doc_sde(alias_type="Vec<BPSkill>"))]
     #[serde(default)]
     #[serde(deserialize_with="deserialize_activity_skills")]
+    #[serde(serialize_with="serialize_activity_skills")]
     pub skills: IndexMap<ids::TypeID, values::SkillLevel>,
     /// Time required for one run of this activity, in seconds
     pub time: u32
 }
 
-#[derive(Debug, Deserialize)]
+impl BPActivity {
+    /// Materials required for a job of this activity, with material efficiency applied
+    ///
+    /// Per material, the quantity is `max(runs, ceil(quantity * runs * (100 - me_percent) / 100))`, computed in integer arithmetic.
+    /// Materials with a base quantity of 1 are therefore never reduced below 1 per run.
+    ///
+    /// # Arguments
+    ///
+    /// * `runs`: Number of job runs
+    /// * `me_percent`: Material efficiency of the blueprint, 0-10 for player blueprints. Values above 100 are treated as 100
+    pub fn required_materials(&self, runs: u32, me_percent: u8) -> IndexMap<ids::TypeID, u64> {
+        let efficiency = 100 - u64::from(me_percent.min(100));
+        self.materials.iter()
+            .map(|(type_id, quantity)| {
+                let base = u64::from(*quantity) * u64::from(runs);
+                (*type_id, u64::max(u64::from(runs), (base * efficiency).div_ceil(100)))
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test_required_materials() {
+    let activity = BPActivity {
+        materials: IndexMap::from([(34, 1000), (35, 1), (36, 7)]),
+        products: IndexMap::new(),
+        skills: IndexMap::new(),
+        time: 600,
+    };
+
+    assert_eq!(activity.required_materials(1, 0), IndexMap::from([(34, 1000), (35, 1), (36, 7)]));
+    assert_eq!(activity.required_materials(1, 10), IndexMap::from([(34, 900), (35, 1), (36, 7)]));
+    assert_eq!(activity.required_materials(10, 10), IndexMap::from([(34, 9000), (35, 10), (36, 63)]));
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
 struct BPMaterial {
     typeID: ids::TypeID,
     quantity: u32
 }
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
 struct BPProduct {
@@ -522,7 +887,7 @@ struct BPProduct {
     quantity: u32,
     probability: Option<f64>
 }
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
 pub struct BPSkill {
@@ -551,6 +916,10 @@ fn deserialize_activity_materials<'de, D: Deserializer<'de>>(deserializer: D) ->
 
     deserializer.deserialize_seq(MaterialVisitor)
 }
+
+fn serialize_activity_materials<S: Serializer>(materials: &IndexMap<ids::TypeID, u32>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(materials.iter().map(|(type_id, quantity)| BPMaterial { typeID: *type_id, quantity: *quantity }))
+}
 fn deserialize_activity_products<'de, D: Deserializer<'de>>(deserializer: D) -> Result<IndexMap<ids::TypeID, (u32, Option<f64>)>, D::Error> {
     pub struct ProductVisitor;
     impl<'de> Visitor<'de> for ProductVisitor {
@@ -572,6 +941,10 @@ fn deserialize_activity_products<'de, D: Deserializer<'de>>(deserializer: D) ->
 
     deserializer.deserialize_seq(ProductVisitor)
 }
+
+fn serialize_activity_products<S: Serializer>(products: &IndexMap<ids::TypeID, (u32, Option<f64>)>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(products.iter().map(|(type_id, (quantity, probability))| BPProduct { typeID: *type_id, quantity: *quantity, probability: *probability }))
+}
 fn deserialize_activity_skills<'de, D: Deserializer<'de>>(deserializer: D) -> Result<IndexMap<ids::TypeID, values::SkillLevel>, D::Error> {
     pub struct SkillVisitor;
     impl<'de> Visitor<'de> for SkillVisitor {
@@ -594,11 +967,15 @@ fn deserialize_activity_skills<'de, D: Deserializer<'de>>(deserializer: D) -> Re
     deserializer.deserialize_seq(SkillVisitor)
 }
 
+fn serialize_activity_skills<S: Serializer>(skills: &IndexMap<ids::TypeID, values::SkillLevel>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(skills.iter().map(|(type_id, level)| BPSkill { typeID: *type_id, level: *level }))
+}
+
 impl_map_collect!(ids::TypeID, Blueprint, blueprintTypeID);
 
 
 /// Item Type 'Category'; Collection of [Groups](Group)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="categories"))]
@@ -618,7 +995,7 @@ impl_map_collect!(ids::CategoryID, Category, categoryID);
 
 
 /// Ship Mastery Certificate
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="certificates"))]
@@ -638,11 +1015,12 @@ pub struct Certificate {
     /// Skill levels for this certificate
     #[serde(rename="skillTypes")]
     #[serde(deserialize_with="deserialize_inline_entry_map")]
+    #[serde(serialize_with="serialize_inline_entry_map")]
     pub skillLevels: IndexMap<ids::TypeID, CertificateSkillLevels>
 }
 
 /// Skill levels required for a certificate level
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
 pub struct CertificateSkillLevels {
@@ -667,10 +1045,76 @@ impl InlineEntry<ids::TypeID> for CertificateSkillLevels {
     }
 }
 
+impl CertificateSkillLevels {
+    /// Skill level required for the specified certificate level
+    pub fn required(&self, level: CertificateLevel) -> values::SkillLevel {
+        match level {
+            CertificateLevel::Basic => self.basic,
+            CertificateLevel::Standard => self.standard,
+            CertificateLevel::Improved => self.improved,
+            CertificateLevel::Advanced => self.advanced,
+            CertificateLevel::Elite => self.elite,
+        }
+    }
+}
+
+/// Certificate level, ordered from lowest to highest
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum CertificateLevel {
+    Basic,
+    Standard,
+    Improved,
+    Advanced,
+    Elite
+}
+
+impl CertificateLevel {
+    /// All certificate levels, from lowest to highest
+    pub const ALL: [CertificateLevel; 5] = [CertificateLevel::Basic, CertificateLevel::Standard, CertificateLevel::Improved, CertificateLevel::Advanced, CertificateLevel::Elite];
+}
+
+impl Certificate {
+    /// Whether the specified trained skills meet a level of this certificate
+    ///
+    /// `skills` maps skill TypeIDs to trained level, skills not present are considered untrained
+    pub fn is_met(&self, level: CertificateLevel, skills: &HashMap<ids::TypeID, values::SkillLevel>) -> bool {
+        self.skillLevels.values()
+            .all(|levels| skills.get(&levels.skillTypeID).copied().unwrap_or(0) >= levels.required(level))
+    }
+
+    /// Highest level of this certificate met by the specified trained skills, see [`Certificate::is_met`]
+    ///
+    /// returns: Highest level met, or None if not even the basic level is met
+    pub fn highest_level(&self, skills: &HashMap<ids::TypeID, values::SkillLevel>) -> Option<CertificateLevel> {
+        CertificateLevel::ALL.into_iter()
+            .take_while(|level| self.is_met(*level, skills))
+            .last()
+    }
+}
+
 impl_map_collect!(ids::CertificateID, Certificate, certificateID);
 
+#[test]
+fn test_certificate_levels() {
+    let certificate = serde_json::from_str::<Certificate>(r#"{"_key": 1, "groupID": 255, "name": {"en": "Gunnery"}, "description": {"en": ""}, "skillTypes": [
+        {"_key": 3300, "basic": 1, "standard": 2, "improved": 3, "advanced": 4, "elite": 5},
+        {"_key": 3301, "basic": 0, "standard": 0, "improved": 1, "advanced": 3, "elite": 5}
+    ]}"#).unwrap();
+    let masteries = IndexMap::from([(587, MasteryInfo { lvl1: vec![1], lvl2: vec![1], lvl3: vec![1], lvl4: vec![1, 2], lvl5: vec![1, 2] })]);
+    let sde = SDE_Full { certificates: IndexMap::from([(1, certificate)]), masteries, ..SDE_Full::default() };
+    let certificate = &sde.certificates[&1];
+
+    assert_eq!(certificate.highest_level(&HashMap::new()), None);
+    assert_eq!(certificate.highest_level(&HashMap::from([(3300, 2)])), Some(CertificateLevel::Standard));
+    assert_eq!(certificate.highest_level(&HashMap::from([(3300, 5), (3301, 3)])), Some(CertificateLevel::Advanced));
+    assert_eq!(sde.ship_mastery_level(587, &HashMap::from([(3300, 2)])), 2);
+    // Level 4 requires unknown certificate 2
+    assert_eq!(sde.ship_mastery_level(587, &HashMap::from([(3300, 5), (3301, 5)])), 3);
+    assert_eq!(sde.ship_mastery_level(588, &HashMap::from([(3300, 5)])), 0);
+}
+
 /// Character skill training Attribute
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="characterAttributes"))]
@@ -693,7 +1137,7 @@ pub struct CharacterAttribute {
 impl_map_collect!(ids::CharacterAttributeID, CharacterAttribute, characterAttributeID);
 
 /// Character skill training Attribute
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="characterAttributes"))]
@@ -709,7 +1153,7 @@ impl_map_collect!(uuids::CharacterTitleID, CharacterTitle, characterTitleID);
 
 /// Information about Alpha clones
 /// Currently there is one entry for each of the 4 races' Alpha Clones, but the entries are the same; Each character race is allowed to train the same skills, including ships of the other races
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="cloneGrades"))]
@@ -721,11 +1165,12 @@ pub struct CloneGrade {
     pub name: String,
     /// Skills that may be trained by this clone grade
     #[serde(deserialize_with="deserialize_clonegrade_skills")]
+    #[serde(serialize_with="serialize_clonegrade_skills")]
     #[cfg_attr(feature="docs_export", doc_sde(alias_type="Vec<CloneSkill>"))]
     pub skills: IndexMap<ids::TypeID, values::SkillLevel>
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
 struct CloneSkill {
@@ -756,13 +1201,17 @@ fn deserialize_clonegrade_skills<'de, D: Deserializer<'de>>(deserializer: D) ->
     deserializer.deserialize_seq(SkillVisitor)
 }
 
+fn serialize_clonegrade_skills<S: Serializer>(skills: &IndexMap<ids::TypeID, values::SkillLevel>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(skills.iter().map(|(type_id, level)| CloneSkill { typeID: *type_id, level: *level }))
+}
+
 impl_map_collect!(ids::CloneGradeID, CloneGrade, cloneGradeID);
 
 /// Information about ore/gas/ice compression
 /// Ore compression has a 1:1 input output ratio, N units of 'oreTypeID' yield N units of 'compressedTypeID'
 /// Volume ratio is provided by `compressedType.volume / oreType.volume`
 /// For ore and ice, compression is lossless. Gas must be decompressed before use, where some losses are had. (Depending on skills & facility used)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="compressibleTypes"))]
@@ -777,7 +1226,7 @@ pub struct CompressibleType {
 impl_map_collect!(ids::TypeID, ids::TypeID, CompressibleType, fn |c| (c.oreTypeID, c.compressedTypeID));
 
 /// Contraband status information for a [`Type`]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="contrabandTypes"))]
@@ -787,11 +1236,12 @@ pub struct ContrabandType {
     pub typeID: ids::TypeID,
     /// Per-faction contraband info; An entry means the Type is contraband in the given faction
     #[serde(deserialize_with="deserialize_inline_entry_map")]
+    #[serde(serialize_with="serialize_inline_entry_map")]
     pub factions: IndexMap<ids::FactionID, ContrabandFactionInfo>
 }
 
 /// Per-faction Contraband information
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -820,7 +1270,7 @@ impl InlineEntry<ids::FactionID> for ContrabandFactionInfo {
 impl_map_collect!(ids::TypeID, ContrabandType, typeID);
 
 /// Resources required for Player-owned-Starbase Control Tower operation
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="controlTowerResources"))]
@@ -832,8 +1282,55 @@ pub struct ControlTowerResources {
     pub resources: Vec<ControlTowerResourceInfo>
 }
 
+impl ControlTowerResources {
+    /// Resources required to keep this Control Tower online
+    ///
+    /// # Arguments
+    ///
+    /// * `hours`: Hours of operation
+    /// * `security`: Security level of the solarsystem the tower is anchored in
+    /// * `faction`: Faction whose space the tower is anchored in, if any
+    ///
+    /// returns: Map of resource TypeID to quantity required
+    pub fn online_fuel(&self, hours: u32, security: f64, faction: Option<ids::FactionID>) -> IndexMap<ids::TypeID, u64> {
+        self.fuel(ResourcePurpose::Online, hours, security, faction)
+    }
+
+    /// Resources required to keep this Control Tower reinforced, see [`ControlTowerResources::online_fuel`]
+    pub fn reinforce_fuel(&self, hours: u32, security: f64, faction: Option<ids::FactionID>) -> IndexMap<ids::TypeID, u64> {
+        self.fuel(ResourcePurpose::Reinforce, hours, security, faction)
+    }
+
+    fn fuel(&self, purpose: ResourcePurpose, hours: u32, security: f64, faction: Option<ids::FactionID>) -> IndexMap<ids::TypeID, u64> {
+        let mut fuel = IndexMap::new();
+        for resource in &self.resources {
+            if resource.purpose == purpose
+                && resource.factionID.is_none_or(|faction_id| faction == Some(faction_id))
+                && resource.minSecurityLevel.is_none_or(|min_security| security >= min_security) {
+                *fuel.entry(resource.resourceTypeID).or_insert(0) += u64::from(resource.quantity) * u64::from(hours);
+            }
+        }
+        fuel
+    }
+}
+
+#[test]
+fn test_control_tower_fuel() {
+    let tower = serde_json::from_str::<ControlTowerResources>(r#"{"_key": 12235, "resources": [
+        {"purpose": 1, "quantity": 40, "resourceTypeID": 4051},
+        {"purpose": 1, "quantity": 1, "resourceTypeID": 24592, "factionID": 500003, "minSecurityLevel": 0.4},
+        {"purpose": 1, "quantity": 1, "resourceTypeID": 24593, "factionID": 500001, "minSecurityLevel": 0.4},
+        {"purpose": 4, "quantity": 400, "resourceTypeID": 16275}
+    ]}"#).unwrap();
+
+    assert_eq!(tower.online_fuel(24, 0.9, Some(500003)), IndexMap::from([(4051, 960), (24592, 24)]));
+    assert_eq!(tower.online_fuel(24, 0.3, Some(500003)), IndexMap::from([(4051, 960)]));
+    assert_eq!(tower.online_fuel(24, -0.5, None), IndexMap::from([(4051, 960)]));
+    assert_eq!(tower.reinforce_fuel(2, 0.9, Some(500003)), IndexMap::from([(16275, 800)]));
+}
+
 /// Resources required for Player-owned-Starbase Control Tower operation
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -884,7 +1381,7 @@ impl Display for ResourcePurpose {
 impl_map_collect!(ids::TypeID, ControlTowerResources, towerTypeID);
 
 /// NPC Station Activity/"Specialization"
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="corporationActivities"))]
@@ -899,7 +1396,7 @@ pub struct CorporationActivity {
 impl_map_collect!(ids::CorporationActivityID, CorporationActivity, corporationActivityID);
 
 /// 'Warefare Buff'; Command Burst bonus effects
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="dbuffCollections"))]
@@ -916,6 +1413,7 @@ pub struct WarfareBuff {
     /// Attributes whose effects are applied as Item Modifiers
     #[serde(default)]
     #[serde(deserialize_with="deserialize_warfarebuff_item_modifiers")]
+    #[serde(serialize_with="serialize_warfarebuff_item_modifiers")]
     pub itemModifiers: Vec<ids::AttributeID>,
     /// Attributes whose effects are applied as Location Group Modifiers
     #[serde(default)]
@@ -923,6 +1421,7 @@ pub struct WarfareBuff {
     /// Attributes whose effects are applied as Location Modifiers
     #[serde(default)]
     #[serde(deserialize_with="deserialize_warfarebuff_location_modifiers")]
+    #[serde(serialize_with="serialize_warfarebuff_location_modifiers")]
     pub locationModifiers: Vec<ids::AttributeID>,
     /// Attributes whose effects are applied as Location with-required-skill Modifiers
     #[serde(default)]
@@ -962,6 +1461,16 @@ fn deserialize_warfarebuff_item_modifiers<'de, D: Deserializer<'de>>(deserialize
     deserializer.deserialize_seq(SeqVisitor)
 }
 
+fn serialize_warfarebuff_item_modifiers<S: Serializer>(attributes: &[ids::AttributeID], serializer: S) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    #[allow(non_snake_case)]
+    struct WarfareBuffItemModifier {
+        dogmaAttributeID: ids::AttributeID
+    }
+
+    serializer.collect_seq(attributes.iter().map(|attribute_id| WarfareBuffItemModifier { dogmaAttributeID: *attribute_id }))
+}
+
 fn deserialize_warfarebuff_location_modifiers<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<ids::AttributeID>, D::Error> {
     struct SeqVisitor;
     impl<'de> Visitor<'de> for SeqVisitor {
@@ -991,8 +1500,18 @@ fn deserialize_warfarebuff_location_modifiers<'de, D: Deserializer<'de>>(deseria
     deserializer.deserialize_seq(SeqVisitor)
 }
 
+fn serialize_warfarebuff_location_modifiers<S: Serializer>(attributes: &[ids::AttributeID], serializer: S) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    #[allow(non_snake_case)]
+    struct WarfareBuffLocationModifier {
+        dogmaAttributeID: ids::AttributeID
+    }
+
+    serializer.collect_seq(attributes.iter().map(|attribute_id| WarfareBuffLocationModifier { dogmaAttributeID: *attribute_id }))
+}
+
 /// Aggregate mode for warfare buff effect stacking
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -1006,7 +1525,7 @@ pub enum WarfareBuffAggregateMode {
 /// Dogma operation for warfare buff
 ///
 /// Subject to change
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -1016,7 +1535,7 @@ pub enum WarfareBuffOperation {
 }
 
 /// Warfare buff display mode
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -1030,7 +1549,7 @@ pub enum WarfareBuffUIMode {
 }
 
 /// Attribute whose effects are applied as Location Group Modifier
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -1042,7 +1561,7 @@ pub struct WarfareBuffLocationGroupModifier {
 }
 
 /// Attributes whose effects are applied as Location with-required-skill Modifiers
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -1057,7 +1576,7 @@ impl_map_collect!(ids::WarfareBuffID, WarfareBuff, warfareBuffID);
 
 
 /// Attribute Category, grouping of [`Attribute`]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="dogmaAttributeCategories"))]
@@ -1074,7 +1593,7 @@ pub struct AttributeCategory {
 impl_map_collect!(ids::AttributeCategoryID, AttributeCategory, attributeCategoryID);
 
 /// Dogma Attribute, describing properties for [`Type`]s. Such as HP, maximum velocity, and other item stats
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="dogmaAttributes"))]
@@ -1108,7 +1627,7 @@ pub struct Attribute {
     pub name: String,
     /// 'Published' status; If false, not visible to players in the game client
     pub published: bool,
-    /// If false, this attribute is subject to stacking penalties
+    /// If false, this attribute is subject to stacking penalties, see [`apply_stacking`]
     pub stackable: bool,
     /// Tooltip tile, as displayed when hovering over an attribute in-game
     pub tooltipTitle: Option<LocalizedString>,
@@ -1121,8 +1640,51 @@ pub struct Attribute {
 
 impl_map_collect!(ids::AttributeID, Attribute, attributeID);
 
+/// Stacking penalty of the `index`-th (zero-based) strongest modifier applied to a non-stackable attribute
+///
+/// The first modifier has full effect, the second 86.9%, the third 57.1%, the fourth 28.3%, etc.
+pub fn stacking_penalty(index: usize) -> f64 {
+    f64::exp(-(index as f64 / 2.67).powi(2))
+}
+
+/// Applies multiplier modifiers (e.g. `1.1` for +10%) to a base value, with stacking penalties
+///
+/// Bonuses and penalties (modifiers below `1.0`) are stacking-penalized separately, with the strongest modifiers applied first
+///
+/// # Arguments
+///
+/// * `base`: Unmodified attribute value
+/// * `modifiers`: Multipliers applied to the attribute
+///
+/// returns: Modified attribute value
+pub fn apply_stacking(base: f64, modifiers: &[f64]) -> f64 {
+    let mut bonuses = modifiers.iter().copied().filter(|modifier| *modifier > 1.0).collect::<Vec<_>>();
+    let mut penalties = modifiers.iter().copied().filter(|modifier| *modifier < 1.0).collect::<Vec<_>>();
+    bonuses.sort_by(|a, b| b.total_cmp(a));
+    penalties.sort_by(f64::total_cmp);
+
+    bonuses.into_iter().enumerate()
+        .chain(penalties.into_iter().enumerate())
+        .fold(base, |value, (index, modifier)| value * (1.0 + (modifier - 1.0) * stacking_penalty(index)))
+}
+
+#[test]
+fn test_apply_stacking() {
+    let penalties = [1.0, 0.869, 0.571, 0.283, 0.106, 0.030];
+    for (index, expected) in penalties.into_iter().enumerate() {
+        assert!((stacking_penalty(index) - expected).abs() < 0.001, "penalty {}: {}", index, stacking_penalty(index));
+    }
+
+    assert_eq!(apply_stacking(100.0, &[]), 100.0);
+    assert!((apply_stacking(100.0, &[1.1, 1.1]) - 100.0 * 1.1 * (1.0 + 0.1 * stacking_penalty(1))).abs() < 1e-9);
+    // Strongest modifier is applied unpenalized, regardless of order
+    assert!((apply_stacking(100.0, &[1.05, 1.2]) - 100.0 * 1.2 * (1.0 + 0.05 * stacking_penalty(1))).abs() < 1e-9);
+    // Bonuses and penalties are penalized separately
+    assert!((apply_stacking(100.0, &[1.5, 0.5]) - 75.0).abs() < 1e-9);
+}
+
 /// Dogma Effect, describing interactions of [`Attribute`]s
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="dogmaEffects"))]
@@ -1187,7 +1749,7 @@ pub struct Effect {
 
 /// Dogma effect "modifier", describing mathematical operations of an effect
 ///
-/// Unintuitive dogma wizardry. Not documented here.
+/// Unintuitive dogma wizardry. See [`ModifierInfo::resolve`] for an interpreted form
 #[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
@@ -1203,11 +1765,115 @@ pub struct ModifierInfo {
     pub skillTypeID: Option<ids::TypeID>
 }
 
+impl ModifierInfo {
+    /// Interprets this modifier's `func` and `domain` strings, see [`ResolvedModifier`]
+    ///
+    /// Known `func` values are:
+    /// * `ItemModifier`: Modifies the item in `domain`
+    /// * `LocationModifier`: Modifies all items located in (e.g. fitted to) the item in `domain`
+    /// * `LocationGroupModifier`: As `LocationModifier`, limited to items of `groupID`
+    /// * `LocationRequiredSkillModifier`: As `LocationModifier`, limited to items requiring the skill `skillTypeID`
+    /// * `OwnerRequiredSkillModifier`: Modifies all items owned by the character in `domain` requiring the skill `skillTypeID`, e.g. drones and charges
+    /// * `EffectStopper`: Prevents `effectID` from being applied, not an attribute modifier
+    ///
+    /// returns: Resolved modifier, or None for `EffectStopper`, unknown `func` or `domain` values, or missing fields required by `func`
+    pub fn resolve(&self) -> Option<ResolvedModifier> {
+        let domain = ModifierDomain::from_name(&self.domain)?;
+        let operation = self.operation?;
+        let modified_attribute = self.modifiedAttributeID?;
+        let modifying_attribute = self.modifyingAttributeID?;
+        Some(match &*self.func {
+            "ItemModifier" => ResolvedModifier::ItemModifier { domain, operation, modified_attribute, modifying_attribute },
+            "LocationModifier" => ResolvedModifier::LocationModifier { domain, operation, modified_attribute, modifying_attribute },
+            "LocationGroupModifier" => ResolvedModifier::LocationGroupModifier { domain, operation, modified_attribute, modifying_attribute, group_id: self.groupID? },
+            "LocationRequiredSkillModifier" => ResolvedModifier::LocationRequiredSkillModifier { domain, operation, modified_attribute, modifying_attribute, skill_type_id: self.skillTypeID? },
+            "OwnerRequiredSkillModifier" => ResolvedModifier::OwnerRequiredSkillModifier { domain, operation, modified_attribute, modifying_attribute, skill_type_id: self.skillTypeID? },
+            _ => return None
+        })
+    }
+}
+
+/// Item a dogma modifier is applied relative to, see [`ModifierInfo::domain`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ModifierDomain {
+    /// `itemID`; The item the effect belongs to
+    Item,
+    /// `shipID`; The ship the item is fitted to
+    Ship,
+    /// `charID`; The character owning the item
+    Character,
+    /// `otherID`; The item's "other" item, e.g. the charge loaded in a module, or the module a charge is loaded into
+    Other,
+    /// `structureID`; The structure the item is fitted to
+    Structure,
+    /// `targetID`; The item's target
+    Target,
+}
+
+impl ModifierDomain {
+    /// Parses a domain as named in [`ModifierInfo::domain`]
+    pub fn from_name(name: &str) -> Option<ModifierDomain> {
+        match name {
+            "itemID" => Some(ModifierDomain::Item),
+            "shipID" => Some(ModifierDomain::Ship),
+            "charID" => Some(ModifierDomain::Character),
+            "otherID" => Some(ModifierDomain::Other),
+            "structureID" => Some(ModifierDomain::Structure),
+            "targetID" | "target" => Some(ModifierDomain::Target),
+            _ => None
+        }
+    }
+}
+
+/// Dogma attribute modifier, interpreted from a [`ModifierInfo`]
+///
+/// In each variant, `modifying_attribute` of the effect's item is applied to `modified_attribute` of the affected items with `operation`.
+/// Known operations are: `-1` pre-assign, `0` pre-multiply, `1` pre-divide, `2` add, `3` subtract, `4` post-multiply, `5` post-divide, `6` post-percent, `7` post-assign
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ResolvedModifier {
+    /// Modifies the item in `domain`
+    ItemModifier { domain: ModifierDomain, operation: i32, modified_attribute: ids::AttributeID, modifying_attribute: ids::AttributeID },
+    /// Modifies all items located in the item in `domain`
+    LocationModifier { domain: ModifierDomain, operation: i32, modified_attribute: ids::AttributeID, modifying_attribute: ids::AttributeID },
+    /// Modifies all items of `group_id` located in the item in `domain`
+    LocationGroupModifier { domain: ModifierDomain, operation: i32, modified_attribute: ids::AttributeID, modifying_attribute: ids::AttributeID, group_id: ids::GroupID },
+    /// Modifies all items requiring the skill `skill_type_id` located in the item in `domain`
+    LocationRequiredSkillModifier { domain: ModifierDomain, operation: i32, modified_attribute: ids::AttributeID, modifying_attribute: ids::AttributeID, skill_type_id: ids::TypeID },
+    /// Modifies all items requiring the skill `skill_type_id` owned by the character in `domain`
+    OwnerRequiredSkillModifier { domain: ModifierDomain, operation: i32, modified_attribute: ids::AttributeID, modifying_attribute: ids::AttributeID, skill_type_id: ids::TypeID },
+}
+
+#[test]
+fn test_resolve_modifier() {
+    let modifier = |domain: &str, func: &str, group_id: Option<ids::GroupID>, skill_type_id: Option<ids::TypeID>| ModifierInfo {
+        domain: domain.to_string(),
+        func: func.to_string(),
+        operation: Some(6),
+        modifiedAttributeID: Some(64),
+        modifyingAttributeID: Some(292),
+        groupID: group_id,
+        effectID: None,
+        skillTypeID: skill_type_id,
+    };
+
+    assert_eq!(
+        modifier("shipID", "LocationGroupModifier", Some(55), None).resolve(),
+        Some(ResolvedModifier::LocationGroupModifier { domain: ModifierDomain::Ship, operation: 6, modified_attribute: 64, modifying_attribute: 292, group_id: 55 })
+    );
+    assert_eq!(
+        modifier("charID", "OwnerRequiredSkillModifier", None, Some(3300)).resolve(),
+        Some(ResolvedModifier::OwnerRequiredSkillModifier { domain: ModifierDomain::Character, operation: 6, modified_attribute: 64, modifying_attribute: 292, skill_type_id: 3300 })
+    );
+    assert_eq!(modifier("shipID", "LocationGroupModifier", None, None).resolve(), None);
+    assert_eq!(modifier("unknownID", "ItemModifier", None, None).resolve(), None);
+    assert_eq!(modifier("itemID", "EffectStopper", None, None).resolve(), None);
+}
+
 impl_map_collect!(ids::EffectID, Effect, effectID);
 
 /// Unit of measurement used in EVE Online, see [`EVEUnit`] for details
 /// For formatting values with units, use [`EVEUnit::format`]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="dogmaUnits"))]
@@ -1228,7 +1894,7 @@ pub struct DogmaUnit {
 impl_map_collect!(EVEUnit, DogmaUnit, unitID);
 
 /// Dungeon, Mission/Anomaly instance
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="dungeons"))]
@@ -1254,7 +1920,7 @@ impl_map_collect!(ids::DungeonID, Dungeon, dungeonID);
 
 
 /// Dynamic attributes for a [`Type`], used for Mutaplasmids.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="dynamicItemAttributes"))]
@@ -1266,13 +1932,14 @@ pub struct DynamicItemAttributes {
     ///
     /// Upon application of the mutaplasmid, a random roll is made between `max` and `min` to generate the value multiplier
     #[serde(deserialize_with="deserialize_inline_entry_map")]
+    #[serde(serialize_with="serialize_inline_entry_map")]
     pub attributeIDs: IndexMap<ids::AttributeID, DynamicAttributeInfo>,
     /// "IOMapping"; Describes which types the mutaplasmid can be applied to, and the resulting output type.
     pub inputOutputMapping: Vec<DynamicItemAttributesIOMapping>
 }
 
 /// Info about a single dynamic attribute
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -1299,7 +1966,7 @@ impl InlineEntry<ids::AttributeID> for DynamicAttributeInfo {
 /// Mutaplasmid IOMapping
 ///
 /// Describes which types the mutaplasmid can be applied to, and the resulting output type.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -1315,7 +1982,7 @@ impl_map_collect!(ids::TypeID, DynamicItemAttributes, mutaplasmidTypeID);
 /// Epic Arc
 ///
 /// Replayable Mission "arc"/storyline
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="epicArcs"))]
@@ -1340,7 +2007,7 @@ pub struct EpicArc {
 impl_map_collect!(ids::EpicArcID, EpicArc, epicArcID);
 
 /// Epic Arc specific information for a [`Mission`]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -1362,7 +2029,7 @@ pub struct EpicArcMission {
 /// The major and minor NPC factions
 ///
 /// e.g. Caldari/Minmatar/Amarr/Gallente but also CONCORD, ORE, and SOE
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="factions"))]
@@ -1398,7 +2065,7 @@ pub struct Faction {
 
 impl_map_collect!(ids::FactionID, Faction, factionID);
 /// Freelance job schema, describes the possible kinds of freelance job
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="freelanceJobSchemas"))]
@@ -1432,11 +2099,12 @@ pub struct FreelanceJobSchema {
     pub maxProgressPerContribution: Option<ContributionInfo>,
     /// Parameters (other options)
     #[serde(deserialize_with="deserialize_inline_entry_map")]
+    #[serde(serialize_with="serialize_inline_entry_map")]
     pub parameters: IndexMap<String, JobSchemaParameter>
 }
 
 /// Job type for a freelance AIR opportunities job
-#[derive(Debug, Deserialize, Eq, PartialEq, Hash, Copy, Clone)]
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Hash, Copy, Clone)]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
 pub enum JobType {
     /// Boost another (specified) player's shield HP
@@ -1466,7 +2134,7 @@ pub enum JobType {
 /// Multiplier of contribution payout
 ///
 /// Used for 'insurance' job type to determine % of ship value that will be reimbursed
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -1490,7 +2158,7 @@ pub struct ContributionMultiplier {
 }
 
 /// Standard contribution option
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -1508,7 +2176,7 @@ pub struct ContributionInfo {
 }
 
 /// Parameters (other options)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -1535,7 +2203,7 @@ impl InlineEntry<String> for JobSchemaParameter {
 }
 
 /// Title and description for a boolean job schema parameter
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -1545,7 +2213,7 @@ pub struct JobSchemaParameterBooleanOption {
 }
 
 /// Boolean-type parameter
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -1559,7 +2227,7 @@ pub struct JobSchemaParameterBoolean {
     pub title: LocalizedString
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -1572,7 +2240,7 @@ pub struct JobSchemaParameterItemDeliveryLocation {
     pub unsetDescription: LocalizedString
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -1585,7 +2253,7 @@ pub struct JobSchemaParameterItemDeliveryInventoryType {
 }
 
 /// Delivery-type parameter
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -1600,7 +2268,7 @@ pub struct JobSchemaParameterItemDelivery {
 /// Matcher-type parameter
 ///
 /// Restricts in which places
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -1617,7 +2285,7 @@ pub struct JobSchemaParameterMatcher {
 }
 
 /// 3D Graphics information, such as metadata for models+textures
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="graphics"))]
@@ -1642,13 +2310,66 @@ pub struct Graphic {
     pub sofRaceName: Option<String>,
 }
 
+impl Graphic {
+    /// "Ship Object Factory" (SOF) description of this graphic, for graphics assembled from a SOF hull rather than a single file
+    ///
+    /// returns: None if this graphic has a [`Graphic::graphicFile`], or is missing any of the hull, faction, or race name
+    pub fn sof_descriptor(&self) -> Option<SofDescriptor<'_>> {
+        if self.graphicFile.is_some() {
+            return None;
+        }
+        Some(SofDescriptor {
+            hull: self.sofHullName.as_deref()?,
+            faction: self.sofFactionName.as_deref()?,
+            race: self.sofRaceName.as_deref()?,
+            layout: &self.sofLayout,
+            material_set: self.sofMaterialSetID,
+        })
+    }
+}
+
 impl_map_collect!(ids::GraphicID, Graphic, graphicID);
 
+/// Ship Object Factory (SOF) fields of a [`Graphic`], see [`Graphic::sof_descriptor`]
+///
+/// The SOF assembles a model from a hull (model geometry), faction (base colours and materials), and race (effect colours, such as thrusters)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SofDescriptor<'a> {
+    /// Hull name, determines model
+    pub hull: &'a str,
+    /// Faction name, determines base colour
+    pub faction: &'a str,
+    /// Race name, determines effect colours
+    pub race: &'a str,
+    /// Additional layout names
+    pub layout: &'a [String],
+    /// Material set overriding the faction's materials
+    pub material_set: Option<ids::MaterialSetID>,
+}
+
+impl SofDescriptor<'_> {
+    /// SOF "DNA" string, `hull:faction:race`, as used by the game client and 3D viewers to identify a model
+    pub fn dna(&self) -> String {
+        format!("{}:{}:{}", self.hull, self.faction, self.race)
+    }
+}
+
+#[test]
+fn test_sof_descriptor() {
+    let graphic = serde_json::from_str::<Graphic>(r#"{"_key": 38, "iconFolder": "res:/dx9/model/ship/amarr/frigate/af1/icons", "sofFactionName": "amarrbase", "sofHullName": "af1_t1", "sofRaceName": "amarr"}"#).unwrap();
+    let descriptor = graphic.sof_descriptor().unwrap();
+    assert_eq!(descriptor.dna(), "af1_t1:amarrbase:amarr");
+    assert!(descriptor.layout.is_empty());
+
+    let graphic = serde_json::from_str::<Graphic>(r#"{"_key": 10, "graphicFile": "res:/dx9/model/celestial/environment/sun/sun_yellow_01a.red"}"#).unwrap();
+    assert_eq!(graphic.sof_descriptor(), None);
+}
+
 
 /// Item-type Group
 ///
 /// Each [`Type`] is part of a parent Group
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="groups"))]
@@ -1678,7 +2399,7 @@ impl_map_collect!(ids::GroupID, Group, groupID);
 
 
 /// Icon and images
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="icons"))]
@@ -1694,7 +2415,7 @@ impl_map_collect!(ids::IconID, Icon, iconID);
 
 
 /// Landmark in the game world
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="landmarks"))]
@@ -1718,7 +2439,7 @@ impl_map_collect!(ids::LandmarkID, Landmark, landmarkID);
 
 
 /// Asteroid belt
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="mapAsteroidBelts"))]
@@ -1773,7 +2494,7 @@ impl AsteroidBelt {
 }
 
 /// Additional celestial information for an asteroidbelt
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -1796,7 +2517,7 @@ impl_map_collect!(ids::AsteroidBeltID, AsteroidBelt, asteroidBeltID);
 
 
 /// Constellation of solarsystems
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="mapConstellations"))]
@@ -1827,7 +2548,7 @@ pub struct Constellation {
 impl_map_collect!(ids::ConstellationID, Constellation, constellationID);
 
 /// Moon
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="mapMoons"))]
@@ -1885,7 +2606,7 @@ impl Moon {
 }
 
 /// Additional celestial information for a moon
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -1906,7 +2627,7 @@ pub struct MoonStatistics {
 }
 
 /// Moon 3D model information
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -1920,7 +2641,7 @@ impl_map_collect!(ids::MoonID, Moon, moonID);
 
 
 /// Planet
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="mapPlanet"))]
@@ -1990,7 +2711,7 @@ impl Planet {
 }
 
 /// Additional celestial information for a planet
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -2011,7 +2732,7 @@ pub struct PlanetStatistics {
 }
 
 /// Planet 3D model information
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -2026,7 +2747,7 @@ impl_map_collect!(ids::PlanetID, Planet, planetID);
 
 
 /// Region of constellations
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="mapRegions"))]
@@ -2045,7 +2766,7 @@ pub struct Region {
     /// May be overridden by factionID values in [`Constellation`] or [`SolarSystem`]
     pub factionID: Option<ids::FactionID>,
     /// Background skybox nebula for this region
-    pub nebulaID: u32,    // TODO: Assign ID type
+    pub nebulaID: ids::NebulaID,
     /// Position of this region (approximate but not exact center), relative to the map origin
     pub position: MapPosition,
     /// Wormhole class of this region
@@ -2060,7 +2781,7 @@ impl_map_collect!(ids::RegionID, Region, regionID);
 /// Wormhole effect "2nd star" (Red Giant, Magnetar, etc)
 ///
 /// Consists of both a star object and an effect beacon. The star object is the same for all wormholes of the same type, while the effect beacon differs with the class and type of the wormhole.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="mapSecondarySuns"))]
@@ -2088,7 +2809,7 @@ impl FromIterator<SecondarySun> for IndexMap<ids::SolarSystemID, SecondarySun> {
 /// Solarsystem, a single in-game star system
 ///
 /// Terminology note: "SolarSystem" is the term for star systems within EVE Online third party development. Players usually use the term "system"
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="mapSolarSystems"))]
@@ -2134,6 +2855,8 @@ pub struct SolarSystem {
     /// Numerical security status,
     pub securityStatus: f64,
     /// [`Star`] of this solarsystem; Only certain special event or instance-host systems do not have a star. All regular k-space and wh-space systems have stars.
+    ///
+    /// Wormhole effect stars are not listed here, see [`SDE_Full::secondary_sun`]
     pub starID: Option<ids::StarID>,
     /// Star luminosity
     pub luminosity: Option<f64>,
@@ -2210,17 +2933,75 @@ impl SolarSystem {
             0.1
         }
     }
+
+    /// Security band of this solarsystem, see [`SecurityBand`]
+    ///
+    /// Known-space bands are determined by [`SolarSystem::security_rounded`], matching the in-game security display:
+    /// The client rounds half-up to one decimal (0.45 displays as 0.5 and is high-sec), with the exception of security status between 0.0 and 0.05, which displays as 0.1 and is low-sec.
+    ///
+    /// Wormhole space is determined by [`SolarSystem::wormholeClassID`] if present, else by solarsystem ID; Wormhole space has a security status of -1.0 and would otherwise be classified as null-sec.
+    pub fn security_band(&self) -> SecurityBand {
+        if !self.securityStatus.is_finite() {
+            return SecurityBand::Unknown;
+        }
+        match self.wormholeClassID {
+            Some(1..=6 | 12..=18) => return SecurityBand::WSpace,
+            Some(_) => {},
+            None if WSPACE_SOLARSYSTEMS.contains(&self.solarSystemID) => return SecurityBand::WSpace,
+            None => {}
+        }
+        match self.security_rounded() {
+            0.5.. => SecurityBand::HighSec,
+            0.1.. => SecurityBand::LowSec,
+            _ => SecurityBand::NullSec
+        }
+    }
+}
+
+/// Solarsystem IDs of wormhole ("J-space") systems, including Thera and the Drifter wormhole systems
+const WSPACE_SOLARSYSTEMS: std::ops::Range<ids::SolarSystemID> = 31_000_000..32_000_000;
+
+/// Security classification of a solarsystem, see [`SolarSystem::security_band`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SecurityBand {
+    /// High-sec, displayed security status 0.5 and up
+    HighSec,
+    /// Low-sec, displayed security status 0.1 to 0.4
+    LowSec,
+    /// Null-sec, displayed security status 0.0 and below; Includes Pochven
+    NullSec,
+    /// Wormhole space, including Thera and the Drifter wormhole systems
+    WSpace,
+    /// Security status is not a number
+    Unknown,
 }
 
 impl_map_collect!(ids::SolarSystemID, SolarSystem, solarSystemID);
 
+#[test]
+fn test_security_band() {
+    let system = |solar_system_id: ids::SolarSystemID, security: f64, wormhole_class: Option<ids::WormholeClassID>| serde_json::from_value::<SolarSystem>(serde_json::json!({
+        "_key": solar_system_id, "constellationID": 20000001, "regionID": 10000001, "name": {"en": "Test"},
+        "position": {"x": 0.0, "y": 0.0, "z": 0.0}, "radius": 1.0, "securityStatus": security, "wormholeClassID": wormhole_class
+    })).unwrap().security_band();
+
+    assert_eq!(system(30000142, 0.9459, None), SecurityBand::HighSec);
+    assert_eq!(system(30002053, 0.4538, Some(7)), SecurityBand::HighSec);
+    assert_eq!(system(30002813, 0.4499, None), SecurityBand::LowSec);
+    assert_eq!(system(30045328, 0.0184, None), SecurityBand::LowSec);
+    assert_eq!(system(30004759, -0.0072, None), SecurityBand::NullSec);
+    assert_eq!(system(30000157, -1.0, Some(25)), SecurityBand::NullSec);
+    assert_eq!(system(31000005, -1.0, Some(12)), SecurityBand::WSpace);
+    assert_eq!(system(31002238, -1.0, None), SecurityBand::WSpace);
+}
+
 
 /// Stargate connecting systems
 ///
 /// Each connection has two entries, one for the stargate in each system.
 ///
 /// Does not include player-built "Ansiblex" jump bridges
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="mapStargates"))]
@@ -2258,7 +3039,7 @@ impl Stargate {
 }
 
 /// Destination for a stargate, both the paired stargate and destination solarsystem
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -2273,7 +3054,7 @@ impl_map_collect!(ids::StargateID, Stargate, stargateID);
 /// Star
 ///
 /// most but not all systems have a central star
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="mapStars"))]
@@ -2292,7 +3073,7 @@ pub struct Star {
 }
 
 /// Additional celestial information for a star
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -2312,7 +3093,7 @@ impl_map_collect!(ids::StarID, Star, starID);
 /// Market groups form a hierarchical tree, with child-groups having their "parentGroupID" field set to the marketGroupID of their parent.
 ///
 /// All items on the market have a market group
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="marketGroups"))]
@@ -2413,8 +3194,22 @@ impl<'de> Deserialize<'de> for MasteryInfo {
     }
 }
 
+impl MasteryInfo {
+    /// Certificates required for each mastery level, with level 1 at index `0`
+    pub fn levels(&self) -> [&[ids::CertificateID]; 5] {
+        [&self.lvl1, &self.lvl2, &self.lvl3, &self.lvl4, &self.lvl5]
+    }
+}
+
+impl Serialize for MasteryInfo {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let levels = [&self.lvl1, &self.lvl2, &self.lvl3, &self.lvl4, &self.lvl5];
+        serializer.collect_seq(levels.into_iter().enumerate().map(|(_key, _value)| ExplicitMapEntry { _key: _key as u8, _value }))
+    }
+}
+
 /// Mercendary den event
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="mercenaryTacticalOperations"))]
@@ -2439,7 +3234,7 @@ pub struct MercenaryTacticalOperation {
 impl_map_collect!(ids::DungeonID, MercenaryTacticalOperation, operation_id);
 
 /// Metagroup or "tech tier" for [`Type`]s
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="metaGroup"))]
@@ -2460,7 +3255,7 @@ pub struct MetaGroup {
 }
 
 /// Colour for metagroup
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -2476,7 +3271,7 @@ pub struct MetaGroupColor {
 impl_map_collect!(ids::MetaGroupID, MetaGroup, metaGroupID);
 
 /// Military Campaign
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="militaryCampaigns"))]
@@ -2494,7 +3289,7 @@ pub struct MilitaryCampaign {
 
 impl_map_collect!(uuids::MilitaryCampaignID, MilitaryCampaign, militaryCampaignID);
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -2502,7 +3297,7 @@ pub struct MilitaryCampaignIssuer {
     pub factionID: ids::FactionID
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -2556,7 +3351,7 @@ pub struct MilitaryCampaignAnnotations {
     pub towCampaignCardButtonImage: String
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -2568,7 +3363,7 @@ pub enum MilitaryCampaignAnnotationRace {
 }
 
 /// Military Campaign Objective
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="militaryCampaignObjectives"))]
@@ -2592,7 +3387,7 @@ pub struct MilitaryCampaignObjective {
 
 impl_map_collect!(uuids::MilitaryCampaignObjectiveID, MilitaryCampaignObjective, militaryCampaignObjectiveID);
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -2603,7 +3398,7 @@ pub struct MilitaryCampaignObjectiveAnnotations {
     pub warning2: Option<LocalizedString>
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -2612,7 +3407,7 @@ pub struct MilitaryCampaignContributionMethod {
     pub parameters: Vec<MilitaryCampaignContributionMethodParameter>
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -2621,7 +3416,7 @@ pub struct MilitaryCampaignContributionMethodParameter {
     pub matcher: MilitaryCampaignContributionMethodParameterMatcher
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -2629,7 +3424,7 @@ pub struct MilitaryCampaignContributionMethodParameterMatcher {
     pub values: Vec<MilitaryCampaignContributionMethodParameterMatcherValue>
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -2639,7 +3434,7 @@ pub struct MilitaryCampaignContributionMethodParameterMatcherValue {    // TODO:
     pub values: Vec<String>
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -2647,7 +3442,7 @@ pub struct MilitaryCampaignObjectiveIssuer {
     pub corporationID : ids::CorporationID
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -2657,7 +3452,7 @@ pub struct MilitaryCampaignObjectiveRewards {
     pub standing: MilitaryCampaignObjectiveRewardStanding
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -2667,7 +3462,7 @@ pub struct MilitaryCampaignObjectiveRewardQuantity {
     pub progressInterval: u32,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -2677,7 +3472,7 @@ pub struct MilitaryCampaignObjectiveRewardStanding {
     pub progressInterval: u32
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -2686,7 +3481,7 @@ pub struct MilitaryCampaignObjectiveStandingIssuer {
 }
 
 /// NPC Mission
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="missions"))]
@@ -2701,6 +3496,7 @@ pub struct Mission {
     pub courierMission: Option<MissionCourier>,
     #[serde(default)]
     #[serde(deserialize_with="deserialize_explicit_entry_map")]
+    #[serde(serialize_with="serialize_explicit_entry_map")]
     pub extraStandings: IndexMap<ids::FactionID, f64>,
     pub factionID: Option<ids::FactionID>,
     pub hasStandingRewards: bool,
@@ -2709,6 +3505,7 @@ pub struct Mission {
     pub killMission: Option<MissionKill>,
     #[serde(default)]
     #[serde(deserialize_with="deserialize_mission_message_map")]
+    #[serde(serialize_with="serialize_mission_message_map")]
     pub messages: IndexMap<String, LocalizedString>,
     pub missionRewards: Option<MissionReward>
 }
@@ -2770,7 +3567,34 @@ fn deserialize_mission_message_map<'de, D: Deserializer<'de>>(deserializer: D) -
     deserializer.deserialize_seq(EntryVisitor(PhantomData::default(), PhantomData::default()))
 }
 
-#[derive(Debug, Deserialize)]
+fn serialize_mission_message_map<S: Serializer>(messages: &IndexMap<String, LocalizedString>, serializer: S) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    struct LocalizedMessage<'a> {
+        _key: &'a str,
+        en: &'a str,
+        de: &'a Option<String>,
+        es: &'a Option<String>,
+        fr: &'a Option<String>,
+        ja: &'a Option<String>,
+        ko: &'a Option<String>,
+        ru: &'a Option<String>,
+        zh: &'a Option<String>
+    }
+
+    serializer.collect_seq(messages.iter().map(|(_key, text)| LocalizedMessage {
+        _key,
+        en: &text.en,
+        de: &text.de,
+        es: &text.es,
+        fr: &text.fr,
+        ja: &text.ja,
+        ko: &text.ko,
+        ru: &text.ru,
+        zh: &text.zh,
+    }))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -2780,7 +3604,7 @@ pub struct MissionReward {
     pub bonusTimeInterval: Option<u32>
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -2789,7 +3613,7 @@ pub struct MissionRewardType {
     pub rewardTypeID: Option<ids::TypeID>
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -2799,7 +3623,7 @@ pub struct MissionCourier {
     pub objectiveTypeID: ids::TypeID
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -2812,7 +3636,7 @@ pub struct MissionKill {
 
 
 /// NPC character
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="npcCharacters"))]
@@ -2845,6 +3669,7 @@ pub struct NpcCharacter {
     /// Skills this NPC has
     #[serde(default)]
     #[serde(deserialize_with = "deserialize_npc_skill")]
+    #[serde(serialize_with="serialize_npc_skill")]
     pub skills: Vec<ids::TypeID>,
     /// NPC speciality
     pub specialityID: Option<ids::SpecialtyID>,
@@ -2887,8 +3712,18 @@ fn deserialize_npc_skill<'de, D: Deserializer<'de>>(deserializer: D) -> Result<V
     deserializer.deserialize_seq(EntryVisitor)
 }
 
+fn serialize_npc_skill<S: Serializer>(skills: &[ids::TypeID], serializer: S) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    #[allow(non_snake_case)]
+    struct NpcCharacterSkill {
+        typeID: ids::TypeID
+    }
+
+    serializer.collect_seq(skills.iter().map(|type_id| NpcCharacterSkill { typeID: *type_id }))
+}
+
 /// Additional agent-specific information for an NPC character
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -2903,10 +3738,10 @@ pub struct NpcCharacterAgent {
     pub level: i32,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
-#[serde(from="bool")]
+#[serde(from="bool", into="bool")]
 pub enum NpcCharacterGender {
     Male,
     Female
@@ -2923,10 +3758,28 @@ impl From<bool> for NpcCharacterGender {
     }
 }
 
+impl From<NpcCharacterGender> for bool {
+    fn from(value: NpcCharacterGender) -> Self {
+        matches!(value, NpcCharacterGender::Male)
+    }
+}
+
 impl_map_collect!(ids::CharacterID, NpcCharacter, characterID);
 
+#[test]
+fn test_serialize_roundtrip() {
+    fn roundtrip<T: Serialize + DeserializeOwned>(line: &str) {
+        let json = serde_json::to_string(&serde_json::from_str::<T>(line).unwrap()).unwrap();
+        assert_eq!(serde_json::to_string(&serde_json::from_str::<T>(&json).unwrap()).unwrap(), json);
+        assert!(json.starts_with("{\"_key\":"));
+    }
+
+    roundtrip::<NpcCharacter>(r#"{"_key": 3008416, "bloodlineID": 4, "ceo": false, "corporationID": 1000002, "gender": true, "name": {"en": "Antaken Kamola"}, "raceID": 1, "skills": [{"typeID": 3402}, {"typeID": 11433}], "uniqueName": false}"#);
+    roundtrip::<TypeMaterials>(r#"{"_key": 56, "materials": [{"materialTypeID": 34, "quantity": 10}], "randomizedMaterials": [{"materialTypeID": 35, "quantityMax": 8, "quantityMin": 2}]}"#);
+}
+
 /// Division of an NPC corporation
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="npcCorporationDivisions"))]
@@ -2949,7 +3802,7 @@ pub struct CorporationDivision {
 impl_map_collect!(ids::DivisionID, CorporationDivision, divisionID);
 
 /// NPC corporation
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="npcCorporations"))]
@@ -2976,10 +3829,12 @@ pub struct NpcCorporation {
     /// Corporation trades
     #[serde(default)]
     #[serde(deserialize_with="deserialize_explicit_entry_map")]
+    #[serde(serialize_with="serialize_explicit_entry_map")]
     pub corporationTrades: IndexMap<ids::TypeID, f64>,  // TODO: Document how these values work
     /// Divisions of this corporation
     #[serde(default)]
     #[serde(deserialize_with="deserialize_inline_entry_map")]
+    #[serde(serialize_with="serialize_inline_entry_map")]
     pub divisions: IndexMap<ids::DivisionID, NpcCorporationDivision>,
     /// Faction this corporation is a part of
     pub factionID: Option<ids::FactionID>,
@@ -2990,6 +3845,7 @@ pub struct NpcCorporation {
     /// ???
     #[serde(default)]
     #[serde(deserialize_with="deserialize_explicit_entry_map")]
+    #[serde(serialize_with="serialize_explicit_entry_map")]
     pub exchangeRates: IndexMap<ids::CorporationID, f64>,
     /// ???
     pub extent: String, // TODO: Enum
@@ -3004,13 +3860,14 @@ pub struct NpcCorporation {
     /// Current shareholders (Other NPC corps, lore information?)
     #[serde(default)]
     #[serde(deserialize_with="deserialize_explicit_entry_map")]
+    #[serde(serialize_with="serialize_explicit_entry_map")]
     pub investors: IndexMap<ids::CorporationID, i32>,
     /// Loyalty point trades offered by this company
     ///
     /// The table numbers are currently meaningless to third party developers. Corporation LP trades can be obtained through ESI based on the corporationID
     /// https://developers.eveonline.com/api-explorer#/operations/GetLoyaltyStoresCorporationIdOffers
     #[serde(default)]
-    pub lpOfferTables: Vec<u32>,    // TODO: Assign ID type
+    pub lpOfferTables: Vec<ids::LPOfferTableID>,
     /// main [`CorporationActivity`]
     pub mainActivityID: Option<ids::CorporationActivityID>,
     /// secondary [`CorporationActivity`]
@@ -3041,7 +3898,7 @@ pub struct NpcCorporation {
 }
 
 /// Division of an NPC corporation
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -3070,7 +3927,7 @@ impl_map_collect!(ids::CorporationID, NpcCorporation, corporationID);
 /// For station services, see [`StationOperation::services`]
 ///
 /// For station descriptions, see [`StationOperation::description`]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="npcStations"))]
@@ -3150,7 +4007,7 @@ impl NpcStation {
 impl_map_collect!(ids::StationID, NpcStation, stationID);
 
 /// Planet sov resource
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="planetResources"))]
@@ -3167,7 +4024,7 @@ pub struct PlanetResource {
 }
 
 /// Planet sov resource reagent info
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -3191,7 +4048,7 @@ pub struct PlanetReagent {
 impl_map_collect!(ids::PlanetID, PlanetResource, planet_id);
 
 /// Planetary industry schematic
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="planetSchematics"))]
@@ -3209,11 +4066,12 @@ pub struct PlanetSchematic {
     pub pins: Vec<ids::TypeID>,
     /// Input-output types
     #[serde(deserialize_with="deserialize_inline_entry_map")]
+    #[serde(serialize_with="serialize_inline_entry_map")]
     pub types: IndexMap<ids::TypeID, PlanetSchematicType>   // This _really_ should be parsed into separate input-output mappings, but that is hard to implement with serde
 }
 
 /// Input-output type for planetary interaction
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -3235,8 +4093,71 @@ impl InlineEntry<ids::TypeID> for PlanetSchematicType {
 
 impl_map_collect!(ids::PlanetSchematicID, PlanetSchematic, schematicID);
 
+/// Planetary industry production chain of a type, see [`SDE_Full::pi_chain`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PiTree {
+    /// Type produced by a schematic
+    Schematic {
+        type_id: ids::TypeID,
+        schematic_id: ids::PlanetSchematicID,
+        /// Quantity produced per schematic cycle
+        quantity: u32,
+        /// Inputs required per schematic cycle, as (quantity, production chain)
+        inputs: Vec<(u32, PiTree)>
+    },
+    /// Raw resource not produced by any schematic
+    Raw {
+        type_id: ids::TypeID,
+        /// True if this type is a [`PlanetReagent`] harvested by skyhooks, false for planet-extracted (P0) resources
+        reagent: bool
+    },
+    /// Type which is already an output further up the chain; Not expanded again
+    Cycle { type_id: ids::TypeID }
+}
+
+impl PiTree {
+    /// Type produced by this node of the chain
+    pub fn type_id(&self) -> ids::TypeID {
+        match self {
+            PiTree::Schematic { type_id, .. } | PiTree::Raw { type_id, .. } | PiTree::Cycle { type_id } => *type_id
+        }
+    }
+}
+
+#[test]
+fn test_pi_chain() {
+    let planet_schematics = [
+        r#"{"_key": 121, "cycleTime": 1800, "name": {"en": "Water"}, "pins": [2473], "types": [{"_key": 2268, "isInput": true, "quantity": 3000}, {"_key": 3645, "isInput": false, "quantity": 20}]}"#,
+        r#"{"_key": 126, "cycleTime": 3600, "name": {"en": "Coolant"}, "pins": [2474], "types": [{"_key": 3645, "isInput": true, "quantity": 40}, {"_key": 9832, "isInput": false, "quantity": 5}, {"_key": 2390, "isInput": true, "quantity": 40}]}"#,
+        // Schematics producing each other's input
+        r#"{"_key": 998, "cycleTime": 3600, "name": {"en": "Loop A"}, "pins": [2474], "types": [{"_key": 2, "isInput": true, "quantity": 1}, {"_key": 1, "isInput": false, "quantity": 1}]}"#,
+        r#"{"_key": 999, "cycleTime": 3600, "name": {"en": "Loop B"}, "pins": [2474], "types": [{"_key": 1, "isInput": true, "quantity": 1}, {"_key": 2, "isInput": false, "quantity": 1}]}"#,
+    ].into_iter().map(|line| serde_json::from_str::<PlanetSchematic>(line).unwrap()).map(|schematic| (schematic.schematicID, schematic)).collect();
+    let planet_resources = [
+        r#"{"_key": 40000001, "reagent": {"type_id": 2390, "amount_per_cycle": 10, "cycle_period": 1800, "secured_capacity": 100, "unsecured_capacity": 100}}"#,
+    ].into_iter().map(|line| serde_json::from_str::<PlanetResource>(line).unwrap()).map(|resource| (resource.planet_id, resource)).collect();
+    let sde = SDE_Full { planet_schematics, planet_resources, ..SDE_Full::default() };
+
+    assert_eq!(sde.pi_chain(9832), Some(PiTree::Schematic {
+        type_id: 9832,
+        schematic_id: 126,
+        quantity: 5,
+        inputs: vec![
+            (40, PiTree::Schematic { type_id: 3645, schematic_id: 121, quantity: 20, inputs: vec![(3000, PiTree::Raw { type_id: 2268, reagent: false })] }),
+            (40, PiTree::Raw { type_id: 2390, reagent: true }),
+        ],
+    }));
+    assert_eq!(sde.pi_chain(1), Some(PiTree::Schematic {
+        type_id: 1,
+        schematic_id: 998,
+        quantity: 1,
+        inputs: vec![(1, PiTree::Schematic { type_id: 2, schematic_id: 999, quantity: 1, inputs: vec![(1, PiTree::Cycle { type_id: 1 })] })],
+    }));
+    assert_eq!(sde.pi_chain(2268), None);
+}
+
 /// NPC or player character race
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="races"))]
@@ -3255,6 +4176,7 @@ pub struct CharacterRace {
     /// "Default" skills all players characters of this race already have upon starting the game
     #[serde(default)]
     #[serde(deserialize_with="deserialize_explicit_entry_map")]
+    #[serde(serialize_with="serialize_explicit_entry_map")]
     pub skills: IndexMap<ids::TypeID, values::SkillLevel>
 }
 
@@ -3262,7 +4184,7 @@ impl_map_collect!(ids::RaceID, CharacterRace, raceID);
 
 
 /// Ship Tree Element (Used weapon type, used tank type, etc)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="shipTreeElements"))]
@@ -3277,7 +4199,7 @@ pub struct ShipTreeElement {
 impl_map_collect!(ids::ShipTreeElementID, ShipTreeElement, shipTreeElementID);
 
 /// Ship Tree Faction
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="shipTreeFactions"))]
@@ -3287,13 +4209,14 @@ pub struct ShipTreeFaction {
     pub description: LocalizedString,
     pub icon: String,
     #[serde(deserialize_with="deserialize_explicit_entry_map")]
+    #[serde(serialize_with="serialize_explicit_entry_map")]
     pub elements: IndexMap<u32, ids::ShipTreeElementID>
 }
 
 impl_map_collect!(ids::FactionID, ShipTreeFaction, factionID);
 
 /// Ship Tree Group (Ship class)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="shipTreeGroups"))]
@@ -3308,15 +4231,17 @@ pub struct ShipTreeGroup {
     pub iconSmallNPC: String,
     #[serde(default)]
     #[serde(deserialize_with="deserialize_explicit_entry_map")]
+    #[serde(serialize_with="serialize_explicit_entry_map")]
     pub elements: IndexMap<u32, ids::ShipTreeElementID>,
     #[serde(default)]
     #[serde(deserialize_with="deserialize_inline_entry_map")]
+    #[serde(serialize_with="serialize_inline_entry_map")]
     pub preReqSkills: IndexMap<ids::FactionID, ShipTreeGroupSkills>
 }
 
 impl_map_collect!(ids::ShipTreeGroupID, ShipTreeGroup, shipTreeGroupID);
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -3324,6 +4249,7 @@ pub struct ShipTreeGroupSkills {
     #[serde(rename="_key")]
     pub factionID: ids::FactionID,
     #[serde(deserialize_with="deserialize_inline_entry_map")]
+    #[serde(serialize_with="serialize_inline_entry_map")]
     pub skills: IndexMap<ids::TypeID, ShipTreeGroupSkillInfo>
 }
 
@@ -3333,7 +4259,7 @@ impl InlineEntry<ids::FactionID> for ShipTreeGroupSkills {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -3352,7 +4278,7 @@ impl InlineEntry<ids::TypeID> for ShipTreeGroupSkillInfo {
 
 
 /// Skin license item
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="skinLicenses"))]
@@ -3377,7 +4303,7 @@ pub struct SkinLicense {
 impl_map_collect!(ids::TypeID, SkinLicense, typeID);
 
 /// Skin material; The design & colours of a skin, shared between multiple ships
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="skinMaterials"))]
@@ -3394,7 +4320,7 @@ pub struct SkinMaterial {
 impl_map_collect!(ids::SkinMaterialID, SkinMaterial, materialID);
 
 /// Ship skin, not to be confused with a [`SkinLicense`] item
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="skins"))]
@@ -3418,10 +4344,22 @@ pub struct Skin {
     pub allowCCPDevs: bool,
 }
 
+impl Skin {
+    /// Whether this skin is visible on the specified server
+    ///
+    /// Singularity (the test server) follows Tranquility visibility
+    pub fn visible_on(&self, server: values::GameServer) -> bool {
+        match server {
+            values::GameServer::Tranquility | values::GameServer::Singularity => self.visibleTranquility,
+            values::GameServer::Serenity => self.visibleSerenity,
+        }
+    }
+}
+
 impl_map_collect!(ids::SkinID, Skin, skinID);
 
 /// Sovereignty Upgrade for use with the Sovereignty Hub
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="sovereigntyUpgrades"))]
@@ -3444,7 +4382,7 @@ pub struct SovereigntyUpgrade {
 }
 
 /// Additional fuel required by a [`SovereigntyUpgrade`]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -3460,7 +4398,7 @@ pub struct SovereigntyUpgradeFuel {
 impl_map_collect!(ids::TypeID, SovereigntyUpgrade, typeID);
 
 /// [`NpcStation`] operation
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="stationOperations"))]
@@ -3481,6 +4419,7 @@ pub struct StationOperation {
     /// Only provided for the 4 major factions and Jove.
     #[serde(default)]
     #[serde(deserialize_with="deserialize_explicit_entry_map")]
+    #[serde(serialize_with="serialize_explicit_entry_map")]
     pub stationTypes: IndexMap<ids::RaceID, ids::TypeID>,
     /// ???
     pub border: f64,
@@ -3501,7 +4440,7 @@ pub struct StationOperation {
 impl_map_collect!(ids::StationOperationID, StationOperation, operationID);
 
 /// [`NpcStation`] service
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="stationServices"))]
@@ -3520,7 +4459,7 @@ impl_map_collect!(ids::StationServiceID, StationService, serviceID);
 /// A language the game officially is translated for
 ///
 /// This SDE library handles translated strings through the [`LocalizedString`] type
-#[derive(Debug, Deserialize, Hash, Eq, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Hash, Eq, PartialEq)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="translationLanguages"))]
@@ -3533,7 +4472,7 @@ pub struct TranslationLanguage {
 }
 
 /// Ship & effect beacon bonuses
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="typeBonus"))]
@@ -3549,6 +4488,7 @@ pub struct TypeBonuses {
     #[serde(default)]
     #[serde(rename = "types")]
     #[serde(deserialize_with="deserialize_explicit_entry_map")]
+    #[serde(serialize_with="serialize_explicit_entry_map")]
     pub skillBonuses: IndexMap<ids::TypeID, Vec<TypeBonus>>,
     /// Misc bonuses, used for effect beacons and T3 Destroyers
     ///
@@ -3563,7 +4503,7 @@ pub struct TypeBonuses {
 }
 
 /// Single bonus
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -3585,7 +4525,7 @@ pub struct TypeBonus {
 impl_map_collect!(ids::TypeID, TypeBonuses, typeID);
 
 /// Dogma information for a [`Type`]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="typeDogma"))]
@@ -3597,12 +4537,14 @@ pub struct TypeDogma {
     ///
     /// Map of attributeID and attribute value
     #[serde(deserialize_with="deserialize_type_attributes")]
+    #[serde(serialize_with="serialize_type_attributes")]
     pub dogmaAttributes: IndexMap<ids::AttributeID, f64>,
     /// Type's effects
     ///
     /// Map of effectID and whether or not the effect is set as "isDefault". (Meaning of "isDefault" not documented here)
     #[serde(default)]
     #[serde(deserialize_with="deserialize_type_effects")]
+    #[serde(serialize_with="serialize_type_effects")]
     pub dogmaEffects: IndexMap<ids::EffectID, bool>
 }
 
@@ -3636,6 +4578,17 @@ fn deserialize_type_attributes<'de, D: Deserializer<'de>>(deserializer: D) -> Re
     deserializer.deserialize_seq(SeqVisitor)
 }
 
+fn serialize_type_attributes<S: Serializer>(attributes: &IndexMap<ids::AttributeID, f64>, serializer: S) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    #[allow(non_snake_case)]
+    struct TypeDogmaAttribute {
+        attributeID: ids::AttributeID,
+        value: f64
+    }
+
+    serializer.collect_seq(attributes.iter().map(|(attribute_id, value)| TypeDogmaAttribute { attributeID: *attribute_id, value: *value }))
+}
+
 fn deserialize_type_effects<'de, D: Deserializer<'de>>(deserializer: D) -> Result<IndexMap<ids::EffectID, bool>, D::Error> {
     struct SeqVisitor;
     impl<'de> Visitor<'de> for SeqVisitor {
@@ -3666,10 +4619,21 @@ fn deserialize_type_effects<'de, D: Deserializer<'de>>(deserializer: D) -> Resul
     deserializer.deserialize_seq(SeqVisitor)
 }
 
+fn serialize_type_effects<S: Serializer>(effects: &IndexMap<ids::EffectID, bool>, serializer: S) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    #[allow(non_snake_case)]
+    struct TypeDogmaEffect {
+        effectID: ids::EffectID,
+        isDefault: bool
+    }
+
+    serializer.collect_seq(effects.iter().map(|(effect_id, is_default)| TypeDogmaEffect { effectID: *effect_id, isDefault: *is_default }))
+}
+
 impl_map_collect!(ids::TypeID, TypeDogma, typeID);
 
 /// TypeList; List of types
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="typeLists"))]
@@ -3745,7 +4709,7 @@ impl_map_collect!(ids::TypeListID, TypeList, typeListID);
 /// Type reprocessing output
 ///
 /// To reprocess an item, a stack of [`Type::portionSize`] units is required
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="typeMaterials"))]
@@ -3756,15 +4720,17 @@ pub struct TypeMaterials {
     /// Output materials
     #[serde(default)]
     pub materials: Vec<TypeMaterial>,
-    /// Output materials subject to random selection
+    /// Output materials subject to random selection, mapped to their (minimum, maximum) quantity per [`Type::portionSize`] of input materials
     ///
-    /// Reprocessing will yield one of these materials, with a quantity between [`TypeRandomMaterial::quantityMin`] and [`TypeRandomMaterial::quantityMax`]
+    /// Reprocessing will yield one of these materials, with a quantity between the minimum and maximum
     #[serde(default)]
-    pub randomizedMaterials: Vec<TypeRandomMaterial>
+    #[serde(deserialize_with="deserialize_random_materials")]
+    #[serde(serialize_with="serialize_random_materials")]
+    pub randomizedMaterials: IndexMap<ids::TypeID, (u32, u32)>
 }
 
 /// Single type reprocessing output
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
@@ -3778,23 +4744,49 @@ pub struct TypeMaterial {
 /// Single random reprocessing output possibility
 ///
 /// During reprocessing, a random roll between `quantityMin` and `quantityMax` is made
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(internal_type))]
-pub struct TypeRandomMaterial {
+struct TypeRandomMaterial {
     /// Result output [`Type`]
-    pub materialTypeID: ids::TypeID,
+    materialTypeID: ids::TypeID,
     /// Maximum quantity, per [`Type::portionSize`] of input materials
-    pub quantityMax: u32,   // TODO: Document whether these are inclusive
+    quantityMax: u32,   // TODO: Document whether these are inclusive
     /// Minimum quantity, per [`Type::portionSize`] of input materials
-    pub quantityMin: u32,
+    quantityMin: u32,
+}
+
+fn deserialize_random_materials<'de, D: Deserializer<'de>>(deserializer: D) -> Result<IndexMap<ids::TypeID, (u32, u32)>, D::Error> {
+    pub struct RandomMaterialVisitor;
+    impl<'de> Visitor<'de> for RandomMaterialVisitor {
+        type Value = IndexMap<ids::TypeID, (u32, u32)>;
+
+        fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+            formatter.write_str("array of randomized materials (materialTypeID, quantityMin & quantityMax)")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error> where A: SeqAccess<'de> {
+            let size_hint = seq.size_hint();
+            let mut map = size_hint.map(IndexMap::with_capacity).unwrap_or_else(IndexMap::new);
+            while let Some(value) = seq.next_element::<TypeRandomMaterial>()? {
+                map.insert(value.materialTypeID, (value.quantityMin, value.quantityMax));
+            }
+            Ok(map)
+        }
+    }
+
+    deserializer.deserialize_seq(RandomMaterialVisitor)
+}
+
+fn serialize_random_materials<S: Serializer>(materials: &IndexMap<ids::TypeID, (u32, u32)>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(materials.iter().map(|(type_id, (min, max))| TypeRandomMaterial { materialTypeID: *type_id, quantityMax: *max, quantityMin: *min }))
 }
 
 impl_map_collect!(ids::TypeID, TypeMaterials, typeID);
 
 /// [`ShipTreeElement`]s for a [`Type`]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 pub struct TypeShipTreeElements {
     /// TypeID for which elements are provided
@@ -3809,7 +4801,7 @@ pub struct TypeShipTreeElements {
 impl_map_collect!(ids::TypeID, TypeShipTreeElements, typeID);
 
 /// Item type
-#[derive(Debug, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 #[allow(non_snake_case)]
 #[cfg_attr(feature="sde_strict", serde(deny_unknown_fields))]
 #[cfg_attr(feature="docs_export", doc_sde(sde_file="types"))]
@@ -3879,9 +4871,9 @@ impl_map_collect!(ids::TypeID, Type, typeID);
 
 /// Entire Static Data Export as a single struct
 ///
-/// Loaded through [`SDELoader::full`]
+/// Loaded through [`SDELoader::full`], or partially through [`SDELoader::select`]
 #[allow(non_camel_case_types)]  // "SDE" is an abbreviation here
-#[derive(Debug)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct SDE_Full {
     pub agent_types: IndexMap<ids::AgentTypeID, AgentType>,
     pub agents_in_space: IndexMap<ids::CharacterID, AgentInSpace>,
@@ -3953,9 +4945,1073 @@ pub struct SDE_Full {
     pub types: IndexMap<ids::TypeID, Type>,
 }
 
+impl SDE_Full {
+    /// Number of entries in each table of the SDE, keyed by field name
+    pub fn table_sizes(&self) -> IndexMap<&'static str, usize> {
+        IndexMap::from([
+            ("agent_types", self.agent_types.len()),
+            ("agents_in_space", self.agents_in_space.len()),
+            ("ancestries", self.ancestries.len()),
+            ("archetypes", self.archetypes.len()),
+            ("bloodlines", self.bloodlines.len()),
+            ("blueprints", self.blueprints.len()),
+            ("categories", self.categories.len()),
+            ("certificates", self.certificates.len()),
+            ("character_attributes", self.character_attributes.len()),
+            ("character_titles", self.character_titles.len()),
+            ("clone_grades", self.clone_grades.len()),
+            ("compressible_types", self.compressible_types.len()),
+            ("contraband_types", self.contraband_types.len()),
+            ("control_tower_resources", self.control_tower_resources.len()),
+            ("corporation_activities", self.corporation_activities.len()),
+            ("dbuff_collections", self.dbuff_collections.len()),
+            ("dogma_attribute_categories", self.dogma_attribute_categories.len()),
+            ("dogma_attributes", self.dogma_attributes.len()),
+            ("dogma_effects", self.dogma_effects.len()),
+            ("dogma_units", self.dogma_units.len()),
+            ("dungeons", self.dungeons.len()),
+            ("dynamic_item_attributes", self.dynamic_item_attributes.len()),
+            ("factions", self.factions.len()),
+            ("freelance_job_schemas", self.freelance_job_schemas.len()),
+            ("graphics", self.graphics.len()),
+            ("groups", self.groups.len()),
+            ("icons", self.icons.len()),
+            ("landmarks", self.landmarks.len()),
+            ("map_asteroid_belts", self.map_asteroid_belts.len()),
+            ("map_constellations", self.map_constellations.len()),
+            ("map_moons", self.map_moons.len()),
+            ("map_planets", self.map_planets.len()),
+            ("map_regions", self.map_regions.len()),
+            ("map_secondarysuns", self.map_secondarysuns.len()),
+            ("map_solarsystems", self.map_solarsystems.len()),
+            ("map_stargates", self.map_stargates.len()),
+            ("map_stars", self.map_stars.len()),
+            ("market_groups", self.market_groups.len()),
+            ("masteries", self.masteries.len()),
+            ("mercenary_tactical_operations", self.mercenary_tactical_operations.len()),
+            ("meta_groups", self.meta_groups.len()),
+            ("military_campaigns", self.military_campaigns.len()),
+            ("military_campaign_objectives", self.military_campaign_objectives.len()),
+            ("missions", self.missions.len()),
+            ("npc_characters", self.npc_characters.len()),
+            ("npc_corporation_divisions", self.npc_corporation_divisions.len()),
+            ("npc_corporations", self.npc_corporations.len()),
+            ("npc_stations", self.npc_stations.len()),
+            ("planet_resources", self.planet_resources.len()),
+            ("planet_schematics", self.planet_schematics.len()),
+            ("races", self.races.len()),
+            ("ship_tree_elements", self.ship_tree_elements.len()),
+            ("ship_tree_factions", self.ship_tree_factions.len()),
+            ("ship_tree_groups", self.ship_tree_groups.len()),
+            ("skin_licenses", self.skin_licenses.len()),
+            ("skin_materials", self.skin_materials.len()),
+            ("skins", self.skins.len()),
+            ("sovereignty_upgrades", self.sovereignty_upgrades.len()),
+            ("station_operations", self.station_operations.len()),
+            ("station_services", self.station_services.len()),
+            ("translation_languages", self.translation_languages.len()),
+            ("type_bonus", self.type_bonus.len()),
+            ("type_dogma", self.type_dogma.len()),
+            ("type_lists", self.type_lists.len()),
+            ("type_materials", self.type_materials.len()),
+            ("types", self.types.len()),
+        ])
+    }
+
+    /// Drop all translations other than `language` from every [`LocalizedString`] in the SDE, reducing memory use for single-language applications
+    ///
+    /// English strings are always kept, so [`LocalizedString::get`] continues to work for any language, falling back to English
+    pub fn retain_language(&mut self, language: Language) {
+        RetainLanguage::retain_language(self, language)
+    }
+
+    /// Returns a division of an NPC corporation, combined with the global division information
+    ///
+    /// # Arguments
+    ///
+    /// * `corporation_id`: NPC corporation
+    /// * `division_id`: Division of that corporation, *not* the per-corporation `divisionNumber`
+    ///
+    /// returns: None if the corporation does not exist or does not have the specified division
+    pub fn corporation_division(&self, corporation_id: ids::CorporationID, division_id: ids::DivisionID) -> Option<DivisionView<'_>> {
+        let corporation = self.npc_corporations.get(&corporation_id)?;
+        let corporation_division = corporation.divisions.get(&division_id)?;
+        let division = self.npc_corporation_divisions.get(&division_id)?;
+        Some(DivisionView {
+            corporation,
+            division,
+            divisionNumber: corporation_division.divisionNumber,
+            leaderID: corporation_division.leaderID,
+            leader: self.npc_characters.get(&corporation_division.leaderID),
+            size: corporation_division.size,
+        })
+    }
+
+    /// Wormhole effect "2nd star" of a solarsystem, if any
+    ///
+    /// Secondary suns are no longer part of the solarsystem data; `mapSecondarySuns` is the authoritative source, loaded into [`SDE_Full::map_secondarysuns`]
+    pub fn secondary_sun(&self, solar_system_id: ids::SolarSystemID) -> Option<&SecondarySun> {
+        self.map_secondarysuns.get(&solar_system_id)
+    }
+
+    /// Contraband information of a type in the specified faction's space, None if the type is not contraband there
+    pub fn contraband_info(&self, type_id: ids::TypeID, faction_id: ids::FactionID) -> Option<&ContrabandFactionInfo> {
+        self.contraband_types.get(&type_id)?.factions.get(&faction_id)
+    }
+
+    /// Whether a type is contraband in the specified faction's space
+    pub fn is_contraband(&self, type_id: ids::TypeID, faction_id: ids::FactionID) -> bool {
+        self.contraband_info(type_id, faction_id).is_some()
+    }
+
+    /// Factions in whose space a type is contraband
+    pub fn contraband_factions(&self, type_id: ids::TypeID) -> impl Iterator<Item=ids::FactionID> + use<'_> {
+        self.contraband_types.get(&type_id)
+            .into_iter()
+            .flat_map(|contraband| contraband.factions.keys().copied())
+    }
+
+    /// Planetary industry production chain of a type, recursively resolving the schematics producing each input
+    ///
+    /// returns: Production chain, or None if no schematic produces the type
+    pub fn pi_chain(&self, output_type: ids::TypeID) -> Option<PiTree> {
+        let producers = self.planet_schematics.values()
+            .flat_map(|schematic| {
+                schematic.types.values()
+                    .filter(|schematic_type| !schematic_type.isInput)
+                    .map(move |schematic_type| (schematic_type.typeID, schematic))
+            })
+            .fold(HashMap::new(), |mut producers, (type_id, schematic)| {
+                producers.entry(type_id).or_insert(schematic);
+                producers
+            });
+        let reagents = self.planet_resources.values()
+            .filter_map(|resource| resource.reagent.as_ref().map(|reagent| reagent.type_id))
+            .collect::<HashSet<_>>();
+
+        fn resolve(type_id: ids::TypeID, producers: &HashMap<ids::TypeID, &PlanetSchematic>, reagents: &HashSet<ids::TypeID>, chain: &mut Vec<ids::TypeID>) -> PiTree {
+            let Some(schematic) = producers.get(&type_id) else {
+                return PiTree::Raw { type_id, reagent: reagents.contains(&type_id) };
+            };
+            if chain.contains(&type_id) {
+                return PiTree::Cycle { type_id };
+            }
+            chain.push(type_id);
+            let inputs = schematic.types.values()
+                .filter(|schematic_type| schematic_type.isInput)
+                .map(|input| (input.quantity, resolve(input.typeID, producers, reagents, chain)))
+                .collect();
+            chain.pop();
+            PiTree::Schematic { type_id, schematic_id: schematic.schematicID, quantity: schematic.types[&type_id].quantity, inputs }
+        }
+
+        producers.contains_key(&output_type).then(|| resolve(output_type, &producers, &reagents, &mut Vec::new()))
+    }
+
+    /// Mastery level (`0..=5`) of a ship with the specified trained skills
+    ///
+    /// Each mastery level requires its certificates at the matching [`CertificateLevel`], and all lower mastery levels.
+    /// Mastery level 0 is returned without checking the ship's required skills; Ships without mastery information and unknown certificates never reach a mastery level above 0.
+    ///
+    /// `skills` maps skill TypeIDs to trained level, skills not present are considered untrained
+    pub fn ship_mastery_level(&self, ship_type_id: ids::TypeID, skills: &HashMap<ids::TypeID, values::SkillLevel>) -> u8 {
+        let Some(mastery) = self.masteries.get(&ship_type_id) else { return 0; };
+        mastery.levels().into_iter()
+            .zip(CertificateLevel::ALL)
+            .take_while(|(certificates, level)| {
+                certificates.iter().all(|certificate_id| {
+                    self.certificates.get(certificate_id).is_some_and(|certificate| certificate.is_met(*level, skills))
+                })
+            })
+            .count() as u8
+    }
+
+    /// Query the types in this SDE, see [`ItemQuery`]
+    pub fn query(&self) -> ItemQuery<'_> {
+        ItemQuery::new(self)
+    }
+
+    /// Research agents, with the skills each agent offers research in
+    ///
+    /// The JSONL SDE has no separate research agent file; Research agents are the [`NpcCharacter`]s with agent type [`AgentType::ResearchAgent`], and their research fields are the character's `skills`
+    pub fn research_agents(&self) -> IndexMap<ids::CharacterID, &[ids::TypeID]> {
+        self.npc_characters.values()
+            .filter(|character| {
+                character.agent.as_ref()
+                    .and_then(|agent| self.agent_types.get(&agent.agentTypeID))
+                    .is_some_and(|agent_type| *agent_type == AgentType::ResearchAgent)
+            })
+            .map(|character| (character.characterID, &*character.skills))
+            .collect()
+    }
+
+    /// Value of an attribute for a type, falling back to the attribute's default value
+    ///
+    /// Attributes not set in the type's [`TypeDogma`] have their [`Attribute::defaultValue`], this includes types without any dogma data.
+    ///
+    /// returns: Attribute value, or None if the attribute is unknown
+    pub fn attribute_value(&self, type_id: ids::TypeID, attribute_id: ids::AttributeID) -> Option<f64> {
+        self.type_dogma.get(&type_id)
+            .and_then(|dogma| dogma.dogmaAttributes.get(&attribute_id))
+            .copied()
+            .or_else(|| self.dogma_attributes.get(&attribute_id).map(|attribute| attribute.defaultValue))
+    }
+
+    /// Clamps an attribute value to the bounds set by the attribute's `minAttributeID` and `maxAttributeID`
+    ///
+    /// Bounds are resolved from the type's own attribute values. Bounds that cannot be resolved are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `type_id`: Type whose attributes specify the bounds
+    /// * `attribute_id`: Attribute to clamp
+    /// * `value`: Value to clamp
+    ///
+    /// returns: Clamped value
+    pub fn clamp_attribute(&self, type_id: ids::TypeID, attribute_id: ids::AttributeID, value: f64) -> f64 {
+        let Some(attribute) = self.dogma_attributes.get(&attribute_id) else { return value; };
+
+        let mut value = value;
+        if let Some(max) = attribute.maxAttributeID.and_then(|max_id| self.attribute_value(type_id, max_id)) {
+            value = value.min(max);
+        }
+        if let Some(min) = attribute.minAttributeID.and_then(|min_id| self.attribute_value(type_id, min_id)) {
+            value = value.max(min);
+        }
+        value
+    }
+
+    /// Applies multiplier modifiers to a base attribute value, stacking-penalized if the attribute is not [`Attribute::stackable`]
+    ///
+    /// Unknown attributes are treated as stackable
+    ///
+    /// # Arguments
+    ///
+    /// * `attribute_id`: Modified attribute
+    /// * `base`: Unmodified attribute value
+    /// * `modifiers`: Multipliers applied to the attribute
+    ///
+    /// returns: Modified attribute value
+    pub fn apply_attribute_modifiers(&self, attribute_id: ids::AttributeID, base: f64, modifiers: &[f64]) -> f64 {
+        if self.dogma_attributes.get(&attribute_id).is_some_and(|attribute| !attribute.stackable) {
+            apply_stacking(base, modifiers)
+        } else {
+            modifiers.iter().fold(base, |value, modifier| value * modifier)
+        }
+    }
+
+    /// Range of the mutaplasmid roll multiplier for an attribute of a mutated ("abyssal") type
+    ///
+    /// If multiple mutaplasmids produce the type, returns the combined range of all of them
+    ///
+    /// # Arguments
+    ///
+    /// * `mutated_type_id`: Resulting type of mutaplasmid application
+    /// * `attribute_id`: Mutated attribute
+    ///
+    /// returns: `(min, max)` multipliers, or None if no mutaplasmid modifies this attribute for this type
+    pub fn dynamic_attribute_range(&self, mutated_type_id: ids::TypeID, attribute_id: ids::AttributeID) -> Option<(f64, f64)> {
+        self.dynamic_item_attributes.values()
+            .filter(|mutaplasmid| mutaplasmid.inputOutputMapping.iter().any(|mapping| mapping.resultingType == mutated_type_id))
+            .filter_map(|mutaplasmid| mutaplasmid.attributeIDs.get(&attribute_id))
+            .map(|info| (info.min, info.max))
+            .reduce(|(min_a, max_a), (min_b, max_b)| (min_a.min(min_b), max_a.max(max_b)))
+    }
+
+    /// Returns all celestials in a solarsystem, with their names
+    ///
+    /// Celestials with dangling references are skipped
+    ///
+    /// returns: None if the solarsystem does not exist
+    pub fn system_map(&self, solar_system_id: ids::SolarSystemID) -> Option<SystemMap<'_>> {
+        let solar_system = self.map_solarsystems.get(&solar_system_id)?;
+        // Generated names of planets & moons, used to name their orbiting celestials
+        let mut names = HashMap::<ids::ItemID, LocalizedString>::new();
+
+        let star = solar_system.starID
+            .and_then(|star_id| self.map_stars.get(&star_id))
+            .map(|star| MapCelestial { name: solar_system.name.clone(), item: star });
+
+        let mut planets = Vec::with_capacity(solar_system.planetIDs.len());
+        let mut station_ids = Vec::new();
+        for planet in solar_system.planetIDs.iter().filter_map(|planet_id| self.map_planets.get(planet_id)) {
+            let Ok(planet_name) = planet.name(|_| Ok::<_, ()>(&solar_system.name)) else { continue; };
+            names.insert(planet.planetID, planet_name.clone());
+            station_ids.extend(&planet.npcStationIDs);
+
+            let mut moons = Vec::with_capacity(planet.moonIDs.len());
+            for moon in planet.moonIDs.iter().filter_map(|moon_id| self.map_moons.get(moon_id)) {
+                let Ok(moon_name) = moon.name(|_| Ok::<_, ()>(&planet_name)) else { continue; };
+                names.insert(moon.moonID, moon_name.clone());
+                station_ids.extend(&moon.npcStationIDs);
+                moons.push(MapCelestial { name: moon_name, item: moon });
+            }
+
+            let asteroid_belts = planet.asteroidBeltIDs.iter()
+                .filter_map(|belt_id| self.map_asteroid_belts.get(belt_id))
+                .map(|belt| MapCelestial { name: belt.name(|_| &planet_name), item: belt })
+                .collect();
+
+            planets.push(MapPlanet { planet: MapCelestial { name: planet_name, item: planet }, moons, asteroid_belts });
+        }
+
+        let stargates = solar_system.stargateIDs.iter()
+            .filter_map(|stargate_id| self.map_stargates.get(stargate_id))
+            .filter_map(|stargate| {
+                let name = stargate.name(|system_id| self.map_solarsystems.get(&system_id).map(|system| &system.name).ok_or(()));
+                name.ok().map(|name| MapCelestial { name, item: stargate })
+            })
+            .collect();
+
+        let stations = station_ids.into_iter()
+            .filter_map(|station_id| self.npc_stations.get(station_id))
+            .filter_map(|station| {
+                let name = station.name(
+                    |orbit_id| names.get(&orbit_id).ok_or(()),
+                    |operation_id| self.station_operations.get(&operation_id).map(|operation| &operation.operationName).ok_or(()),
+                    |corporation_id| self.npc_corporations.get(&corporation_id).map(|corporation| &corporation.name).ok_or(()),
+                );
+                name.ok().map(|name| MapCelestial { name, item: station })
+            })
+            .collect();
+
+        Some(SystemMap { solar_system, star, planets, stargates, stations })
+    }
+
+    /// Direct skill requirements of a type, as specified by its `requiredSkill` dogma attributes
+    ///
+    /// # Arguments
+    ///
+    /// * `type_id`: Type to get skill requirements of, typically a skill, ship, or module
+    ///
+    /// returns: Required skills and their required level
+    pub fn skill_prerequisites(&self, type_id: ids::TypeID) -> Vec<(ids::TypeID, values::SkillLevel)> {
+        /// Pairs of (requiredSkillN, requiredSkillNLevel) attributes
+        const REQUIRED_SKILL_ATTRIBUTES: [(ids::AttributeID, ids::AttributeID); 6] = [(182, 277), (183, 278), (184, 279), (1285, 1286), (1289, 1287), (1290, 1288)];
+
+        let Some(dogma) = self.type_dogma.get(&type_id) else { return Vec::new(); };
+        REQUIRED_SKILL_ATTRIBUTES.iter()
+            .filter_map(|(skill_attribute, level_attribute)| {
+                let skill = *dogma.dogmaAttributes.get(skill_attribute)?;
+                let level = dogma.dogmaAttributes.get(level_attribute).copied().unwrap_or(0.0);
+                // Unused requirement slots may be present with a value of 0
+                (skill > 0.0).then_some((skill as ids::TypeID, level as values::SkillLevel))
+            })
+            .collect()
+    }
+
+    /// All skills required for a type, including prerequisites of required skills
+    ///
+    /// Each skill is listed once, at the highest level required anywhere in the requirement tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `type_id`: Type to get skill plan for
+    ///
+    /// returns: Required skills and their required level, in trainable order; A skill's prerequisites are listed before the skill itself
+    pub fn full_skill_plan(&self, type_id: ids::TypeID) -> Vec<(ids::TypeID, values::SkillLevel)> {
+        fn visit(sde: &SDE_Full, type_id: ids::TypeID, visited: &mut HashSet<ids::TypeID>, required_levels: &mut HashMap<ids::TypeID, values::SkillLevel>, plan: &mut Vec<ids::TypeID>) {
+            for (skill_id, level) in sde.skill_prerequisites(type_id) {
+                let required = required_levels.entry(skill_id).or_default();
+                *required = (*required).max(level);
+                if visited.insert(skill_id) {
+                    visit(sde, skill_id, visited, required_levels, plan);
+                    plan.push(skill_id);
+                }
+            }
+        }
+
+        let mut visited = HashSet::from([type_id]);
+        let mut required_levels = HashMap::new();
+        let mut plan = Vec::new();
+        visit(self, type_id, &mut visited, &mut required_levels, &mut plan);
+        plan.into_iter()
+            .map(|skill_id| (skill_id, required_levels[&skill_id]))
+            .collect()
+    }
+
+    /// Resolve the manufacturing dependency tree for a type, through manufacturing and reaction blueprints
+    ///
+    /// Quantities are for building a single unit of `type_id`, rounded up to whole job runs at each level; No material efficiency is applied.
+    /// Inputs that already occur higher up in the tree (cyclic reactions) are not expanded further, nor are inputs below `max_depth`.
+    pub fn build_tree(&self, type_id: ids::TypeID, max_depth: usize) -> ProductionNode {
+        fn expand(products: &HashMap<ids::TypeID, (ids::TypeID, &BPActivity)>, node: &mut ProductionNode, path: &mut Vec<ids::TypeID>, depth: usize) {
+            let Some((blueprint_id, activity)) = products.get(&node.type_id) else { return; };
+            node.blueprint_id = Some(*blueprint_id);
+            if depth == 0 || path.contains(&node.type_id) {
+                return;
+            }
+
+            let per_run = activity.products.get(&node.type_id).map(|(quantity, _)| u64::from(*quantity)).unwrap_or(1).max(1);
+            let runs = node.quantity.div_ceil(per_run);
+
+            path.push(node.type_id);
+            for (material_id, quantity) in &activity.materials {
+                let mut child = ProductionNode { type_id: *material_id, quantity: u64::from(*quantity) * runs, blueprint_id: None, children: Vec::new() };
+                expand(products, &mut child, path, depth - 1);
+                node.children.push(child);
+            }
+            path.pop();
+        }
+
+        let mut products = HashMap::new();
+        for blueprint in self.blueprints.values() {
+            // Manufacturing takes precedence over reactions for types produced by both
+            for activity in [&blueprint.activities.reaction, &blueprint.activities.manufacturing].into_iter().flatten() {
+                for product_id in activity.products.keys() {
+                    products.insert(*product_id, (blueprint.blueprintTypeID, activity));
+                }
+            }
+        }
+
+        let mut root = ProductionNode { type_id, quantity: 1, blueprint_id: None, children: Vec::new() };
+        expand(&products, &mut root, &mut Vec::new(), max_depth);
+        root
+    }
+}
+
+#[test]
+fn test_full_skill_plan() {
+    // Type 1000 requires skills 10 (level 3) and 20, skill 20 requires skill 10 at level 5
+    let type_dogma = [
+        r#"{"_key": 1000, "dogmaAttributes": [{"attributeID": 182, "value": 10}, {"attributeID": 277, "value": 3}, {"attributeID": 183, "value": 20}, {"attributeID": 278, "value": 1}]}"#,
+        r#"{"_key": 20, "dogmaAttributes": [{"attributeID": 184, "value": 0}, {"attributeID": 1285, "value": 10}, {"attributeID": 1286, "value": 5}]}"#,
+        r#"{"_key": 10, "dogmaAttributes": []}"#,
+    ].into_iter().map(|line| serde_json::from_str::<TypeDogma>(line).unwrap()).map(|dogma| (dogma.typeID, dogma)).collect();
+    let sde = SDE_Full { type_dogma, ..SDE_Full::default() };
+
+    assert_eq!(sde.skill_prerequisites(1000), [(10, 3), (20, 1)]);
+    assert_eq!(sde.skill_prerequisites(20), [(10, 5)]);
+    assert_eq!(sde.full_skill_plan(1000), [(10, 5), (20, 1)]);
+    assert!(sde.full_skill_plan(10).is_empty());
+}
+
+#[test]
+fn test_build_tree() {
+    // Type 1 is manufactured from 2 and raw material 3, type 2 is reacted from 1
+    let blueprints = [
+        r#"{"_key": 100, "blueprintTypeID": 100, "maxProductionLimit": 10, "activities": {"manufacturing": {"materials": [{"typeID": 2, "quantity": 2}, {"typeID": 3, "quantity": 5}], "products": [{"typeID": 1, "quantity": 1}], "time": 60}}}"#,
+        r#"{"_key": 200, "blueprintTypeID": 200, "maxProductionLimit": 10, "activities": {"reaction": {"materials": [{"typeID": 1, "quantity": 3}], "products": [{"typeID": 2, "quantity": 1}], "time": 60}}}"#,
+    ].into_iter().map(|line| serde_json::from_str::<Blueprint>(line).unwrap()).map(|blueprint| (blueprint.blueprintTypeID, blueprint)).collect();
+    let sde = SDE_Full { blueprints, ..SDE_Full::default() };
+    let node = |node: &ProductionNode| (node.type_id, node.quantity, node.blueprint_id, node.children.len());
+
+    let tree = sde.build_tree(1, 10);
+    assert_eq!(node(&tree), (1, 1, Some(100), 2));
+    assert_eq!(node(&tree.children[0]), (2, 2, Some(200), 1));
+    assert_eq!(node(&tree.children[1]), (3, 5, None, 0));
+    // Cycle ends at the repeated type
+    assert_eq!(node(&tree.children[0].children[0]), (1, 6, Some(100), 0));
+
+    let tree = sde.build_tree(1, 1);
+    assert_eq!(node(&tree), (1, 1, Some(100), 2));
+    assert!(tree.children.iter().all(|child| child.children.is_empty()));
+}
+
+/// Error reading or writing an [`SDE_Full`] cache file, see [`SDE_Full::save_cache`]
+#[cfg(feature = "sde_cache")]
+#[derive(Debug)]
+pub enum SDECacheError {
+    /// General IO error
+    IO(io::Error),
+    /// Cache data could not be encoded or decoded
+    Encoding(postcard::Error),
+    /// Data is not an SDE cache file
+    NotACache,
+    /// Cache was written by a different version of this library, and must be recreated from the SDE
+    Stale { version: String },
+}
+
+#[cfg(feature = "sde_cache")]
+impl Display for SDECacheError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SDECacheError::IO(err) => write!(f, "IO error: {}", err),
+            SDECacheError::Encoding(err) => write!(f, "cache encoding error: {}", err),
+            SDECacheError::NotACache => write!(f, "not an SDE cache file"),
+            SDECacheError::Stale { version } => write!(f, "stale SDE cache written by {} version {}, expected version {}", crate::CRATE_NAME, version, crate::CRATE_VERSION),
+        }
+    }
+}
+
+#[cfg(feature = "sde_cache")]
+impl Error for SDECacheError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SDECacheError::IO(err) => Some(err),
+            SDECacheError::Encoding(err) => Some(err),
+            SDECacheError::NotACache => None,
+            SDECacheError::Stale { .. } => None,
+        }
+    }
+}
+
+#[cfg(feature = "sde_cache")]
+impl From<io::Error> for SDECacheError {
+    fn from(value: io::Error) -> Self {
+        SDECacheError::IO(value)
+    }
+}
+
+#[cfg(feature = "sde_cache")]
+impl From<postcard::Error> for SDECacheError {
+    fn from(value: postcard::Error) -> Self {
+        SDECacheError::Encoding(value)
+    }
+}
+
+#[cfg(feature = "sde_cache")]
+impl SDE_Full {
+    /// Magic bytes at the start of a cache file, followed by the length-prefixed library version that wrote it
+    const CACHE_MAGIC: &'static [u8; 8] = b"EVESDE\x00\x01";
+
+    /// Write this SDE as a compact binary cache, to be loaded with [`SDE_Full::load_cache`]
+    pub fn save_cache<W: io::Write>(&self, mut writer: W) -> Result<(), SDECacheError> {
+        writer.write_all(Self::CACHE_MAGIC)?;
+        writer.write_all(&[crate::CRATE_VERSION.len() as u8])?;
+        writer.write_all(crate::CRATE_VERSION.as_bytes())?;
+        postcard::to_io(self, writer)?;
+        Ok(())
+    }
+
+    /// Load an SDE from a binary cache written by [`SDE_Full::save_cache`]
+    ///
+    /// Caches written by a different version of this library are rejected with [`SDECacheError::Stale`], as the data layout may have changed; The SDE must then be loaded through [`SDELoader`] instead.
+    pub fn load_cache<R: Read>(mut reader: R) -> Result<SDE_Full, SDECacheError> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic).map_err(|err| if err.kind() == io::ErrorKind::UnexpectedEof { SDECacheError::NotACache } else { SDECacheError::IO(err) })?;
+        if &magic != Self::CACHE_MAGIC {
+            return Err(SDECacheError::NotACache);
+        }
+
+        let mut version_len = [0u8; 1];
+        reader.read_exact(&mut version_len)?;
+        let mut version = vec![0u8; version_len[0] as usize];
+        reader.read_exact(&mut version)?;
+        if version != crate::CRATE_VERSION.as_bytes() {
+            return Err(SDECacheError::Stale { version: String::from_utf8_lossy(&version).into_owned() });
+        }
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        Ok(postcard::from_bytes(&buf)?)
+    }
+}
+
+#[cfg(feature = "sde_cache")]
+#[test]
+fn test_cache_roundtrip() {
+    let mut sde = SDE_Full::default();
+    sde.type_dogma.insert(587, TypeDogma {
+        typeID: 587,
+        dogmaAttributes: IndexMap::from([(4, 1_100_000.0), (9, 350.0)]),
+        dogmaEffects: IndexMap::from([(11, false)]),
+    });
+    sde.masteries.insert(587, MasteryInfo { lvl1: vec![1], lvl2: vec![], lvl3: vec![2, 3], lvl4: vec![], lvl5: vec![4] });
+
+    let mut buf = Vec::new();
+    sde.save_cache(&mut buf).unwrap();
+    let loaded = SDE_Full::load_cache(buf.as_slice()).unwrap();
+    assert_eq!(loaded.type_dogma[&587].dogmaAttributes, sde.type_dogma[&587].dogmaAttributes);
+    assert_eq!(loaded.type_dogma[&587].dogmaEffects, sde.type_dogma[&587].dogmaEffects);
+    assert_eq!(loaded.masteries[&587].lvl3, vec![2, 3]);
+
+    // Different library version
+    let version_offset = SDE_Full::CACHE_MAGIC.len() + 1;
+    buf[version_offset] = b'0' + (buf[version_offset] - b'0' + 1) % 10;
+    assert!(matches!(SDE_Full::load_cache(buf.as_slice()), Err(SDECacheError::Stale { .. })));
+    assert!(matches!(SDE_Full::load_cache(&b"{}"[..]), Err(SDECacheError::NotACache)));
+}
+
+/// Reverse lookup indices for [`SDE_Full`], built once with [`SDEIndices::build`]
+///
+/// Entries are listed in SDE order
+#[derive(Debug, Default)]
+pub struct SDEIndices {
+    group_types: IndexMap<ids::GroupID, Vec<ids::TypeID>>,
+    category_groups: IndexMap<ids::CategoryID, Vec<ids::GroupID>>,
+    market_group_types: IndexMap<ids::MarketGroupID, Vec<ids::TypeID>>,
+}
+
+impl SDEIndices {
+    pub fn build(sde: &SDE_Full) -> Self {
+        let mut indices = SDEIndices::default();
+        for (type_id, item_type) in &sde.types {
+            indices.group_types.entry(item_type.groupID).or_default().push(*type_id);
+            if let Some(market_group_id) = item_type.marketGroupID {
+                indices.market_group_types.entry(market_group_id).or_default().push(*type_id);
+            }
+        }
+        for (group_id, group) in &sde.groups {
+            indices.category_groups.entry(group.categoryID).or_default().push(*group_id);
+        }
+        indices
+    }
+
+    /// Types in the specified group
+    pub fn types_in_group(&self, group_id: ids::GroupID) -> &[ids::TypeID] {
+        self.group_types.get(&group_id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Groups in the specified category
+    pub fn groups_in_category(&self, category_id: ids::CategoryID) -> &[ids::GroupID] {
+        self.category_groups.get(&category_id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Types directly in the specified market group, not including types in child market groups
+    pub fn types_in_market_group(&self, market_group_id: ids::MarketGroupID) -> &[ids::TypeID] {
+        self.market_group_types.get(&market_group_id).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
+
+/// Lookup of the relationships between skins, applicable types, licenses, and materials, built once with [`SkinIndex::build`]
+#[derive(Debug, Default)]
+pub struct SkinIndex {
+    type_skins: IndexMap<ids::TypeID, Vec<ids::SkinID>>,
+    skin_licenses: IndexMap<ids::SkinID, ids::TypeID>,
+    skin_material_sets: IndexMap<ids::SkinID, ids::MaterialSetID>,
+}
+
+impl SkinIndex {
+    pub fn build(sde: &SDE_Full) -> Self {
+        let mut index = SkinIndex::default();
+        for (skin_id, skin) in &sde.skins {
+            for type_id in &skin.types {
+                index.type_skins.entry(*type_id).or_default().push(*skin_id);
+            }
+            if let Some(material) = sde.skin_materials.get(&skin.skinMaterialID) {
+                index.skin_material_sets.insert(*skin_id, material.materialSetID);
+            }
+        }
+        for license in sde.skin_licenses.values() {
+            // Prefer permanent licenses over limited-duration licenses
+            match index.skin_licenses.get(&license.skinID) {
+                Some(existing) if license.duration != -1 || sde.skin_licenses.get(existing).is_some_and(|existing| existing.duration == -1) => {}
+                _ => { index.skin_licenses.insert(license.skinID, license.typeID); }
+            }
+        }
+        index
+    }
+
+    /// Skins applicable to the specified ship (or structure) type
+    pub fn skins_for_type(&self, type_id: ids::TypeID) -> &[ids::SkinID] {
+        self.type_skins.get(&type_id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// License item type for the specified skin, if any; If a skin has multiple licenses, the permanent license is returned
+    pub fn license_for_skin(&self, skin_id: ids::SkinID) -> Option<ids::TypeID> {
+        self.skin_licenses.get(&skin_id).copied()
+    }
+
+    /// Material set of the specified skin, through the skin's [`SkinMaterial`]
+    pub fn material_set_for_skin(&self, skin_id: ids::SkinID) -> Option<ids::MaterialSetID> {
+        self.skin_material_sets.get(&skin_id).copied()
+    }
+}
+
+/// Query over the types in the SDE, created with [`SDE_Full::query`]
+///
+/// Filters are combined; Only types matching all filters are returned. Types are listed in SDE order.
+///
+/// ```ignore
+/// let frigates = sde.query().group(25).published_only().iter().collect::<Vec<_>>();
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct ItemQuery<'a> {
+    sde: &'a SDE_Full,
+    category: Option<ids::CategoryID>,
+    group: Option<ids::GroupID>,
+    market_group_subtree: Option<ids::MarketGroupID>,
+    meta_group: Option<ids::MetaGroupID>,
+    published_only: bool,
+}
+
+impl<'a> ItemQuery<'a> {
+    pub fn new(sde: &'a SDE_Full) -> Self {
+        ItemQuery { sde, category: None, group: None, market_group_subtree: None, meta_group: None, published_only: false }
+    }
+
+    /// Only types in the specified category
+    pub fn category(mut self, category_id: ids::CategoryID) -> Self {
+        self.category = Some(category_id);
+        self
+    }
+
+    /// Only types in the specified group
+    pub fn group(mut self, group_id: ids::GroupID) -> Self {
+        self.group = Some(group_id);
+        self
+    }
+
+    /// Only types in the specified market group, or any of its (transitive) child market groups
+    pub fn market_group_subtree(mut self, market_group_id: ids::MarketGroupID) -> Self {
+        self.market_group_subtree = Some(market_group_id);
+        self
+    }
+
+    /// Only types in the specified meta group
+    pub fn meta_group(mut self, meta_group_id: ids::MetaGroupID) -> Self {
+        self.meta_group = Some(meta_group_id);
+        self
+    }
+
+    /// Only published types
+    pub fn published_only(mut self) -> Self {
+        self.published_only = true;
+        self
+    }
+
+    /// Returns true if the market group is the queried market group, or one of its descendants
+    fn in_market_group_subtree(&self, market_group_id: ids::MarketGroupID, root: ids::MarketGroupID) -> bool {
+        let mut current = Some(market_group_id);
+        // Parent chains are bounded by the number of market groups, guarding against malformed (cyclic) data
+        for _ in 0..=self.sde.market_groups.len() {
+            match current {
+                Some(id) if id == root => return true,
+                Some(id) => current = self.sde.market_groups.get(&id).and_then(|group| group.parentGroupID),
+                None => return false
+            }
+        }
+        false
+    }
+
+    fn matches(&self, item_type: &Type) -> bool {
+        (!self.published_only || item_type.published)
+            && self.group.is_none_or(|group_id| item_type.groupID == group_id)
+            && self.meta_group.is_none_or(|meta_group_id| item_type.metaGroupID == Some(meta_group_id))
+            && self.category.is_none_or(|category_id| {
+                self.sde.groups.get(&item_type.groupID).is_some_and(|group| group.categoryID == category_id)
+            })
+            && self.market_group_subtree.is_none_or(|root| {
+                item_type.marketGroupID.is_some_and(|market_group_id| self.in_market_group_subtree(market_group_id, root))
+            })
+    }
+
+    /// Iterator over the matching types
+    pub fn iter(&self) -> impl Iterator<Item=(&'a ids::TypeID, &'a Type)> + use<'a> {
+        let query = *self;
+        self.sde.types.iter().filter(move |(_, item_type)| query.matches(item_type))
+    }
+}
+
+impl<'a> IntoIterator for ItemQuery<'a> {
+    type Item = (&'a ids::TypeID, &'a Type);
+    type IntoIter = Box<dyn Iterator<Item=Self::Item> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Box::new(self.iter())
+    }
+}
+
+#[test]
+fn test_item_query() {
+    let groups = [
+        r#"{"_key": 25, "anchorable": false, "anchored": false, "categoryID": 6, "fittableNonSingleton": false, "name": {"en": "Frigate"}, "published": true, "useBasePrice": false}"#,
+        r#"{"_key": 53, "anchorable": false, "anchored": false, "categoryID": 7, "fittableNonSingleton": false, "name": {"en": "Energy Weapon"}, "published": true, "useBasePrice": false}"#,
+    ].into_iter().map(|line| serde_json::from_str::<Group>(line).unwrap()).map(|group| (group.groupID, group)).collect();
+    let market_groups = [
+        r#"{"_key": 9, "hasTypes": false, "name": {"en": "Ship Equipment"}}"#,
+        r#"{"_key": 10, "hasTypes": false, "name": {"en": "Turrets & Launchers"}, "parentGroupID": 9}"#,
+        r#"{"_key": 567, "hasTypes": true, "name": {"en": "Small"}, "parentGroupID": 10}"#,
+        r#"{"_key": 61, "hasTypes": true, "name": {"en": "Frigates"}}"#,
+    ].into_iter().map(|line| serde_json::from_str::<MarketGroup>(line).unwrap()).map(|group| (group.marketGroupID, group)).collect();
+    let types = [
+        r#"{"_key": 587, "groupID": 25, "marketGroupID": 61, "metaGroupID": 1, "name": {"en": "Rifter"}, "portionSize": 1, "published": true}"#,
+        r#"{"_key": 3756, "groupID": 25, "name": {"en": "Gnosis"}, "portionSize": 1, "published": false}"#,
+        r#"{"_key": 3001, "groupID": 53, "marketGroupID": 567, "metaGroupID": 1, "name": {"en": "Small Focused Pulse Laser I"}, "portionSize": 1, "published": true}"#,
+        r#"{"_key": 3002, "groupID": 53, "marketGroupID": 567, "metaGroupID": 2, "name": {"en": "Small Focused Pulse Laser II"}, "portionSize": 1, "published": true}"#,
+    ].into_iter().map(|line| serde_json::from_str::<Type>(line).unwrap()).map(|item_type| (item_type.typeID, item_type)).collect();
+    let sde = SDE_Full { groups, market_groups, types, ..SDE_Full::default() };
+
+    let ids = |query: ItemQuery| query.into_iter().map(|(type_id, _)| *type_id).collect::<Vec<_>>();
+    assert_eq!(ids(sde.query().group(25)), [587, 3756]);
+    assert_eq!(ids(sde.query().group(25).published_only()), [587]);
+    assert_eq!(ids(sde.query().category(7)), [3001, 3002]);
+    assert_eq!(ids(sde.query().market_group_subtree(9)), [3001, 3002]);
+    assert_eq!(ids(sde.query().market_group_subtree(9).meta_group(2)), [3002]);
+    assert!(ids(sde.query().market_group_subtree(567).category(6)).is_empty());
+}
+
+/// Stargate jump network between solarsystems, built with [`JumpGraph::build`] or [`JumpGraph::build_filtered`]
+#[derive(Debug, Default)]
+pub struct JumpGraph {
+    adjacency: IndexMap<ids::SolarSystemID, Vec<ids::SolarSystemID>>,
+}
+
+impl JumpGraph {
+    /// Build the jump graph of all stargates in the SDE
+    pub fn build(sde: &SDE_Full) -> Self {
+        Self::build_filtered(sde, |_| true)
+    }
+
+    /// Build the jump graph, only including solarsystems for which `include` returns true
+    ///
+    /// E.g. `JumpGraph::build_filtered(&sde, SolarSystem::is_highsec)` for high-sec only routes
+    pub fn build_filtered<F: Fn(&SolarSystem) -> bool>(sde: &SDE_Full, include: F) -> Self {
+        let mut adjacency = IndexMap::new();
+        for (system_id, system) in &sde.map_solarsystems {
+            if include(system) {
+                adjacency.insert(*system_id, Vec::new());
+            }
+        }
+        for stargate in sde.map_stargates.values() {
+            let destination = stargate.destination.solarSystemID;
+            if adjacency.contains_key(&destination) && let Some(neighbors) = adjacency.get_mut(&stargate.solarSystemID) && !neighbors.contains(&destination) {
+                neighbors.push(destination);
+            }
+        }
+        JumpGraph { adjacency }
+    }
+
+    /// Solarsystems directly connected to the specified system by stargate
+    pub fn neighbors(&self, system_id: ids::SolarSystemID) -> &[ids::SolarSystemID] {
+        self.adjacency.get(&system_id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Number of jumps on the shortest route between two solarsystems
+    ///
+    /// returns: None if no route exists, or either system is not in this graph
+    pub fn jumps_between(&self, from: ids::SolarSystemID, to: ids::SolarSystemID) -> Option<u32> {
+        if !self.adjacency.contains_key(&from) || !self.adjacency.contains_key(&to) {
+            return None;
+        }
+
+        let mut visited = HashSet::from([from]);
+        let mut queue = VecDeque::from([(from, 0)]);
+        while let Some((system_id, jumps)) = queue.pop_front() {
+            if system_id == to {
+                return Some(jumps);
+            }
+            for neighbor in self.neighbors(system_id) {
+                if visited.insert(*neighbor) {
+                    queue.push_back((*neighbor, jumps + 1));
+                }
+            }
+        }
+        None
+    }
+}
+
+#[test]
+fn test_jump_graph() {
+    // 30000001 (high-sec) - 30000002 (low-sec) - 30000003 (high-sec), 30000004 is not connected
+    let map_solarsystems = [
+        r#"{"_key": 30000001, "constellationID": 20000001, "regionID": 10000001, "name": {"en": "System 30000001"}, "position": {"x": 0, "y": 0, "z": 0}, "radius": 1, "securityStatus": 0.9}"#,
+        r#"{"_key": 30000002, "constellationID": 20000001, "regionID": 10000001, "name": {"en": "System 30000002"}, "position": {"x": 0, "y": 0, "z": 0}, "radius": 1, "securityStatus": 0.3}"#,
+        r#"{"_key": 30000003, "constellationID": 20000001, "regionID": 10000001, "name": {"en": "System 30000003"}, "position": {"x": 0, "y": 0, "z": 0}, "radius": 1, "securityStatus": 0.7}"#,
+        r#"{"_key": 30000004, "constellationID": 20000001, "regionID": 10000001, "name": {"en": "System 30000004"}, "position": {"x": 0, "y": 0, "z": 0}, "radius": 1, "securityStatus": 1.0}"#,
+    ].into_iter().map(|line| serde_json::from_str::<SolarSystem>(line).unwrap()).map(|system| (system.solarSystemID, system)).collect();
+    let map_stargates = [
+        r#"{"_key": 50000001, "solarSystemID": 30000001, "destination": {"solarSystemID": 30000002, "stargateID": 50000002}, "position": {"x": 0, "y": 0, "z": 0}, "typeID": 16}"#,
+        r#"{"_key": 50000002, "solarSystemID": 30000002, "destination": {"solarSystemID": 30000001, "stargateID": 50000001}, "position": {"x": 0, "y": 0, "z": 0}, "typeID": 16}"#,
+        r#"{"_key": 50000003, "solarSystemID": 30000002, "destination": {"solarSystemID": 30000003, "stargateID": 50000004}, "position": {"x": 0, "y": 0, "z": 0}, "typeID": 16}"#,
+        r#"{"_key": 50000004, "solarSystemID": 30000003, "destination": {"solarSystemID": 30000002, "stargateID": 50000003}, "position": {"x": 0, "y": 0, "z": 0}, "typeID": 16}"#,
+    ].into_iter().map(|line| serde_json::from_str::<Stargate>(line).unwrap()).map(|stargate| (stargate.stargateID, stargate)).collect();
+    let sde = SDE_Full { map_solarsystems, map_stargates, ..SDE_Full::default() };
+
+    let graph = JumpGraph::build(&sde);
+    assert_eq!(graph.neighbors(30000002), [30000001, 30000003]);
+    assert_eq!(graph.jumps_between(30000001, 30000003), Some(2));
+    assert_eq!(graph.jumps_between(30000001, 30000001), Some(0));
+    assert_eq!(graph.jumps_between(30000001, 30000004), None);
+    assert_eq!(graph.jumps_between(30000001, 30000005), None);
+
+    let highsec = JumpGraph::build_filtered(&sde, SolarSystem::is_highsec);
+    assert!(highsec.neighbors(30000001).is_empty());
+    assert_eq!(highsec.jumps_between(30000001, 30000003), None);
+    assert_eq!(highsec.jumps_between(30000003, 30000003), Some(0));
+    assert_eq!(highsec.jumps_between(30000001, 30000002), None);
+}
+
+/// Node in a manufacturing dependency tree, see [`SDE_Full::build_tree`]
+#[derive(Debug, Clone)]
+pub struct ProductionNode {
+    pub type_id: ids::TypeID,
+    /// Quantity of this type required by the parent node
+    pub quantity: u64,
+    /// Manufacturing or reaction blueprint producing this type, if any
+    pub blueprint_id: Option<ids::TypeID>,
+    /// Inputs for building this type; Empty for raw materials, and for nodes that were not expanded due to cycles or depth limit
+    pub children: Vec<ProductionNode>,
+}
+
+/// Division of an NPC corporation, see [`SDE_Full::corporation_division`]
+#[derive(Debug, Copy, Clone)]
+#[allow(non_snake_case)]
+pub struct DivisionView<'a> {
+    /// Corporation this division belongs to
+    pub corporation: &'a NpcCorporation,
+    /// Global division information (name, description)
+    pub division: &'a CorporationDivision,
+    /// Division number within the corporation, starting at 1
+    pub divisionNumber: i32,
+    /// Leader of this division
+    pub leaderID: ids::CharacterID,
+    /// Leader of this division, if present in the SDE
+    pub leader: Option<&'a NpcCharacter>,
+    /// ???
+    pub size: i32,
+}
+
+/// All celestials of a solarsystem, see [`SDE_Full::system_map`]
+#[derive(Debug)]
+pub struct SystemMap<'a> {
+    pub solar_system: &'a SolarSystem,
+    /// Star of this solarsystem, named after the solarsystem
+    pub star: Option<MapCelestial<'a, Star>>,
+    /// Planets, with their moons and asteroid belts
+    pub planets: Vec<MapPlanet<'a>>,
+    pub stargates: Vec<MapCelestial<'a, Stargate>>,
+    /// NPC stations orbiting any of this solarsystem's planets or moons
+    pub stations: Vec<MapCelestial<'a, NpcStation>>,
+}
+
+/// Planet in a [`SystemMap`]
+#[derive(Debug)]
+pub struct MapPlanet<'a> {
+    pub planet: MapCelestial<'a, Planet>,
+    pub moons: Vec<MapCelestial<'a, Moon>>,
+    pub asteroid_belts: Vec<MapCelestial<'a, AsteroidBelt>>,
+}
+
+/// Celestial in a [`SystemMap`] with it's unique or generated name
+#[derive(Debug)]
+pub struct MapCelestial<'a, T> {
+    pub name: LocalizedString,
+    pub item: &'a T,
+}
+
+/// Iterator over the entries of a single JSONL file, see [`SDELoader::load_file`]
+///
+/// Skipped entries (through [`Iterator::nth`]/[`Iterator::skip`]) are read but not parsed.
+struct JsonlIter<'a, T, B: BufRead> {
+    reader: B,
+    file_name: &'a str,
+    line_buf: String,
+    /// Number of the last entry read, starting at 1
+    entry: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T, B: BufRead> JsonlIter<'a, T, B> {
+    fn new(reader: B, file_name: &'a str) -> Self {
+        JsonlIter { reader, file_name, line_buf: String::new(), entry: 0, _marker: PhantomData }
+    }
+
+    /// Read the next entry into `line_buf`, skipping blank lines; Blank lines are not counted as entries
+    ///
+    /// returns: false at the end of the file
+    fn next_line(&mut self) -> io::Result<bool> {
+        loop {
+            if self.reader.read_line(&mut self.line_buf)? == 0 {
+                return Ok(false);
+            }
+            if self.line_buf.trim().is_empty() {
+                self.line_buf.clear();
+            } else {
+                self.entry += 1;
+                return Ok(true);
+            }
+        }
+    }
+}
+
+impl<'a, T: DeserializeOwned, B: BufRead> Iterator for JsonlIter<'a, T, B> {
+    type Item = Result<T, SDELoadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_line() {
+            Ok(true) => {
+                let res = parse_entry(self.file_name, self.entry, &self.line_buf);
+                self.line_buf.clear();
+                Some(res)
+            }
+            Ok(false) => None,
+            Err(err) => Some(Err(SDELoadError::IO(err))),
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        for _ in 0..n {
+            match self.next_line() {
+                Ok(true) => self.line_buf.clear(),
+                Ok(false) => return None,
+                Err(err) => return Some(Err(SDELoadError::IO(err))),
+            }
+        }
+        self.next()
+    }
+}
+
+#[test]
+fn test_jsonl_blank_lines() {
+    let data = "{\"_key\": 1, \"name\": \"NonAgent\"}\n\n  \r\n{\"_key\": 4, \"name\": \"ResearchAgent\"}\n\n{\"_key\": 5}\n\n";
+    let entries = JsonlIter::<AgentTypeEntry, _>::new(data.as_bytes(), "agentTypes.jsonl").collect::<Vec<_>>();
+    assert_eq!(entries.len(), 3);
+    assert!(matches!(entries[0], Ok(AgentTypeEntry { agentTypeID: 1, name: AgentType::NonAgent })));
+    assert!(matches!(entries[1], Ok(AgentTypeEntry { agentTypeID: 4, name: AgentType::ResearchAgent })));
+    assert!(matches!(entries[2], Err(SDELoadError::ParseError { entry: 3, .. })));
+
+    let mut iter = JsonlIter::<AgentTypeEntry, _>::new(data.as_bytes(), "agentTypes.jsonl");
+    assert!(matches!(iter.nth(1), Some(Ok(AgentTypeEntry { agentTypeID: 4, name: AgentType::ResearchAgent }))));
+}
+
+/// Temporary directory for a test, removed when dropped so that failing tests also clean up
+#[cfg(test)]
+struct TempDir(PathBuf);
+
+#[cfg(test)]
+impl TempDir {
+    fn new(name: &str) -> io::Result<TempDir> {
+        let dir = std::env::temp_dir().join(format!("evestaticdata_{}_{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        Ok(TempDir(dir))
+    }
+}
+
+#[cfg(test)]
+impl std::ops::Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn test_load_from_dir() {
+    let dir = TempDir::new("test_load_from_dir").unwrap();
+    std::fs::write(dir.join("_sde.jsonl"), "{\"_key\": \"sde\", \"buildNumber\": 3000000, \"releaseDate\": \"2025-01-01T00:00:00Z\"}\n").unwrap();
+    std::fs::write(dir.join("agentTypes.jsonl"), "{\"_key\": 1, \"name\": \"NonAgent\"}\n{\"_key\": 4, \"name\": \"ResearchAgent\"}\n").unwrap();
+
+    let mut loader = SDELoader::from_dir(&*dir).unwrap();
+    let agent_types = loader.load_agent_types_map();
+    let missing = loader.load_agents_in_space_map();
+
+    assert_eq!(loader.version(), 3000000);
+    assert_eq!(agent_types.unwrap(), IndexMap::from([(1, AgentType::NonAgent), (4, AgentType::ResearchAgent)]));
+    assert!(matches!(missing, Err(SDELoadError::ArchiveFileNotFound(file)) if file == "agentsInSpace.jsonl"));
+}
+
+/// Location of the SDE files read by [`SDELoader`]
+#[derive(Clone)]
+enum SDESource<R: Read + Seek> {
+    /// SDE zip archive
+    Zip(ZipArchive<R>),
+    /// Directory containing the extracted `.jsonl` files
+    Directory(PathBuf),
+}
+
 // SDELoader encapsulates ZipArchive & zip crate dependency
 pub struct SDELoader<R: Read + Seek = File> {
-    archive: ZipArchive<R>,
+    source: SDESource<R>,
     build_number: u32
 }
 
@@ -3966,14 +6022,26 @@ impl SDELoader<File> {
         crate::sde::update::update_sde(file.as_ref())?;
         Self::new(File::open(file)?)
     }
+
+    /// Opens a directory containing the extracted SDE `.jsonl` files
+    ///
+    /// Files are parsed the same as from the SDE zip; Useful during development, when the SDE is kept extracted.
+    ///
+    /// ```ignore
+    /// let sde = SDELoader::from_dir("./sde")?.full()?;
+    /// ```
+    pub fn from_dir<P: AsRef<Path>>(dir: P) -> Result<Self, SDELoadError> {
+        Self::with_source(SDESource::Directory(dir.as_ref().to_path_buf()))
+    }
 }
 
 impl<R: Read + Seek> SDELoader<R> {
     pub fn new(reader: R) -> Result<Self, SDELoadError> {
-        let mut loader = SDELoader {
-            archive: ZipArchive::new(reader)?,
-            build_number: 0
-        };
+        Self::with_source(SDESource::Zip(ZipArchive::new(reader)?))
+    }
+
+    fn with_source(source: SDESource<R>) -> Result<Self, SDELoadError> {
+        let mut loader = SDELoader { source, build_number: 0 };
 
         #[derive(Deserialize)]
         #[allow(non_snake_case, unused)]
@@ -3995,36 +6063,34 @@ impl<R: Read + Seek> SDELoader<R> {
         self.build_number
     }
 
-    /// Load a single file from the zip archive, and parse it to a datatype
+    /// Load a single file from the zip archive or directory, and parse it to a datatype
     ///
     /// Returns an iterator over each entry
     fn load_file<'a, T: DeserializeOwned>(&'a mut self, file_name: &'a str) -> Result<impl Iterator<Item=Result<T, SDELoadError>> + use<'a, T, R>, SDELoadError> {
-        let mut str_buf = String::new();
-        let mut reader = BufReader::new(
-            self.archive
-                .by_name(file_name)
-                .map_err(|err| {
-                    if let ZipError::FileNotFound = err {
-                        SDELoadError::ArchiveFileNotFound(file_name.to_owned())
-                    } else {
-                        SDELoadError::Zip(err)
-                    }
-                })?
-        );
-
-        let mut entry = 0;
-        Ok(std::iter::from_fn(move || { // TODO: Replace with a proper custom iterator implementation to provide better support for skip/nth
-            match reader.read_line(&mut str_buf) {
-                Ok(0) => None,
-                Ok(_) => {
-                    entry += 1;
-                    let res = serde_json::from_str::<T>(&str_buf).map_err(|error| SDELoadError::ParseError { file: file_name.to_owned(), entry, error });
-                    str_buf.clear();
-                    Some(res)
-                }
-                Err(err) => Some(Err(SDELoadError::IO(err))),
-            }
-        }))
+        let file: Box<dyn Read + 'a> = match &mut self.source {
+            SDESource::Zip(archive) => Box::new(
+                archive.by_name(file_name)
+                    .map_err(|err| {
+                        if let ZipError::FileNotFound = err {
+                            SDELoadError::ArchiveFileNotFound(file_name.to_owned())
+                        } else {
+                            SDELoadError::Zip(err)
+                        }
+                    })?
+            ),
+            SDESource::Directory(dir) => Box::new(
+                File::open(dir.join(file_name))
+                    .map_err(|err| {
+                        if err.kind() == io::ErrorKind::NotFound {
+                            SDELoadError::ArchiveFileNotFound(file_name.to_owned())
+                        } else {
+                            SDELoadError::IO(err)
+                        }
+                    })?
+            ),
+        };
+
+        Ok(JsonlIter::new(BufReader::new(file), file_name))
     }
 
     /// Load 'agentTypes' as iterator
@@ -4714,6 +6780,9 @@ impl<R: Read + Seek> SDELoader<R> {
         self.load_types()?.collect()
     }
 
+    /// Load all files in the SDE
+    ///
+    /// Loading the map files (`map*.jsonl`) makes up the bulk of the load time; Consumers that only need part of the SDE should use the individual `load_*` or `load_*_map` functions instead.
     pub fn full(&mut self) -> Result<SDE_Full, SDELoadError> {
         Ok(SDE_Full {
             agent_types: self.load_agent_types_map()?,
@@ -4784,4 +6853,215 @@ impl<R: Read + Seek> SDELoader<R> {
             types: self.load_types_map()?,
         })
     }
-}
\ No newline at end of file
+
+    /// Load a selection of files in the SDE, see [`SDESelection`]
+    ///
+    /// ```ignore
+    /// let sde = loader.select().with_types().with_groups().with_categories().load()?;
+    /// ```
+    pub fn select(&mut self) -> SDESelection<'_, R> {
+        SDESelection::new(self)
+    }
+}
+
+#[cfg(feature = "sde_load_parallel")]
+impl<R: Read + Seek + Clone + Send> SDELoader<R> {
+    /// Load all files in the SDE, decoding files concurrently on the rayon thread pool
+    ///
+    /// Each file is read through its own clone of the underlying archive, which shares the parsed zip directory; The reader should be cheap to clone, such as a `Cursor` over a borrowed slice or `Arc<[u8]>` of the SDE zip.
+    /// Use [`SDELoader::full`] for readers that cannot be cloned, such as [`File`]
+    pub fn full_parallel(&self) -> Result<SDE_Full, SDELoadError> {
+        macro_rules! load_parallel {
+            ($($field:ident: $load:ident),* $(,)?) => {{
+                $(let mut $field = None;)*
+                // Clone a loader per file up front, so the scope doesn't borrow `self` and `R` needn't be `Sync`
+                $(let mut $load = SDELoader { source: self.source.clone(), build_number: self.build_number };)*
+                rayon::scope(|scope| {
+                    $({
+                        let slot = &mut $field;
+                        scope.spawn(move |_| *slot = Some($load.$load()));
+                    })*
+                });
+                // Slots are always filled once the scope has returned
+                Ok(SDE_Full {
+                    $($field: $field.expect("rayon scope completed")?,)*
+                })
+            }};
+        }
+
+        load_parallel!(
+            agent_types: load_agent_types_map,
+            agents_in_space: load_agents_in_space_map,
+            ancestries: load_ancestries_map,
+            archetypes: load_archetypes_map,
+            bloodlines: load_bloodlines_map,
+            blueprints: load_blueprints_map,
+            categories: load_categories_map,
+            certificates: load_certificates_map,
+            character_attributes: load_character_attributes_map,
+            character_titles: load_character_titles_map,
+            clone_grades: load_clone_grades_map,
+            compressible_types: load_compressible_types_map,
+            contraband_types: load_contraband_types_map,
+            control_tower_resources: load_controltower_resources_map,
+            corporation_activities: load_corporation_activities_map,
+            dbuff_collections: load_dbuff_collections_map,
+            dogma_attribute_categories: load_dogma_attribute_categories_map,
+            dogma_attributes: load_dogma_attributes_map,
+            dogma_effects: load_dogma_effects_map,
+            dogma_units: load_dogma_units_map,
+            dungeons: load_dungeons_map,
+            dynamic_item_attributes: load_dynamic_item_attributes_map,
+            factions: load_factions_map,
+            freelance_job_schemas: load_freelance_job_schemas_map,
+            graphics: load_graphics_map,
+            groups: load_groups_map,
+            icons: load_icons_map,
+            landmarks: load_landmarks_map,
+            map_asteroid_belts: load_asteroid_belts_map,
+            map_constellations: load_constellations_map,
+            map_moons: load_moons_map,
+            map_planets: load_planets_map,
+            map_regions: load_regions_map,
+            map_secondarysuns: load_secondarysuns_map,
+            map_solarsystems: load_solarsystems_map,
+            map_stargates: load_stargates_map,
+            map_stars: load_stars_map,
+            market_groups: load_market_groups_map,
+            masteries: load_masteries_map,
+            mercenary_tactical_operations: load_merc_tactical_operations_map,
+            meta_groups: load_meta_groups_map,
+            military_campaigns: load_military_campaigns_map,
+            military_campaign_objectives: load_military_campaign_objectives_map,
+            missions: load_missions_map,
+            npc_characters: load_npc_characters_map,
+            npc_corporation_divisions: load_npc_corporation_divisions_map,
+            npc_corporations: load_npc_corporations_map,
+            npc_stations: load_npc_stations_map,
+            planet_resources: load_planet_resources_map,
+            planet_schematics: load_planet_schematics_map,
+            races: load_races_map,
+            ship_tree_elements: load_ship_tree_elements_map,
+            ship_tree_factions: load_ship_tree_factions_map,
+            ship_tree_groups: load_ship_tree_groups_map,
+            skin_licenses: load_skin_licenses_map,
+            skin_materials: load_skin_materials_map,
+            skins: load_skins_map,
+            sovereignty_upgrades: load_sovereignty_upgrades_map,
+            station_operations: load_station_operations_map,
+            station_services: load_station_services_map,
+            translation_languages: load_translation_languages_list,
+            type_bonus: load_type_bonuses_map,
+            type_dogma: load_type_dogma_map,
+            type_lists: load_type_lists_map,
+            type_materials: load_type_materials_map,
+            types: load_types_map
+        )
+    }
+}
+
+macro_rules! sde_selection {
+    ($($field:ident: $with:ident => $load:ident),* $(,)?) => {
+        /// Builder to load only part of the SDE into [`SDE_Full`], created by [`SDELoader::select`]
+        ///
+        /// Files that are not selected are not read, and left empty in the resulting `SDE_Full`
+        pub struct SDESelection<'a, R: Read + Seek = File> {
+            loader: &'a mut SDELoader<R>,
+            $($field: bool,)*
+        }
+
+        impl<'a, R: Read + Seek> SDESelection<'a, R> {
+            fn new(loader: &'a mut SDELoader<R>) -> Self {
+                SDESelection { loader, $($field: false,)* }
+            }
+
+            $(
+                #[doc = concat!("Select `", stringify!($field), "` to be loaded")]
+                pub fn $with(mut self) -> Self {
+                    self.$field = true;
+                    self
+                }
+            )*
+
+            /// Load the selected files
+            pub fn load(self) -> Result<SDE_Full, SDELoadError> {
+                let mut sde = SDE_Full::default();
+                $(
+                    if self.$field {
+                        sde.$field = self.loader.$load()?;
+                    }
+                )*
+                Ok(sde)
+            }
+        }
+    };
+}
+
+sde_selection!(
+    agent_types: with_agent_types => load_agent_types_map,
+    agents_in_space: with_agents_in_space => load_agents_in_space_map,
+    ancestries: with_ancestries => load_ancestries_map,
+    archetypes: with_archetypes => load_archetypes_map,
+    bloodlines: with_bloodlines => load_bloodlines_map,
+    blueprints: with_blueprints => load_blueprints_map,
+    categories: with_categories => load_categories_map,
+    certificates: with_certificates => load_certificates_map,
+    character_attributes: with_character_attributes => load_character_attributes_map,
+    character_titles: with_character_titles => load_character_titles_map,
+    clone_grades: with_clone_grades => load_clone_grades_map,
+    compressible_types: with_compressible_types => load_compressible_types_map,
+    contraband_types: with_contraband_types => load_contraband_types_map,
+    control_tower_resources: with_control_tower_resources => load_controltower_resources_map,
+    corporation_activities: with_corporation_activities => load_corporation_activities_map,
+    dbuff_collections: with_dbuff_collections => load_dbuff_collections_map,
+    dogma_attribute_categories: with_dogma_attribute_categories => load_dogma_attribute_categories_map,
+    dogma_attributes: with_dogma_attributes => load_dogma_attributes_map,
+    dogma_effects: with_dogma_effects => load_dogma_effects_map,
+    dogma_units: with_dogma_units => load_dogma_units_map,
+    dungeons: with_dungeons => load_dungeons_map,
+    dynamic_item_attributes: with_dynamic_item_attributes => load_dynamic_item_attributes_map,
+    factions: with_factions => load_factions_map,
+    freelance_job_schemas: with_freelance_job_schemas => load_freelance_job_schemas_map,
+    graphics: with_graphics => load_graphics_map,
+    groups: with_groups => load_groups_map,
+    icons: with_icons => load_icons_map,
+    landmarks: with_landmarks => load_landmarks_map,
+    map_asteroid_belts: with_map_asteroid_belts => load_asteroid_belts_map,
+    map_constellations: with_map_constellations => load_constellations_map,
+    map_moons: with_map_moons => load_moons_map,
+    map_planets: with_map_planets => load_planets_map,
+    map_regions: with_map_regions => load_regions_map,
+    map_secondarysuns: with_map_secondarysuns => load_secondarysuns_map,
+    map_solarsystems: with_map_solarsystems => load_solarsystems_map,
+    map_stargates: with_map_stargates => load_stargates_map,
+    map_stars: with_map_stars => load_stars_map,
+    market_groups: with_market_groups => load_market_groups_map,
+    masteries: with_masteries => load_masteries_map,
+    mercenary_tactical_operations: with_mercenary_tactical_operations => load_merc_tactical_operations_map,
+    meta_groups: with_meta_groups => load_meta_groups_map,
+    military_campaigns: with_military_campaigns => load_military_campaigns_map,
+    military_campaign_objectives: with_military_campaign_objectives => load_military_campaign_objectives_map,
+    missions: with_missions => load_missions_map,
+    npc_characters: with_npc_characters => load_npc_characters_map,
+    npc_corporation_divisions: with_npc_corporation_divisions => load_npc_corporation_divisions_map,
+    npc_corporations: with_npc_corporations => load_npc_corporations_map,
+    npc_stations: with_npc_stations => load_npc_stations_map,
+    planet_resources: with_planet_resources => load_planet_resources_map,
+    planet_schematics: with_planet_schematics => load_planet_schematics_map,
+    races: with_races => load_races_map,
+    ship_tree_elements: with_ship_tree_elements => load_ship_tree_elements_map,
+    ship_tree_factions: with_ship_tree_factions => load_ship_tree_factions_map,
+    ship_tree_groups: with_ship_tree_groups => load_ship_tree_groups_map,
+    skin_licenses: with_skin_licenses => load_skin_licenses_map,
+    skin_materials: with_skin_materials => load_skin_materials_map,
+    skins: with_skins => load_skins_map,
+    sovereignty_upgrades: with_sovereignty_upgrades => load_sovereignty_upgrades_map,
+    station_operations: with_station_operations => load_station_operations_map,
+    station_services: with_station_services => load_station_services_map,
+    translation_languages: with_translation_languages => load_translation_languages_list,
+    type_bonus: with_type_bonus => load_type_bonuses_map,
+    type_dogma: with_type_dogma => load_type_dogma_map,
+    type_lists: with_type_lists => load_type_lists_map,
+    type_materials: with_type_materials => load_type_materials_map,
+    types: with_types => load_types_map
+);
\ No newline at end of file
@@ -8,15 +8,18 @@ pub mod diff {
     use std::cmp::Ordering;
     use std::error::Error;
     use std::fs::File;
+    use std::hash::Hash;
     use std::io;
     use std::io::{BufRead, BufReader, Bytes, Read, Write};
     use std::path::Path;
-    use indexmap::IndexSet;
+    use indexmap::{IndexMap, IndexSet};
     use json_patch::Patch;
     use serde::Serialize;
     use serde_json::Value;
     use zip::{ZipArchive, ZipWriter};
     use zip::write::FileOptions;
+    use crate::sde::load::SDE_Full;
+    use crate::types::ids;
 
     pub fn build_diff<P1: AsRef<Path>, P2: AsRef<Path>, OP: AsRef<Path>>(current: P1, previous: P2, out: OP) -> Result<(), Box<dyn Error>> {
         let mut current = ZipArchive::new(File::open(current)?)?;
@@ -153,6 +156,38 @@ pub mod diff {
 
         Ok(())
     }
+
+    /// Added, removed, and changed entries between two versions of an SDE map, see [`diff_entries`]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct EntryDiff<K> {
+        /// Keys present only in the new version
+        pub added: Vec<K>,
+        /// Keys present only in the old version
+        pub removed: Vec<K>,
+        /// Keys present in both versions, with unequal values
+        pub changed: Vec<K>,
+    }
+
+    pub type TypeDiff = EntryDiff<ids::TypeID>;
+
+    /// Compare two versions of an SDE map; Keys are listed in the order of the map they are taken from
+    pub fn diff_entries<K: Copy + Hash + Eq, V: PartialEq>(old: &IndexMap<K, V>, new: &IndexMap<K, V>) -> EntryDiff<K> {
+        let mut diff = EntryDiff { added: Vec::new(), removed: Vec::new(), changed: Vec::new() };
+        for (key, new_value) in new {
+            match old.get(key) {
+                Some(old_value) if old_value != new_value => diff.changed.push(*key),
+                Some(_) => {}
+                None => diff.added.push(*key)
+            }
+        }
+        diff.removed.extend(old.keys().filter(|key| !new.contains_key(*key)));
+        diff
+    }
+
+    /// Compare the types of two loaded SDEs; Any difference in a [`Type`](crate::sde::load::Type)'s fields marks it as changed
+    pub fn diff_types(old: &SDE_Full, new: &SDE_Full) -> TypeDiff {
+        diff_entries(&old.types, &new.types)
+    }
 }
 
 #[allow(non_snake_case)]
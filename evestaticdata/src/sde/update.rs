@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::ops::Range;
 use std::path::Path;
 use std::{fs, io};
 use std::fmt::{Display, Formatter};
@@ -79,10 +81,7 @@ impl SdeVersion {
     }
 
     pub fn download_sde<P: AsRef<Path>>(&self, file: P) -> Result<SdeVersion, io::Error> {
-        reqwest::blocking::get(self.sde_url()).map_err(io::Error::other)?
-            .copy_to(&mut File::create(&file)?).map(|_| ()).map_err(io::Error::other)?;
-
-        SdeVersion::from_sde_zip(file)
+        download_verified(&self.sde_url(), file.as_ref(), Some(self.build_number()))
     }
 }
 
@@ -119,19 +118,254 @@ pub struct ChangeLogEntry {
     removed: Vec<u32>,
 }
 
+/// Download an SDE zip to a temporary file next to `file`, and only move it into place once it has been verified
+///
+/// The download must match the response's `Content-Length` (if any), be a valid zip whose entries all pass their CRC check, and have the expected build number (if any).
+/// On failure, `file` is left untouched.
+fn download_verified(url: &str, file: &Path, expected_build: Option<u32>) -> Result<SdeVersion, io::Error> {
+    let mut temp_name = file.as_os_str().to_owned();
+    temp_name.push(".tmp");
+    let temp_file = std::path::PathBuf::from(temp_name);
+
+    let result = (|| {
+        let mut response = reqwest::blocking::get(url).map_err(io::Error::other)?.error_for_status().map_err(io::Error::other)?;
+        let expected_len = response.content_length();
+        let written = response.copy_to(&mut File::create(&temp_file)?).map_err(io::Error::other)?;
+        if let Some(expected_len) = expected_len && written != expected_len {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, format!("incomplete SDE download: {} of {} bytes", written, expected_len)));
+        }
+
+        // Reading each entry to the end verifies its CRC
+        let mut archive = ZipArchive::new(File::open(&temp_file)?).map_err(io::Error::other)?;
+        for index in 0..archive.len() {
+            io::copy(&mut archive.by_index(index).map_err(io::Error::other)?, &mut io::sink())?;
+        }
+
+        let version = SdeVersion::from_sde_zip(&temp_file)?;
+        if let Some(expected_build) = expected_build && version.build_number() != expected_build {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("downloaded SDE has build number {}, expected {}", version.build_number(), expected_build)));
+        }
+        Ok(version)
+    })();
+
+    match result {
+        Ok(version) => {
+            fs::rename(&temp_file, file)?;
+            Ok(version)
+        }
+        Err(err) => {
+            let _ = fs::remove_file(&temp_file);
+            Err(err)
+        }
+    }
+}
+
+/// Download the latest SDE to the specified file
+///
+/// The download is verified before it replaces any existing file
 pub fn download_latest_sde<P: AsRef<Path>>(file: P) -> Result<SdeVersion, io::Error> {
-    reqwest::blocking::get(SDE_URL).map_err(io::Error::other)?
-        .copy_to(&mut File::create(&file)?).map(|_| ()).map_err(io::Error::other)?;
+    download_verified(SDE_URL, file.as_ref(), None)
+}
 
-    SdeVersion::from_sde_zip(file)
+/// Checks whether a newer version of the SDE is available than the one at the specified file, without downloading it
+///
+/// returns: The latest version if it is newer than the local file (or the file does not exist), `None` if the local file is up-to-date
+pub fn check_update<P: AsRef<Path>>(file: P) -> Result<Option<SdeVersion>, io::Error> {
+    let current = SdeVersion::try_sde_zip(&file)?;
+    let latest = SdeVersion::fetch_latest()?;
+    if current.build_number() < latest.build_number() {
+        Ok(Some(latest))
+    } else {
+        Ok(None)
+    }
 }
 
 pub fn update_sde<P: AsRef<Path>>(file: P) -> Result<SdeVersion, io::Error> {
-    let current @ SdeVersion::sde { buildNumber: current_version, .. } = SdeVersion::try_sde_zip(&file)?;
-    let SdeVersion::sde { buildNumber: latest, .. } = SdeVersion::fetch_latest()?;
-    if current_version < latest {
-        download_latest_sde(file)
-    } else {
-        Ok(current)
+    match check_update(&file)? {
+        Some(latest) => latest.download_sde(file),
+        None => SdeVersion::from_sde_zip(file)
     }
 }
+
+/// Minimum size of the HTTP requests made by [`HttpRangeReader`]'s `Read` implementation
+const RANGE_READ_AHEAD: u64 = 64 * 1024;
+
+/// Reads a remote file with HTTP `Range` requests, without downloading it whole
+///
+/// Implements `Read` and `Seek`, so a remote SDE zip can be opened with e.g. [`ZipArchive`], only downloading the central directory and the files that are read.
+/// Sequential reads are buffered in blocks of at least 64KiB.
+#[derive(Debug)]
+pub struct HttpRangeReader {
+    client: reqwest::blocking::Client,
+    url: String,
+    len: u64,
+    position: u64,
+    /// Read-ahead buffer, holding the bytes starting at `buffer_start`
+    buffer: Vec<u8>,
+    buffer_start: u64,
+}
+
+impl HttpRangeReader {
+    /// Opens a remote file, requesting its length with a `HEAD` request
+    ///
+    /// Fails with [`io::ErrorKind::Unsupported`] if the server does not support byte range requests
+    pub fn new<S: Into<String>>(url: S) -> Result<HttpRangeReader, io::Error> {
+        let url = url.into();
+        let client = reqwest::blocking::Client::new();
+        let response = client.head(&url).send().map_err(io::Error::other)?.error_for_status().map_err(io::Error::other)?;
+
+        let accepts_ranges = response.headers().get(reqwest::header::ACCEPT_RANGES)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.split(',').any(|unit| unit.trim() == "bytes"));
+        if !accepts_ranges {
+            return Err(io::Error::new(io::ErrorKind::Unsupported, format!("server does not support range requests for {}", url)));
+        }
+        let len = response.headers().get(reqwest::header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("missing Content-Length for {}", url)))?;
+
+        Ok(HttpRangeReader { client, url, len, position: 0, buffer: Vec::new(), buffer_start: 0 })
+    }
+
+    /// Opens the remote SDE zip of the latest version
+    pub fn latest_sde() -> Result<HttpRangeReader, io::Error> {
+        HttpRangeReader::new(SDE_URL)
+    }
+
+    /// Length of the remote file, in bytes
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Reads a range of bytes with a single request; The range is truncated to the length of the file
+    pub fn read_range(&self, range: Range<u64>) -> Result<Vec<u8>, io::Error> {
+        let range = range.start.min(self.len)..range.end.min(self.len);
+        if range.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let response = self.client.get(&self.url)
+            .header(reqwest::header::RANGE, format!("bytes={}-{}", range.start, range.end - 1))
+            .send().map_err(io::Error::other)?
+            .error_for_status().map_err(io::Error::other)?;
+        // Servers that ignore the range would send the entire file
+        if response.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+            return Err(io::Error::new(io::ErrorKind::Unsupported, format!("range request not honoured for {}", self.url)));
+        }
+        let bytes = response.bytes().map_err(io::Error::other)?;
+        if bytes.len() as u64 != range.end - range.start {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, format!("expected {} bytes, received {}", range.end - range.start, bytes.len())));
+        }
+        Ok(bytes.to_vec())
+    }
+}
+
+impl Read for HttpRangeReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position >= self.len || buf.is_empty() {
+            return Ok(0);
+        }
+        let buffer_end = self.buffer_start + self.buffer.len() as u64;
+        if self.position < self.buffer_start || self.position >= buffer_end {
+            let request_len = u64::max(buf.len() as u64, RANGE_READ_AHEAD);
+            self.buffer = self.read_range(self.position..self.position.saturating_add(request_len))?;
+            self.buffer_start = self.position;
+        }
+
+        let offset = (self.position - self.buffer_start) as usize;
+        let count = usize::min(buf.len(), self.buffer.len() - offset);
+        buf[..count].copy_from_slice(&self.buffer[offset..offset + count]);
+        self.position += count as u64;
+        Ok(count)
+    }
+}
+
+impl Seek for HttpRangeReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => self.len.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+        };
+        self.position = position.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "seek to a negative or overflowing position"))?;
+        Ok(self.position)
+    }
+}
+
+/// Handle to a task spawned by [`spawn_auto_update`]
+#[cfg(feature="sde_update_async")]
+#[derive(Debug)]
+pub struct AutoUpdateHandle {
+    task: tokio::task::JoinHandle<()>
+}
+
+#[cfg(feature="sde_update_async")]
+impl AutoUpdateHandle {
+    /// Stop the update loop
+    ///
+    /// The loop is aborted at its next `await`; a download or `on_update` call already running on the blocking thread pool still runs to completion
+    pub fn cancel(&self) {
+        self.task.abort();
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+}
+
+/// Periodically updates the SDE at the specified file, calling `on_update` whenever a new version is available
+///
+/// `on_update` is also called on the first successful check, with the version already present or just downloaded.
+/// Downloads and `on_update` run on tokio's blocking thread pool, so `on_update` may block.
+/// Errors, including those returned by `on_update`, are logged to stderr and retried at the next interval.
+///
+/// Must be called from within a tokio runtime.
+///
+/// # Arguments
+///
+/// * `file`: SDE zip file to keep up to date
+/// * `interval`: Time between update checks
+/// * `on_update`: Called with the new version of the SDE after it has been written to `file`; If it returns an error, it is called again for the same version at the next interval
+///
+/// returns: AutoUpdateHandle
+#[cfg(feature="sde_update_async")]
+pub fn spawn_auto_update<P, F>(file: P, interval: std::time::Duration, on_update: F) -> AutoUpdateHandle
+where
+    P: Into<std::path::PathBuf>,
+    F: Fn(SdeVersion) -> Result<(), Box<dyn std::error::Error + Send + Sync>> + Send + Sync + 'static
+{
+    let file = file.into();
+    let on_update = std::sync::Arc::new(on_update);
+
+    let task = tokio::spawn(async move {
+        let mut interval = tokio::time::interval(interval);
+        let mut last_build = None;
+        loop {
+            interval.tick().await;
+
+            let file = file.clone();
+            let on_update = on_update.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                let version = update_sde(&file)?;
+                let build_number = version.build_number();
+                if last_build != Some(build_number) {
+                    on_update(version).map_err(io::Error::other)?;
+                }
+                Ok::<u32, io::Error>(build_number)
+            }).await;
+
+            match result {
+                Ok(Ok(build_number)) => last_build = Some(build_number),
+                Ok(Err(err)) => eprintln!("SDE update failed: {}", err),
+                Err(err) => eprintln!("SDE update task failed: {}", err),
+            }
+        }
+    });
+
+    AutoUpdateHandle { task }
+}
@@ -44,6 +44,8 @@ pub mod ids {
     pub type TypeListID = u32;
     pub type EpicArcID = u32;
     pub type MissionID = u32;
+    pub type NebulaID = u32;
+    pub type LPOfferTableID = u32;
 
     pub type ShipTreeElementID = u32;
     pub type ShipTreeGroupID = u32;
@@ -71,7 +73,7 @@ pub mod uuids {
 
     #[allow(non_camel_case_types)]
     #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
-    #[cfg_attr(feature="serde", derive(serde::Deserialize))]  // TODO: Make configurable depending on load feature
+    #[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]  // TODO: Make configurable depending on load feature
     pub struct EVE_UUID(
         #[cfg_attr(feature="serde", serde(with = "uuid::serde::hyphenated"))]
         pub Uuid
@@ -91,4 +93,14 @@ pub mod values {
 
     /// SharedCache resource
     pub type CacheResource = String;
+
+    /// EVE Online game server
+    ///
+    /// Mirrors `evesharedcache::cache::GameServer`, as this crate does not depend on the sharedcache crate
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+    pub enum GameServer {
+        Tranquility,
+        Singularity,
+        Serenity
+    }
 }
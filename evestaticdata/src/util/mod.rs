@@ -1,8 +1,33 @@
 pub mod item_list;
 
 pub mod user_agent {
+    use std::error::Error;
+    use std::fmt::{Display, Formatter};
     use std::ops::Deref;
 
+    /// User Agent does not meet CCP's policy for third party tools
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub enum UaPolicyError {
+        /// User Agent is empty
+        Empty,
+        /// User Agent contains no contact information (email address, URL, or `kind:value` contact)
+        NoContact,
+        /// User Agent does not start with an `app/version` product token
+        NoProduct,
+    }
+
+    impl Display for UaPolicyError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                UaPolicyError::Empty => write!(f, "User Agent is empty"),
+                UaPolicyError::NoContact => write!(f, "User Agent must include contact information (email address, URL, or `kind:value` contact)"),
+                UaPolicyError::NoProduct => write!(f, "User Agent must start with the application name and version (`app/version`)"),
+            }
+        }
+    }
+
+    impl Error for UaPolicyError {}
+
     pub struct UserAgent(String);
     impl Deref for UserAgent {
         type Target = str;
@@ -21,8 +46,62 @@ pub mod user_agent {
                 comments: Vec::new(),
             }
         }
+
+        /// Checks a User Agent string against CCP's policy; It must be non-empty, and contain contact information in the form of an email address, URL, or `kind:value` pair of a [`CONTACT_KINDS`] kind
+        ///
+        /// Library repository links (`+https://...`) are not counted as contact information.
+        /// This is a basic sanity check, it does not validate whether the contact information is real
+        pub fn validate(user_agent: &str) -> Result<(), UaPolicyError> {
+            if user_agent.trim().is_empty() { return Err(UaPolicyError::Empty); }
+
+            let has_contact = user_agent.split(|c: char| c.is_whitespace() || matches!(c, '(' | ')' | ';' | ',' | '<' | '>'))
+                .filter(|token| !token.starts_with('+'))
+                .any(|token| {
+                    let is_email = token.split_once('@').is_some_and(|(user, domain)| !user.is_empty() && domain.contains('.') && !domain.ends_with('.'));
+                    let is_url = token.contains("http://") || token.contains("https://");  // Also matches `kind:https://` forms
+                    is_email || is_url || is_contact_kind(token)
+                });
+
+            if has_contact { Ok(()) } else { Err(UaPolicyError::NoContact) }
+        }
+
+        /// Parses an existing User Agent string, checking it against CCP's format of `app/version (contacts) lib/version (+repo)`
+        ///
+        /// The string must start with an `app/version` product token, and meet [`UserAgent::validate`]; Library tokens are optional
+        pub fn parse(user_agent: &str) -> Result<UserAgent, UaPolicyError> {
+            let user_agent = user_agent.trim();
+            if user_agent.is_empty() { return Err(UaPolicyError::Empty); }
+
+            let product = user_agent.split_whitespace().next().unwrap_or("");
+            let is_product = product.split_once('/').is_some_and(|(app, version)| !app.is_empty() && !version.is_empty() && !app.contains(['(', ')']));
+            if !is_product { return Err(UaPolicyError::NoProduct); }
+
+            UserAgent::validate(user_agent)?;
+            Ok(UserAgent(user_agent.to_string()))
+        }
+
+        /// Contact entries of this User Agent, the `;`-separated entries in parentheses that contain an email address, URL, or `kind:value` pair of a [`CONTACT_KINDS`] kind
+        ///
+        /// Library repository links (`+https://...`) are not included
+        pub fn contacts(&self) -> Vec<&str> {
+            self.0.split('(')
+                .skip(1)
+                .filter_map(|group| group.split_once(')').map(|(contents, _)| contents))
+                .flat_map(|contents| contents.split(';'))
+                .map(str::trim)
+                .filter(|entry| is_contact_kind(entry) || UserAgent::validate(entry).is_ok())
+                .collect()
+        }
+    }
+
+    /// Whether `entry` is a `kind:value` contact with a [`CONTACT_KINDS`] kind
+    fn is_contact_kind(entry: &str) -> bool {
+        entry.split_once(':').is_some_and(|(kind, value)| CONTACT_KINDS.contains(&kind) && !value.is_empty())
     }
     
+    /// Contact kinds recommended for third party tool User Agents, see [`UABuilder::contact`]
+    pub const CONTACT_KINDS: &[&str] = &["email", "name", "discord", "eve", "url"];
+
     pub struct UABuilder {
         app_name: String,
         app_version: String,
@@ -31,8 +110,23 @@ pub mod user_agent {
     }
     
     impl UABuilder {
+        /// Add contact information, formatted as `kind:value`
+        ///
+        /// `kind` must be one of [`CONTACT_KINDS`], e.g. `email` or `discord`; Other kinds are rejected by [`UABuilder::build`]
+        pub fn contact(mut self, kind: &str, value: &str) -> Self {
+            self.contacts.push((kind.to_string(), value.to_string()));
+            self
+        }
+
+        /// Add a free-form comment, such as a description of the tool
+        pub fn comment(mut self, text: &str) -> Self {
+            self.comments.push(text.to_string());
+            self
+        }
+
         pub fn build(self) -> Result<UserAgent, &'static str> {
             if self.contacts.len() == 0 { return Err("Must have at least 1 contact") }
+            if self.contacts.iter().any(|(kind, _)| !CONTACT_KINDS.contains(&&**kind)) { return Err("Contact kind must be one of `email`, `name`, `discord`, `eve` or `url`") }
 
             use std::fmt::Write;
             let mut buf = String::new();
@@ -45,7 +139,7 @@ pub mod user_agent {
             buf.push_str(") ");
             
             if self.comments.len() > 0 {
-                first = false;
+                first = true;
                 buf.push('(');
 
                 for comment in self.comments {
@@ -60,6 +154,36 @@ pub mod user_agent {
             Ok(UserAgent(buf))
         }
     }
+
+    #[test]
+    fn test_builder() {
+        let user_agent = UserAgent::new("my-app", "1.0")
+            .contact("email", "dev@example.com")
+            .contact("discord", "dev")
+            .comment("icon export")
+            .build()
+            .unwrap();
+        assert_eq!(
+            &*user_agent,
+            format!("my-app/1.0 (email:dev@example.com; discord:dev) (icon export) {}/{} (+{})", crate::CRATE_NAME, crate::CRATE_VERSION, crate::CRATE_REPO)
+        );
+        assert!(UserAgent::validate(&user_agent).is_ok());
+
+        assert!(UserAgent::new("my-app", "1.0").build().is_err());
+        assert!(UserAgent::new("my-app", "1.0").contact("phone", "555-0100").build().is_err());
+    }
+
+    #[test]
+    fn test_parse() {
+        let user_agent = UserAgent::parse("my-app/1.0 (dev@example.com; discord:dev) (icon export) evestaticdata/0.1.0 (+https://github.com/example/repo)").unwrap();
+        assert_eq!(user_agent.contacts(), ["dev@example.com", "discord:dev"]);
+        assert_eq!(UserAgent::parse("my-app/1.0 (discord:dev)").unwrap().contacts(), ["discord:dev"]);
+
+        assert_eq!(UserAgent::parse("  ").err(), Some(UaPolicyError::Empty));
+        assert_eq!(UserAgent::parse("dev@example.com").err(), Some(UaPolicyError::NoProduct));
+        assert_eq!(UserAgent::parse("my-app/1.0 (phone:555-0100)").err(), Some(UaPolicyError::NoContact));
+        assert_eq!(UserAgent::parse("my-app/1.0 evestaticdata/0.1.0 (+https://github.com/example/repo)").err(), Some(UaPolicyError::NoContact));
+    }
 }
 pub mod units {
     #[allow(non_camel_case_types)]
@@ -127,15 +251,155 @@ pub mod units {
         Datetime = 143,
         AU_per_Second = 144,
         ModifierRealPercent = 205,
-    }   // TODO: Port formatter function from Java, don't forget non-breaking spaces!
+    }
 
     impl EVEUnit {
         pub fn unit_id(self) -> u32 {
             self as u32
         }
 
-        pub fn format(self, _value: f64) -> String {
-            unimplemented!()
+        /// Format a value with this unit, as displayed in the EVE client (English locale)
+        ///
+        /// Numbers and unit symbols are separated by non-breaking spaces.
+        /// ID-type units (`GroupID`, `TypeID`, `AttributeID`) are formatted as the bare ID number, as resolving their name requires SDE data.
+        pub fn format(self, value: f64) -> String {
+            match self {
+                EVEUnit::Meter => with_symbol(value, 2, "m"),
+                EVEUnit::Kilogram => with_symbol(value, 2, "kg"),
+                EVEUnit::Second | EVEUnit::Seconds => with_symbol(value, 2, "s"),
+                EVEUnit::Ampere => with_symbol(value, 2, "A"),
+                EVEUnit::Kelvin => with_symbol(value, 2, "K"),
+                EVEUnit::Mol => with_symbol(value, 2, "mol"),
+                EVEUnit::Candela => with_symbol(value, 2, "cd"),
+                EVEUnit::M2 => with_symbol(value, 2, "m²"),
+                EVEUnit::M3 => with_symbol(value, 2, "m³"),
+                EVEUnit::M_per_sec => with_symbol(value, 2, "m/sec"),
+                EVEUnit::M_per_sec2 => with_symbol(value, 2, "m/sec²"),
+                EVEUnit::WaveNumber => with_symbol(value, 2, "m⁻¹"),
+                EVEUnit::Kg_per_m3 => with_symbol(value, 2, "kg/m³"),
+                EVEUnit::M3_per_kg => with_symbol(value, 2, "m³/kg"),
+                EVEUnit::A_per_m2 => with_symbol(value, 2, "A/m²"),
+                EVEUnit::A_per_m => with_symbol(value, 2, "A/m"),
+                EVEUnit::Mol_per_m3 => with_symbol(value, 2, "mol/m³"),
+                EVEUnit::Candela_per_m2 => with_symbol(value, 2, "cd/m²"),
+                EVEUnit::MassFraction => with_symbol(value, 2, "kg/kg"),
+                EVEUnit::Milliseconds => with_symbol(value / 1000.0, 2, "s"),
+                EVEUnit::Millimeters => with_symbol(value, 2, "mm"),
+                EVEUnit::MegaPascals => with_symbol(value, 2, "MPa"),
+                EVEUnit::Multiplier => with_symbol(value, 3, "x"),
+                EVEUnit::Percentage | EVEUnit::AbsolutePercent => with_symbol(value * 100.0, 2, "%"),
+                EVEUnit::Teraflops => with_symbol(value, 2, "tf"),
+                EVEUnit::MegaWatts => with_symbol(value, 2, "MW"),
+                EVEUnit::InversePercentage | EVEUnit::InverseModifierPercent => with_symbol((1.0 - value) * 100.0, 2, "%"),
+                EVEUnit::ModifierPercent => with_symbol((value - 1.0) * 100.0, 2, "%"),
+                EVEUnit::Rad_per_sec => with_symbol(value, 4, "rad/sec"),
+                EVEUnit::Hitpoints => with_symbol(value, 2, "HP"),
+                EVEUnit::GigaJoule => with_symbol(value, 2, "GJ"),
+                EVEUnit::GroupID | EVEUnit::TypeID | EVEUnit::AttributeID => format!("{}", value as i64),
+                EVEUnit::SizeClass => match value as i64 {
+                    1 => "Small".to_string(),
+                    2 => "Medium".to_string(),
+                    3 => "Large".to_string(),
+                    4 => "X-Large".to_string(),
+                    _ => format_number(value, 0),
+                },
+                EVEUnit::OreUnits | EVEUnit::Units => with_symbol(value, 2, "units"),
+                EVEUnit::Points => with_symbol(value, 2, "points"),
+                EVEUnit::RealPercent | EVEUnit::ModifierRealPercent => with_symbol(value, 2, "%"),
+                EVEUnit::ModifierRelativePercent => format!("{}{}", if value > 0.0 { "+" } else { "" }, with_symbol(value, 2, "%")),
+                EVEUnit::FittingSlots | EVEUnit::Slot => format_number(value, 0),
+                EVEUnit::Newton => with_symbol(value, 2, "N"),
+                EVEUnit::LightYear => with_symbol(value, 2, "ly"),
+                EVEUnit::Mbit_per_sec => with_symbol(value, 2, "Mbit/sec"),
+                EVEUnit::Hours => with_symbol(value, 2, "hours"),
+                EVEUnit::ISK => {
+                    let mut buf = format_number(value, 2);
+                    // ISK is always displayed with 2 decimals
+                    match buf.find('.') {
+                        Some(index) => (0..(3 - (buf.len() - index))).for_each(|_| buf.push('0')),
+                        None => buf.push_str(".00"),
+                    }
+                    buf.push_str("\u{a0}ISK");
+                    buf
+                },
+                EVEUnit::M3_per_Hour => with_symbol(value, 2, "m³/hour"),
+                EVEUnit::AU => with_symbol(value, 2, "AU"),
+                EVEUnit::Boolean => if value != 0.0 { "True".to_string() } else { "False".to_string() },
+                EVEUnit::Bonus => format!("{}{}", if value >= 0.0 { "+" } else { "" }, format_number(value, 2)),
+                EVEUnit::Level => format!("Level\u{a0}{}", format_number(value, 0)),
+                EVEUnit::Hardpoints => with_symbol(value, 0, "hardpoints"),
+                EVEUnit::Sex => match value as i64 {
+                    1 => "Male".to_string(),
+                    2 => "Unisex".to_string(),
+                    3 => "Female".to_string(),
+                    _ => format_number(value, 0),
+                },
+                EVEUnit::Datetime => format_number(value, 0),
+                EVEUnit::AU_per_Second => with_symbol(value, 2, "AU/s"),
+            }
+        }
+    }
+
+    /// Format a number with the specified unit symbol, separated by a non-breaking space
+    fn with_symbol(value: f64, max_decimals: usize, symbol: &str) -> String {
+        let mut buf = format_number(value, max_decimals);
+        buf.push('\u{a0}');
+        buf.push_str(symbol);
+        buf
+    }
+
+    /// Format a number with thousands separators, rounded to at most `max_decimals` decimals, omitting trailing zeroes
+    fn format_number(value: f64, max_decimals: usize) -> String {
+        let rounded = format!("{:.*}", max_decimals, value.abs());
+        let (integer, fraction) = rounded.split_once('.').unwrap_or((&rounded, ""));
+        let fraction = fraction.trim_end_matches('0');
+
+        let mut buf = String::with_capacity(rounded.len() + integer.len() / 3 + 1);
+        if value < 0.0 && rounded.bytes().any(|b| b.is_ascii_digit() && b != b'0') {
+            buf.push('-');
+        }
+        for (index, digit) in integer.chars().enumerate() {
+            if index > 0 && (integer.len() - index) % 3 == 0 {
+                buf.push(',');
+            }
+            buf.push(digit);
+        }
+        if !fraction.is_empty() {
+            buf.push('.');
+            buf.push_str(fraction);
+        }
+        buf
+    }
+
+    #[test]
+    fn test_format() {
+        let cases: &[(EVEUnit, f64, &str)] = &[
+            (EVEUnit::Meter, 2500.0, "2,500\u{a0}m"),
+            (EVEUnit::Milliseconds, 1500.0, "1.5\u{a0}s"),
+            (EVEUnit::M3, 0.01, "0.01\u{a0}m³"),
+            (EVEUnit::ISK, 1234567.891, "1,234,567.89\u{a0}ISK"),
+            (EVEUnit::ISK, 5.5, "5.50\u{a0}ISK"),
+            (EVEUnit::ISK, 100.0, "100.00\u{a0}ISK"),
+            (EVEUnit::Percentage, 0.25, "25\u{a0}%"),
+            (EVEUnit::InversePercentage, 0.85, "15\u{a0}%"),
+            (EVEUnit::ModifierPercent, 1.1, "10\u{a0}%"),
+            (EVEUnit::AbsolutePercent, 0.5, "50\u{a0}%"),
+            (EVEUnit::RealPercent, -5.0, "-5\u{a0}%"),
+            (EVEUnit::ModifierRelativePercent, 5.0, "+5\u{a0}%"),
+            (EVEUnit::Multiplier, 1.25, "1.25\u{a0}x"),
+            (EVEUnit::TypeID, 587.0, "587"),
+            (EVEUnit::GroupID, 25.0, "25"),
+            (EVEUnit::AttributeID, 9.0, "9"),
+            (EVEUnit::Boolean, 1.0, "True"),
+            (EVEUnit::Boolean, 0.0, "False"),
+            (EVEUnit::SizeClass, 3.0, "Large"),
+            (EVEUnit::Level, 5.0, "Level\u{a0}5"),
+            (EVEUnit::Bonus, 3.0, "+3"),
+            (EVEUnit::Hitpoints, -0.001, "0\u{a0}HP"),
+        ];
+
+        for (unit, value, expected) in cases {
+            assert_eq!(&unit.format(*value), expected, "{:?} {}", unit, value);
         }
     }
 }
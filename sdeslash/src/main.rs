@@ -1,15 +1,21 @@
 use std::collections::HashMap;
 use std::error::Error;
 use std::fs::File;
+use std::io;
+use std::io::Write;
 use std::sync::Arc;
-use std::time::{Duration};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use axum::body::{Body, Bytes};
 use axum::extract::{Query, State};
-use axum::http::{header, StatusCode};
-use axum::response::IntoResponse;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
 use axum::Router;
 use axum::routing::get;
-use evestaticdata::sde::update::SdeVersion;
-use tokio::sync::RwLock;
+use evestaticdata::sde::update::{spawn_auto_update, SdeVersion};
+use futures_util::StreamExt;
+use md5::{Digest, Md5};
+use tokio::sync::{mpsc, RwLock};
 use zipslash::parse::ParseOpts;
 use zipslash::{RepackOpts, Repacker};
 use zipslash::range_read::SliceRangeReader;
@@ -22,44 +28,50 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     let repacker = Repacker::load_archive(&SliceRangeReader(include_bytes!("./empty.zip")), &ParseOpts::default())?;
 
-    let arc = Arc::new(RwLock::new((repacker, SdeVersion::sde { buildNumber: 0, releaseDate: "".to_string() })));
-    let arc2 = arc.clone();
-
-
-    rt.spawn(async move {
-        let mut interval = tokio::time::interval(Duration::from_mins(15));
-        loop {
-            interval.tick().await;
-            if let Ok(version) = evestaticdata::sde::update::update_sde("./sde.zip") {
-                if let Ok(input) = File::open("./sde.zip") {
-                    if let Ok(repacker) = Repacker::load_archive(&input, &ParseOpts::default()) {
-                        let mut guard = arc.write().await;
-                        let old = std::mem::replace(&mut *guard, (repacker, version));
-                        drop(guard);
-                        drop(old);
-                    }
-                }
-            }
-        }
+    let state = Arc::new(AppState {
+        repacker: RwLock::new(Arc::new((repacker, SdeVersion::sde { buildNumber: 0, releaseDate: None }))),
+        last_refresh: AtomicU64::new(0),
+        repack_requests: AtomicU64::new(0),
+        bytes_served: AtomicU64::new(0),
+        repack_errors: AtomicU64::new(0),
+    });
+    let state2 = state.clone();
+
+    let _guard = rt.enter();
+    let _updater = spawn_auto_update("./sde.zip", Duration::from_mins(15), move |version| {
+        // Returning an error here makes the updater retry loading this version at the next interval
+        let input = File::open("./sde.zip")?;
+        let repacker = Repacker::load_archive(&input, &ParseOpts::default()).map_err(|err| format!("Could not load SDE archive: {}", err))?;
+        let old = std::mem::replace(&mut *state.repacker.blocking_write(), Arc::new((repacker, version)));
+        drop(old);
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or(0);
+        state.last_refresh.store(now, Ordering::Relaxed);
+        Ok(())
     });
 
-    rt.block_on(server(arc2))?;
+    rt.block_on(server(state2))?;
 
     Ok(())
 }
 
 #[derive(Debug)]
 struct AppState {
-    pub repacker: Arc<RwLock<(Repacker, SdeVersion)>>
+    /// Current SDE; Requests clone the `Arc` and release the lock before repacking, so slow clients do not hold up updates
+    pub repacker: RwLock<Arc<(Repacker, SdeVersion)>>,
+    /// Unix timestamp (seconds) of the last time the SDE was loaded, 0 if no SDE has been loaded yet
+    pub last_refresh: AtomicU64,
+    pub repack_requests: AtomicU64,
+    pub bytes_served: AtomicU64,
+    pub repack_errors: AtomicU64,
 }
 
-async fn server(repacker: Arc<RwLock<(Repacker, SdeVersion)>>) -> Result<(), Box<dyn Error>>{
-    let state = AppState { repacker };
-
+async fn server(state: Arc<AppState>) -> Result<(), Box<dyn Error>>{
     let router = Router::new()
         .route("/", get(sde))
         .route("/version/", get(sde_version))
-        .with_state(Arc::new(state));
+        .route("/health", get(health))
+        .route("/metrics", get(metrics))
+        .with_state(state);
 
     axum::serve(
         tokio::net::TcpListener::bind("0.0.0.0:3000").await?,
@@ -71,28 +83,136 @@ async fn server(repacker: Arc<RwLock<(Repacker, SdeVersion)>>) -> Result<(), Box
 }
 
 // basic handler that responds with a static string
-const BUFFER_PREALLOC_SIZE: usize = 4 * 1024 * 1024;
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+const STREAM_CHANNEL_SIZE: usize = 4;
 const EXPLAINER_MESSAGE: &'static [u8] = include_bytes!("./explainer.txt");
 const REPACK_OPTS: RepackOpts = RepackOpts::const_default().skip_missing_files(true);
 
-async fn sde(State(state): State<Arc<AppState>>, Query(parameters): Query<HashMap<String, String>>) -> impl IntoResponse {
+async fn sde(State(state): State<Arc<AppState>>, headers: HeaderMap, Query(parameters): Query<HashMap<String, String>>) -> Response {
     if parameters.len() == 0 {
-        (StatusCode::BAD_REQUEST, [(header::CONTENT_TYPE, "text/plain"), (header::CONTENT_DISPOSITION, "inline")], Vec::from(EXPLAINER_MESSAGE))
+        (StatusCode::BAD_REQUEST, [(header::CONTENT_TYPE, "text/plain"), (header::CONTENT_DISPOSITION, "inline")], Vec::from(EXPLAINER_MESSAGE)).into_response()
     } else {
-        let filenames = Vec::from_iter(parameters.keys());  // TODO: Make Repacker support iterator input
-        let mut buffer = Vec::with_capacity(BUFFER_PREALLOC_SIZE);
-        match state.repacker.read().await.0.repack(&mut buffer, &filenames, &REPACK_OPTS) {
-            Ok(_) => (StatusCode::OK, [(header::CONTENT_TYPE, "application/zip"), (header::CONTENT_DISPOSITION, "attachment; filename=\"sde_repack.zip\"")], buffer),
-            Err(err) => (
+        state.repack_requests.fetch_add(1, Ordering::Relaxed);
+        let mut filenames = Vec::from_iter(parameters.keys());  // TODO: Make Repacker support iterator input
+        filenames.sort();
+
+        let repacker = Arc::clone(&*state.repacker.read().await);
+        let etag = repack_etag(repacker.1.build_number(), &filenames);
+        let if_none_match = headers.get(header::IF_NONE_MATCH).and_then(|value| value.to_str().ok());
+        if if_none_match.is_some_and(|value| etag_matches(value, &etag)) {
+            return (StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response();
+        }
+
+        // Repack on the blocking thread pool, streaming the output into the response body
+        let filenames = filenames.into_iter().cloned().collect::<Vec<String>>();
+        let (sender, mut receiver) = mpsc::channel(STREAM_CHANNEL_SIZE);
+        let repack_state = state.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut writer = ChannelWriter { buffer: Vec::with_capacity(STREAM_CHUNK_SIZE), sender };
+            let result = repacker.0.repack(&mut writer, &filenames, &REPACK_OPTS)
+                .map_err(|err| io::Error::other(err.to_string()))
+                .and_then(|_| writer.flush());
+            if let Err(err) = result {
+                repack_state.repack_errors.fetch_add(1, Ordering::Relaxed);
+                let _ = writer.sender.blocking_send(Err(err));
+            }
+        });
+
+        // Errors usually occur before the first chunk is written, and can still be reported with an error status
+        match receiver.recv().await {
+            Some(Err(err)) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 [(header::CONTENT_TYPE, "text/plain"), (header::CONTENT_DISPOSITION, "inline")],
                 format!("{}", err).into_bytes()
-            ),
+            ).into_response(),
+            first_chunk => {
+                let remaining = futures_util::stream::unfold(receiver, |mut receiver| async move {
+                    receiver.recv().await.map(|chunk| (chunk, receiver))
+                });
+                let stream = futures_util::stream::iter(first_chunk).chain(remaining)
+                    .inspect(move |chunk| if let Ok(bytes) = chunk {
+                        state.bytes_served.fetch_add(bytes.len() as u64, Ordering::Relaxed);
+                    });
+                (
+                    StatusCode::OK,
+                    [(header::CONTENT_TYPE, "application/zip"), (header::CONTENT_DISPOSITION, "attachment; filename=\"sde_repack.zip\""), (header::ETAG, etag.as_str())],
+                    Body::from_stream(stream)
+                ).into_response()
+            }
+        }
+    }
+}
+
+/// Writer that sends its output to a response body stream, in chunks of [`STREAM_CHUNK_SIZE`]
+struct ChannelWriter {
+    buffer: Vec<u8>,
+    sender: mpsc::Sender<Result<Bytes, io::Error>>,
+}
+
+impl Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        if self.buffer.len() >= STREAM_CHUNK_SIZE {
+            self.flush()?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.buffer.is_empty() {
+            let chunk = std::mem::replace(&mut self.buffer, Vec::with_capacity(STREAM_CHUNK_SIZE));
+            // Receiver is dropped if the client disconnects, abort the repack
+            self.sender.blocking_send(Ok(Bytes::from(chunk))).map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
         }
+        Ok(())
     }
 }
 
+/// ETag for a repack of the (sorted) filenames from the specified SDE build
+///
+/// Uses md5 rather than std's hasher, which is not stable across Rust versions, so ETags stay valid across server restarts and rebuilds
+fn repack_etag(build_number: u32, filenames: &[&String]) -> String {
+    let mut md5 = Md5::new();
+    for filename in filenames {
+        md5.update(filename.as_bytes());
+        md5.update(b"\0");
+    }
+    format!("\"{}-{:032x}\"", build_number, u128::from_be_bytes(md5.finalize().into()))
+}
+
+/// Whether an `If-None-Match` header value matches the ETag
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match.split(',')
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == etag)
+}
+
 async fn sde_version(State(state): State<Arc<AppState>>) -> impl IntoResponse {
     let SdeVersion::sde { buildNumber, .. } = state.repacker.read().await.1;
     (StatusCode::OK, [(header::CONTENT_TYPE, "text/plain")], buildNumber.to_string())
-}
\ No newline at end of file
+}
+
+async fn health(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let build_number = state.repacker.read().await.1.build_number();
+    let last_refresh = match state.last_refresh.load(Ordering::Relaxed) {
+        0 => "null".to_string(),
+        timestamp => timestamp.to_string()
+    };
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "application/json")],
+        format!("{{\"status\":\"ok\",\"buildNumber\":{},\"lastRefresh\":{}}}", build_number, last_refresh)
+    )
+}
+
+async fn metrics(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let mut body = String::new();
+    for (name, help, value) in [
+        ("sdeslash_repack_requests_total", "Total number of repack requests", &state.repack_requests),
+        ("sdeslash_bytes_served_total", "Total bytes of repacked zip files served", &state.bytes_served),
+        ("sdeslash_repack_errors_total", "Total number of failed repacks", &state.repack_errors),
+    ] {
+        body.push_str(&format!("# HELP {} {}\n# TYPE {} counter\n{} {}\n", name, help, name, name, value.load(Ordering::Relaxed)));
+    }
+    (StatusCode::OK, [(header::CONTENT_TYPE, "text/plain; version=0.0.4")], body)
+}